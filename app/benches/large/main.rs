@@ -0,0 +1,223 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fast_transpose::{transpose_plane, transpose_rgba, FlipMode, FlopMode};
+use yuv_sys::RotationMode_kRotate90;
+
+// Sizes where destination rows are far apart enough that TLB misses dominate,
+// "Bands" runs are the untiled baseline to compare tiled traversal against.
+const WIDTH: usize = 16384;
+const HEIGHT: usize = 16384;
+
+/// Source rows handed to each call of the band baseline, one row of 16x16 blocks.
+const BAND: usize = 16;
+
+/// Transposes `BAND` source rows per call, so every band of blocks is walked across the
+/// full width before the next one starts, as executors did before tiling.
+fn transpose_in_bands(
+    input: &[u8],
+    output: &mut [u8],
+    channels: usize,
+    transposer: fn(
+        &[u8],
+        usize,
+        &mut [u8],
+        usize,
+        usize,
+        usize,
+        FlipMode,
+        FlopMode,
+    ) -> Result<(), fast_transpose::TransposeError>,
+) {
+    for y in (0..HEIGHT).step_by(BAND) {
+        let src = &input[y * WIDTH * channels..(y + BAND) * WIDTH * channels];
+        let dst_start = y * channels;
+        let dst_end = dst_start + (WIDTH - 1) * HEIGHT * channels + BAND * channels;
+        transposer(
+            src,
+            WIDTH * channels,
+            &mut output[dst_start..dst_end],
+            HEIGHT * channels,
+            WIDTH,
+            BAND,
+            FlipMode::NoFlip,
+            FlopMode::Flop,
+        )
+        .unwrap();
+    }
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("16K");
+    group.sample_size(10);
+
+    let plane = (0..WIDTH * HEIGHT)
+        .map(|x| (x % 251) as u8)
+        .collect::<Vec<_>>();
+    let mut transposed = vec![0u8; WIDTH * HEIGHT];
+
+    group.bench_function("FT Rotate 90: Plane u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_plane(
+                &plane,
+                WIDTH,
+                &mut transposed,
+                HEIGHT,
+                WIDTH,
+                HEIGHT,
+                FlipMode::NoFlip,
+                FlopMode::NoFlop,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("FT Rotate 270: Plane u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_plane(
+                &plane,
+                WIDTH,
+                &mut transposed,
+                HEIGHT,
+                WIDTH,
+                HEIGHT,
+                FlipMode::Flip,
+                FlopMode::Flop,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("FT Transpose: Plane u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_plane(
+                &plane,
+                WIDTH,
+                &mut transposed,
+                HEIGHT,
+                WIDTH,
+                HEIGHT,
+                FlipMode::NoFlip,
+                FlopMode::Flop,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("FT Transpose Bands: Plane u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_in_bands(&plane, &mut transposed, 1, transpose_plane);
+        });
+    });
+
+    group.bench_function("Libyuv Rotate 90: Plane u8 (16384x16384)", |b| {
+        b.iter(|| unsafe {
+            yuv_sys::rs_RotatePlane90(
+                plane.as_ptr(),
+                WIDTH as i32,
+                transposed.as_mut_ptr(),
+                HEIGHT as i32,
+                WIDTH as i32,
+                HEIGHT as i32,
+            );
+        });
+    });
+
+    group.bench_function("Transpose: Plane u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose::transpose(&plane, &mut transposed, WIDTH, HEIGHT);
+        });
+    });
+
+    drop(transposed);
+    drop(plane);
+
+    let rgba = (0..WIDTH * HEIGHT * 4)
+        .map(|x| (x % 251) as u8)
+        .collect::<Vec<_>>();
+    let mut transposed = vec![0u8; WIDTH * HEIGHT * 4];
+
+    group.bench_function("FT Rotate 90: Rgba u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_rgba(
+                &rgba,
+                WIDTH * 4,
+                &mut transposed,
+                HEIGHT * 4,
+                WIDTH,
+                HEIGHT,
+                FlipMode::NoFlip,
+                FlopMode::NoFlop,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("FT Transpose: Rgba u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_rgba(
+                &rgba,
+                WIDTH * 4,
+                &mut transposed,
+                HEIGHT * 4,
+                WIDTH,
+                HEIGHT,
+                FlipMode::NoFlip,
+                FlopMode::Flop,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("FT Transpose Bands: Rgba u8 (16384x16384)", |b| {
+        b.iter(|| {
+            transpose_in_bands(&rgba, &mut transposed, 4, transpose_rgba);
+        });
+    });
+
+    group.bench_function("Libyuv Rotate 90: Rgba u8 (16384x16384)", |b| {
+        b.iter(|| unsafe {
+            yuv_sys::rs_ARGBRotate(
+                rgba.as_ptr(),
+                WIDTH as i32 * 4,
+                transposed.as_mut_ptr(),
+                HEIGHT as i32 * 4,
+                WIDTH as i32,
+                HEIGHT as i32,
+                RotationMode_kRotate90,
+            );
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -29,8 +29,8 @@
 
 use criterion::{criterion_group, criterion_main, Criterion};
 use fast_transpose::{
-    flip_rgba, rotate180_rgba, transpose_rgba, transpose_rgba16, transpose_rgba_f32, FlipMode,
-    FlopMode,
+    flip_rgba, flop_rgba_alpha, rotate180_rgba, transpose_rgba, transpose_rgba16,
+    transpose_rgba_alpha, transpose_rgba_f32, AlphaMode, FlipMode, FlopMode,
 };
 use image::imageops::FilterType;
 use image::{DynamicImage, ImageReader};
@@ -77,6 +77,55 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     });
 
+    c.bench_function("FT Rotate 90 Premultiply: Rgba u8", |b| {
+        let mut transposed = vec![0u8; dimensions.0 as usize * dimensions.1 as usize * components];
+        b.iter(|| {
+            transpose_rgba_alpha(
+                &img,
+                dimensions.0 as usize * 4,
+                &mut transposed,
+                dimensions.1 as usize * 4,
+                dimensions.0 as usize,
+                dimensions.1 as usize,
+                AlphaMode::Premultiply,
+                FlipMode::NoFlip,
+                FlopMode::NoFlop,
+            )
+            .unwrap();
+        });
+    });
+
+    c.bench_function("FT Rotate 90 then Premultiply pass: Rgba u8", |b| {
+        let mut transposed = vec![0u8; dimensions.0 as usize * dimensions.1 as usize * components];
+        let mut premultiplied =
+            vec![0u8; dimensions.0 as usize * dimensions.1 as usize * components];
+        b.iter(|| {
+            transpose_rgba(
+                &img,
+                dimensions.0 as usize * 4,
+                &mut transposed,
+                dimensions.1 as usize * 4,
+                dimensions.0 as usize,
+                dimensions.1 as usize,
+                FlipMode::NoFlip,
+                FlopMode::NoFlop,
+            )
+            .unwrap();
+            // Second pass on the same SIMD alpha kernels, reordering rows of a flop costs
+            // no more than a straight copy.
+            flop_rgba_alpha(
+                &transposed,
+                dimensions.1 as usize * 4,
+                &mut premultiplied,
+                dimensions.1 as usize * 4,
+                dimensions.1 as usize,
+                dimensions.0 as usize,
+                AlphaMode::Premultiply,
+            )
+            .unwrap();
+        });
+    });
+
     let k_img = ImageReader::open("../assets/sonderland.jpg")
         .unwrap()
         .decode()
@@ -0,0 +1,101 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::utils::swizzle_mask_samples;
+use std::arch::x86_64::*;
+
+/// Transposes 4x4 pixels of four floats as 2x2 blocks of two `__m256` rows, `shuffle` is
+/// applied to every transposed row on the way.
+#[inline(always)]
+unsafe fn avx_transpose_f32x4_4x4_impl<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+    shuffle: impl Fn(__m256) -> __m256,
+) {
+    for y in (0..4).step_by(2) {
+        let (src_y0, src_y1) = if FLIP { (3 - y, 2 - y) } else { (y, y + 1) };
+        for x in (0..4).step_by(2) {
+            let row0 = _mm256_loadu_ps(src.get_unchecked(src_y0 * src_stride + x * 4..).as_ptr());
+            let row1 = _mm256_loadu_ps(src.get_unchecked(src_y1 * src_stride + x * 4..).as_ptr());
+
+            let v0 = _mm256_permute2f128_ps::<0x20>(row0, row1);
+            let v1 = _mm256_permute2f128_ps::<0x31>(row0, row1);
+
+            let (dst_y0, dst_y1) = if FLOP { (x, x + 1) } else { (3 - x, 2 - x) };
+            _mm256_storeu_ps(
+                dst.get_unchecked_mut(dst_y0 * dst_stride + y * 4..)
+                    .as_mut_ptr(),
+                shuffle(v0),
+            );
+            _mm256_storeu_ps(
+                dst.get_unchecked_mut(dst_y1 * dst_stride + y * 4..)
+                    .as_mut_ptr(),
+                shuffle(v1),
+            );
+        }
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx2_transpose_f32x4_4x4<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        avx_transpose_f32x4_4x4_impl::<FLOP, FLIP>(src, src_stride, dst, dst_stride, |v| v);
+    }
+}
+
+/// Same as [avx2_transpose_f32x4_4x4] but also reorders channels of every pixel by `KEY`
+/// from [crate::ChannelOrder] with one in-lane shuffle per row.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx2_transpose_f32x4_4x4_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        let mask = const { swizzle_mask_samples(KEY, 4) };
+        let mask = _mm256_broadcastsi128_si256(_mm_loadu_si128(mask.as_ptr() as *const __m128i));
+        avx_transpose_f32x4_4x4_impl::<FLOP, FLIP>(src, src_stride, dst, dst_stride, |v| {
+            _mm256_castsi256_ps(_mm256_shuffle_epi8(_mm256_castps_si256(v), mask))
+        });
+    }
+}
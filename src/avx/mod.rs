@@ -27,11 +27,27 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 mod f32x2_4x4;
+mod f32x4_4x4;
+mod premultiply;
+mod reverse;
+mod u16_16x16;
+mod u16x3_4x4;
 mod u16x4_4x4;
+mod u8_16x16;
+mod u8x2_16x16;
+mod u8x3_8x8;
 mod x8_f32;
 mod x8_u32;
 
 pub(crate) use f32x2_4x4::avx2_transpose_f32x2_4x4;
-pub(crate) use u16x4_4x4::avx2_transpose_u16x4_4x4;
+pub(crate) use f32x4_4x4::{avx2_transpose_f32x4_4x4, avx2_transpose_f32x4_4x4_swizzled};
+pub(crate) use premultiply::{avx2_premultiply_u8, avx2_unpremultiply_u8};
+pub(crate) use reverse::avx2_reverse_pixels;
+pub(crate) use u16_16x16::avx2_transpose_u16_16x16;
+pub(crate) use u16x3_4x4::avx2_transpose_u16x3_4x4;
+pub(crate) use u16x4_4x4::{avx2_transpose_u16x4_4x4, avx2_transpose_u16x4_4x4_swizzled};
+pub(crate) use u8_16x16::avx2_transpose_u8_16x16;
+pub(crate) use u8x2_16x16::avx2_transpose_u8x2_16x16;
+pub(crate) use u8x3_8x8::avx2_transpose_u8x3_8x8;
 pub(crate) use x8_f32::avx_transpose_8x8_f32;
-pub(crate) use x8_u32::avx_transpose_8x8_u32;
+pub(crate) use x8_u32::{avx_transpose_8x8_u32, avx_transpose_8x8_u32_swizzled};
@@ -0,0 +1,139 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::x86_64::*;
+
+/// Premultiplies color of RGBA8 pixels in runs of 8, see [crate::sse::sse_premultiply_u8].
+/// Returns how many pixels were written, pixels land in reverse order when `REVERSE` is set.
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx2_premultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 8;
+    unsafe {
+        let alpha_shuffle = _mm256_setr_epi8(
+            3, 3, 3, -1, 7, 7, 7, -1, 11, 11, 11, -1, 15, 15, 15, -1, 3, 3, 3, -1, 7, 7, 7, -1, 11,
+            11, 11, -1, 15, 15, 15, -1,
+        );
+        let alpha_lane = _mm256_set1_epi32(0xFF00_0000u32 as i32);
+        let rounding = _mm256_set1_epi16(128);
+        let div_255 = _mm256_set1_epi16(257);
+        let reverse = _mm256_setr_epi32(7, 6, 5, 4, 3, 2, 1, 0);
+        let zeros = _mm256_setzero_si256();
+        for i in 0..chunks {
+            let v = _mm256_loadu_si256(src.get_unchecked(i * 32..).as_ptr() as *const __m256i);
+            // Alpha lane is scaled by 255 so it survives the division unchanged.
+            let a = _mm256_or_si256(_mm256_shuffle_epi8(v, alpha_shuffle), alpha_lane);
+            let lo = _mm256_mullo_epi16(
+                _mm256_unpacklo_epi8(v, zeros),
+                _mm256_unpacklo_epi8(a, zeros),
+            );
+            let hi = _mm256_mullo_epi16(
+                _mm256_unpackhi_epi8(v, zeros),
+                _mm256_unpackhi_epi8(a, zeros),
+            );
+            let lo = _mm256_mulhi_epu16(_mm256_add_epi16(lo, rounding), div_255);
+            let hi = _mm256_mulhi_epu16(_mm256_add_epi16(hi, rounding), div_255);
+            let mut v = _mm256_packus_epi16(lo, hi);
+            let dst_px = if REVERSE {
+                v = _mm256_permutevar8x32_epi32(v, reverse);
+                pixels - 8 * (i + 1)
+            } else {
+                8 * i
+            };
+            _mm256_storeu_si256(
+                dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr() as *mut __m256i,
+                v,
+            );
+        }
+    }
+    chunks * 8
+}
+
+/// Divides color of two pixels widened to `[r, g, b, a]` lanes by their alpha, see
+/// [crate::sse::sse_unpremultiply_u8].
+#[inline(always)]
+unsafe fn avx_unpremultiply_px2(p: __m256i) -> __m256i {
+    let a = _mm256_shuffle_epi32::<0xFF>(p);
+    let n = _mm256_add_epi32(
+        _mm256_sub_epi32(_mm256_slli_epi32::<8>(p), p),
+        _mm256_srli_epi32::<1>(a),
+    );
+    let af = _mm256_cvtepi32_ps(a);
+    let recip = _mm256_rcp_ps(af);
+    let recip = _mm256_mul_ps(
+        recip,
+        _mm256_sub_ps(_mm256_set1_ps(2.), _mm256_mul_ps(af, recip)),
+    );
+    let q = _mm256_cvttps_epi32(_mm256_mul_ps(_mm256_cvtepi32_ps(n), recip));
+    let r = _mm256_sub_epi32(n, _mm256_mullo_epi32(q, a));
+    // Masks are -1 where set, so adding one steps down and subtracting one steps up.
+    let q = _mm256_add_epi32(q, _mm256_cmpgt_epi32(_mm256_setzero_si256(), r));
+    let q = _mm256_sub_epi32(
+        q,
+        _mm256_cmpgt_epi32(r, _mm256_sub_epi32(a, _mm256_set1_epi32(1))),
+    );
+    let q = _mm256_min_epi32(q, _mm256_set1_epi32(255));
+    let q = _mm256_andnot_si256(_mm256_cmpeq_epi32(a, _mm256_setzero_si256()), q);
+    _mm256_blend_epi32::<0b1000_1000>(q, p)
+}
+
+/// Unpremultiplies color of RGBA8 pixels in runs of 8, see [avx_unpremultiply_px2].
+/// Returns how many pixels were written, pixels land in reverse order when `REVERSE` is set.
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx2_unpremultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 8;
+    unsafe {
+        // Packing interleaves pixels of the two lanes, this puts them back in order.
+        let order = if REVERSE {
+            _mm256_setr_epi32(7, 3, 6, 2, 5, 1, 4, 0)
+        } else {
+            _mm256_setr_epi32(0, 4, 1, 5, 2, 6, 3, 7)
+        };
+        for i in 0..chunks {
+            let src = src.get_unchecked(i * 32..);
+            let load = |offset: usize| {
+                avx_unpremultiply_px2(_mm256_cvtepu8_epi32(_mm_loadl_epi64(
+                    src.get_unchecked(offset..).as_ptr() as *const __m128i,
+                )))
+            };
+            let v = _mm256_packus_epi16(
+                _mm256_packus_epi32(load(0), load(8)),
+                _mm256_packus_epi32(load(16), load(24)),
+            );
+            let v = _mm256_permutevar8x32_epi32(v, order);
+            let dst_px = if REVERSE { pixels - 8 * (i + 1) } else { 8 * i };
+            _mm256_storeu_si256(
+                dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr() as *mut __m256i,
+                v,
+            );
+        }
+    }
+    chunks * 8
+}
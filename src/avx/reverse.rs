@@ -0,0 +1,101 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::reverse_masks::ReverseMasks;
+use crate::sse::sse_reverse_pixels;
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn avx2_reverse_reg(v: __m256i, mask: __m256i) -> __m256i {
+    _mm256_permute4x64_epi64::<0x4E>(_mm256_shuffle_epi8(v, mask))
+}
+
+/// Reverses order of `PX` bytes wide pixels from `src` into `dst`.
+///
+/// Pixels that do not fit in a 128-bit lane evenly are processed by SSE kernel.
+#[inline(always)]
+pub(crate) fn avx2_reverse_pixels<const PX: usize>(src: &[u8], dst: &mut [u8]) {
+    if 16 % PX != 0 {
+        sse_reverse_pixels::<PX>(src, dst);
+        return;
+    }
+
+    assert_eq!(src.len(), dst.len());
+    let width = src.len() / PX;
+    let pixels_per_reg = 32 / PX;
+    let mut x = 0usize;
+
+    unsafe {
+        let mask = _mm256_broadcastsi128_si256(_mm_loadu_si128(
+            ReverseMasks::<PX>::SINGLE.as_ptr() as *const __m128i,
+        ));
+
+        while x + 4 * pixels_per_reg <= width {
+            let src_ptr = src.get_unchecked(x * PX..).as_ptr();
+            let row0 = _mm256_loadu_si256(src_ptr as *const __m256i);
+            let row1 = _mm256_loadu_si256(src_ptr.add(32) as *const __m256i);
+            let row2 = _mm256_loadu_si256(src_ptr.add(64) as *const __m256i);
+            let row3 = _mm256_loadu_si256(src_ptr.add(96) as *const __m256i);
+
+            let dst_ptr = dst
+                .get_unchecked_mut((width - x - 4 * pixels_per_reg) * PX..)
+                .as_mut_ptr();
+            _mm256_storeu_si256(dst_ptr as *mut __m256i, avx2_reverse_reg(row3, mask));
+            _mm256_storeu_si256(
+                dst_ptr.add(32) as *mut __m256i,
+                avx2_reverse_reg(row2, mask),
+            );
+            _mm256_storeu_si256(
+                dst_ptr.add(64) as *mut __m256i,
+                avx2_reverse_reg(row1, mask),
+            );
+            _mm256_storeu_si256(
+                dst_ptr.add(96) as *mut __m256i,
+                avx2_reverse_reg(row0, mask),
+            );
+
+            x += 4 * pixels_per_reg;
+        }
+
+        while x + pixels_per_reg <= width {
+            let row0 = _mm256_loadu_si256(src.get_unchecked(x * PX..).as_ptr() as *const __m256i);
+            _mm256_storeu_si256(
+                dst.get_unchecked_mut((width - x - pixels_per_reg) * PX..)
+                    .as_mut_ptr() as *mut __m256i,
+                avx2_reverse_reg(row0, mask),
+            );
+            x += pixels_per_reg;
+        }
+
+        sse_reverse_pixels::<PX>(
+            src.get_unchecked(x * PX..),
+            dst.get_unchecked_mut(..(width - x) * PX),
+        );
+    }
+}
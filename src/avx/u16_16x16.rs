@@ -0,0 +1,119 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn transpose_8x8_lanes(v: &[__m256i]) -> [__m256i; 8] {
+    let a0 = _mm256_unpacklo_epi16(v[0], v[1]);
+    let a1 = _mm256_unpackhi_epi16(v[0], v[1]);
+    let a2 = _mm256_unpacklo_epi16(v[2], v[3]);
+    let a3 = _mm256_unpackhi_epi16(v[2], v[3]);
+    let a4 = _mm256_unpacklo_epi16(v[4], v[5]);
+    let a5 = _mm256_unpackhi_epi16(v[4], v[5]);
+    let a6 = _mm256_unpacklo_epi16(v[6], v[7]);
+    let a7 = _mm256_unpackhi_epi16(v[6], v[7]);
+
+    let b0 = _mm256_unpacklo_epi32(a0, a2);
+    let b1 = _mm256_unpackhi_epi32(a0, a2);
+    let b2 = _mm256_unpacklo_epi32(a1, a3);
+    let b3 = _mm256_unpackhi_epi32(a1, a3);
+    let b4 = _mm256_unpacklo_epi32(a4, a6);
+    let b5 = _mm256_unpackhi_epi32(a4, a6);
+    let b6 = _mm256_unpacklo_epi32(a5, a7);
+    let b7 = _mm256_unpackhi_epi32(a5, a7);
+
+    [
+        _mm256_unpacklo_epi64(b0, b4),
+        _mm256_unpackhi_epi64(b0, b4),
+        _mm256_unpacklo_epi64(b1, b5),
+        _mm256_unpackhi_epi64(b1, b5),
+        _mm256_unpacklo_epi64(b2, b6),
+        _mm256_unpackhi_epi64(b2, b6),
+        _mm256_unpacklo_epi64(b3, b7),
+        _mm256_unpackhi_epi64(b3, b7),
+    ]
+}
+
+/// Transposes 16 rows of sixteen 16-bit lanes, FLIP reverses the order of the rows
+/// before transposition.
+#[inline(always)]
+pub(crate) unsafe fn avx2_transpose_u16_16x16_impl<const FLIP: bool>(
+    mut v: [__m256i; 16],
+) -> [__m256i; 16] {
+    if FLIP {
+        v.reverse();
+    }
+    // Low lanes carry columns 0..8, high lanes columns 8..16
+    let top = transpose_8x8_lanes(&v[0..8]);
+    let bottom = transpose_8x8_lanes(&v[8..16]);
+    [
+        _mm256_permute2x128_si256::<0x20>(top[0], bottom[0]),
+        _mm256_permute2x128_si256::<0x20>(top[1], bottom[1]),
+        _mm256_permute2x128_si256::<0x20>(top[2], bottom[2]),
+        _mm256_permute2x128_si256::<0x20>(top[3], bottom[3]),
+        _mm256_permute2x128_si256::<0x20>(top[4], bottom[4]),
+        _mm256_permute2x128_si256::<0x20>(top[5], bottom[5]),
+        _mm256_permute2x128_si256::<0x20>(top[6], bottom[6]),
+        _mm256_permute2x128_si256::<0x20>(top[7], bottom[7]),
+        _mm256_permute2x128_si256::<0x31>(top[0], bottom[0]),
+        _mm256_permute2x128_si256::<0x31>(top[1], bottom[1]),
+        _mm256_permute2x128_si256::<0x31>(top[2], bottom[2]),
+        _mm256_permute2x128_si256::<0x31>(top[3], bottom[3]),
+        _mm256_permute2x128_si256::<0x31>(top[4], bottom[4]),
+        _mm256_permute2x128_si256::<0x31>(top[5], bottom[5]),
+        _mm256_permute2x128_si256::<0x31>(top[6], bottom[6]),
+        _mm256_permute2x128_si256::<0x31>(top[7], bottom[7]),
+    ]
+}
+
+#[inline(always)]
+pub(crate) fn avx2_transpose_u16_16x16<const FLOP: bool, const FLIP: bool>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let mut rows = [_mm256_setzero_si256(); 16];
+        for (i, row) in rows.iter_mut().enumerate() {
+            *row = _mm256_loadu_si256(src.get_unchecked(i * src_stride..).as_ptr() as *const _);
+        }
+
+        let cols = avx2_transpose_u16_16x16_impl::<FLIP>(rows);
+
+        for (k, v) in cols.into_iter().enumerate() {
+            let y = if FLOP { k } else { 15 - k };
+            _mm256_storeu_si256(
+                dst.get_unchecked_mut(y * dst_stride..).as_mut_ptr() as *mut _,
+                v,
+            );
+        }
+    }
+}
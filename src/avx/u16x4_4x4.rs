@@ -28,6 +28,7 @@
  */
 
 use crate::sse::_mm_shuffle;
+use crate::utils::swizzle_mask_samples;
 use std::arch::x86_64::*;
 
 #[inline(always)]
@@ -72,6 +73,34 @@ pub(crate) unsafe fn avx_transpose_u64_4x4_impl<const FLIP: bool>(
     }
 }
 
+#[inline(always)]
+unsafe fn avx_load_u16x4_4x4(
+    src: &[u16],
+    src_stride: usize,
+) -> (__m256i, __m256i, __m256i, __m256i) {
+    (
+        _mm256_loadu_si256(src.get_unchecked(0..).as_ptr() as *const _),
+        _mm256_loadu_si256(src.get_unchecked(src_stride..).as_ptr() as *const _),
+        _mm256_loadu_si256(src.get_unchecked(2 * src_stride..).as_ptr() as *const _),
+        _mm256_loadu_si256(src.get_unchecked(3 * src_stride..).as_ptr() as *const _),
+    )
+}
+
+#[inline(always)]
+unsafe fn avx_store_u16x4_4x4<const FLOP: bool>(
+    dst: &mut [u16],
+    dst_stride: usize,
+    v0: (__m256i, __m256i, __m256i, __m256i),
+) {
+    for (i, row) in [v0.0, v0.1, v0.2, v0.3].into_iter().enumerate() {
+        let dst_y = if FLOP { i } else { 3 - i };
+        _mm256_storeu_si256(
+            dst.get_unchecked_mut(dst_y * dst_stride..).as_mut_ptr() as *mut _,
+            row,
+        );
+    }
+}
+
 #[inline]
 #[target_feature(enable = "avx2")]
 pub(crate) fn avx2_transpose_u16x4_4x4<const FLOP: bool, const FLIP: bool>(
@@ -81,41 +110,38 @@ pub(crate) fn avx2_transpose_u16x4_4x4<const FLOP: bool, const FLIP: bool>(
     dst_stride: usize,
 ) {
     unsafe {
-        let row0 = _mm256_loadu_si256(src.get_unchecked(0..).as_ptr() as *const _);
-        let row1 = _mm256_loadu_si256(src.get_unchecked(src_stride..).as_ptr() as *const _);
-        let row2 = _mm256_loadu_si256(src.get_unchecked(2 * src_stride..).as_ptr() as *const _);
-        let row3 = _mm256_loadu_si256(src.get_unchecked(3 * src_stride..).as_ptr() as *const _);
-
-        let v0 = avx_transpose_u64_4x4_impl::<FLIP>((row0, row1, row2, row3));
+        let v0 = avx_transpose_u64_4x4_impl::<FLIP>(avx_load_u16x4_4x4(src, src_stride));
+        avx_store_u16x4_4x4::<FLOP>(dst, dst_stride, v0);
+    }
+}
 
-        if FLOP {
-            _mm256_storeu_si256(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.0);
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v0.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.2,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.3,
-            );
-        } else {
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.0,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v0.2,
-            );
-            _mm256_storeu_si256(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.3);
-        }
+/// Same as [avx2_transpose_u16x4_4x4] but also reorders channels of every 4 channel 16-bit
+/// pixel by `KEY` from [crate::ChannelOrder] with one in-lane shuffle per row.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx2_transpose_u16x4_4x4_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let v0 = avx_transpose_u64_4x4_impl::<FLIP>(avx_load_u16x4_4x4(src, src_stride));
+        let mask = const { swizzle_mask_samples(KEY, 2) };
+        let mask = _mm256_broadcastsi128_si256(_mm_loadu_si128(mask.as_ptr() as *const __m128i));
+        avx_store_u16x4_4x4::<FLOP>(
+            dst,
+            dst_stride,
+            (
+                _mm256_shuffle_epi8(v0.0, mask),
+                _mm256_shuffle_epi8(v0.1, mask),
+                _mm256_shuffle_epi8(v0.2, mask),
+                _mm256_shuffle_epi8(v0.3, mask),
+            ),
+        );
     }
 }
@@ -0,0 +1,127 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn load_rows<const FLIP: bool>(src: &[u8], src_stride: usize, i: usize) -> __m256i {
+    // Rows `i` and `i + 8` share a register, so in-lane unpacks handle both halves at once
+    let (lo, hi) = if FLIP { (15 - i, 7 - i) } else { (i, i + 8) };
+    let lo = _mm_loadu_si128(src.get_unchecked(lo * src_stride..).as_ptr() as *const _);
+    let hi = _mm_loadu_si128(src.get_unchecked(hi * src_stride..).as_ptr() as *const _);
+    _mm256_setr_m128i(lo, hi)
+}
+
+#[inline(always)]
+unsafe fn store_rows<const FLOP: bool>(dst: &mut [u8], dst_stride: usize, k: usize, v: __m256i) {
+    let (lo, hi) = if FLOP {
+        (2 * k, 2 * k + 1)
+    } else {
+        (15 - 2 * k, 14 - 2 * k)
+    };
+    _mm_storeu_si128(
+        dst.get_unchecked_mut(lo * dst_stride..).as_mut_ptr() as *mut _,
+        _mm256_castsi256_si128(v),
+    );
+    _mm_storeu_si128(
+        dst.get_unchecked_mut(hi * dst_stride..).as_mut_ptr() as *mut _,
+        _mm256_extracti128_si256::<1>(v),
+    );
+}
+
+#[inline(always)]
+unsafe fn avx2_transpose_u8_16x16_impl(v: [__m256i; 8]) -> [__m256i; 8] {
+    let a0 = _mm256_unpacklo_epi8(v[0], v[1]);
+    let a1 = _mm256_unpackhi_epi8(v[0], v[1]);
+    let a2 = _mm256_unpacklo_epi8(v[2], v[3]);
+    let a3 = _mm256_unpackhi_epi8(v[2], v[3]);
+    let a4 = _mm256_unpacklo_epi8(v[4], v[5]);
+    let a5 = _mm256_unpackhi_epi8(v[4], v[5]);
+    let a6 = _mm256_unpacklo_epi8(v[6], v[7]);
+    let a7 = _mm256_unpackhi_epi8(v[6], v[7]);
+
+    let b0 = _mm256_unpacklo_epi16(a0, a2);
+    let b1 = _mm256_unpackhi_epi16(a0, a2);
+    let b2 = _mm256_unpacklo_epi16(a1, a3);
+    let b3 = _mm256_unpackhi_epi16(a1, a3);
+    let b4 = _mm256_unpacklo_epi16(a4, a6);
+    let b5 = _mm256_unpackhi_epi16(a4, a6);
+    let b6 = _mm256_unpacklo_epi16(a5, a7);
+    let b7 = _mm256_unpackhi_epi16(a5, a7);
+
+    // Each lane now holds columns `2k` and `2k + 1` of its own 8 rows
+    let d0 = _mm256_unpacklo_epi32(b0, b4);
+    let d1 = _mm256_unpackhi_epi32(b0, b4);
+    let d2 = _mm256_unpacklo_epi32(b1, b5);
+    let d3 = _mm256_unpackhi_epi32(b1, b5);
+    let d4 = _mm256_unpacklo_epi32(b2, b6);
+    let d5 = _mm256_unpackhi_epi32(b2, b6);
+    let d6 = _mm256_unpacklo_epi32(b3, b7);
+    let d7 = _mm256_unpackhi_epi32(b3, b7);
+
+    // Joins both lanes so each 128-bit half is a complete 16 byte column
+    const JOIN: i32 = 0b11_01_10_00;
+    [
+        _mm256_permute4x64_epi64::<JOIN>(d0),
+        _mm256_permute4x64_epi64::<JOIN>(d1),
+        _mm256_permute4x64_epi64::<JOIN>(d2),
+        _mm256_permute4x64_epi64::<JOIN>(d3),
+        _mm256_permute4x64_epi64::<JOIN>(d4),
+        _mm256_permute4x64_epi64::<JOIN>(d5),
+        _mm256_permute4x64_epi64::<JOIN>(d6),
+        _mm256_permute4x64_epi64::<JOIN>(d7),
+    ]
+}
+
+#[inline(always)]
+pub(crate) fn avx2_transpose_u8_16x16<const FLOP: bool, const FLIP: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let rows = [
+            load_rows::<FLIP>(src, src_stride, 0),
+            load_rows::<FLIP>(src, src_stride, 1),
+            load_rows::<FLIP>(src, src_stride, 2),
+            load_rows::<FLIP>(src, src_stride, 3),
+            load_rows::<FLIP>(src, src_stride, 4),
+            load_rows::<FLIP>(src, src_stride, 5),
+            load_rows::<FLIP>(src, src_stride, 6),
+            load_rows::<FLIP>(src, src_stride, 7),
+        ];
+
+        let cols = avx2_transpose_u8_16x16_impl(rows);
+
+        for (k, v) in cols.into_iter().enumerate() {
+            store_rows::<FLOP>(dst, dst_stride, k, v);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::avx::u16_16x16::avx2_transpose_u16_16x16_impl;
+use std::arch::x86_64::*;
+
+#[inline(always)]
+pub(crate) fn avx2_transpose_u8x2_16x16<const FLOP: bool, const FLIP: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        // A two channel 8-bit pixel moves as a single 16-bit lane
+        let mut rows = [_mm256_setzero_si256(); 16];
+        for (i, row) in rows.iter_mut().enumerate() {
+            *row = _mm256_loadu_si256(src.get_unchecked(i * src_stride..).as_ptr() as *const _);
+        }
+
+        let cols = avx2_transpose_u16_16x16_impl::<FLIP>(rows);
+
+        for (k, v) in cols.into_iter().enumerate() {
+            let y = if FLOP { k } else { 15 - k };
+            _mm256_storeu_si256(
+                dst.get_unchecked_mut(y * dst_stride..).as_mut_ptr() as *mut _,
+                v,
+            );
+        }
+    }
+}
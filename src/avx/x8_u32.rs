@@ -27,11 +27,12 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+use crate::utils::swizzle_mask_u8x4;
 use std::arch::x86_64::*;
 
 #[inline(always)]
 #[allow(clippy::type_complexity)]
-unsafe fn avx_transpose_8x8_impl<const FLIP: bool>(
+pub(crate) unsafe fn avx_transpose_8x8_impl<const FLIP: bool>(
     v0: (__m256i, __m256i, __m256i, __m256i),
     v1: (__m256i, __m256i, __m256i, __m256i),
 ) -> (
@@ -84,6 +85,48 @@ unsafe fn avx_transpose_8x8_impl<const FLIP: bool>(
     }
 }
 
+#[inline(always)]
+#[allow(clippy::type_complexity)]
+unsafe fn avx_load_8x8_u32(
+    src: &[u8],
+    src_stride: usize,
+) -> (
+    (__m256i, __m256i, __m256i, __m256i),
+    (__m256i, __m256i, __m256i, __m256i),
+) {
+    (
+        (
+            _mm256_loadu_si256(src.get_unchecked(0..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(src_stride..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(2 * src_stride..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(3 * src_stride..).as_ptr() as *const _),
+        ),
+        (
+            _mm256_loadu_si256(src.get_unchecked(4 * src_stride..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(5 * src_stride..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(6 * src_stride..).as_ptr() as *const _),
+            _mm256_loadu_si256(src.get_unchecked(7 * src_stride..).as_ptr() as *const _),
+        ),
+    )
+}
+
+#[inline(always)]
+unsafe fn avx_store_8x8_u32<const FLOP: bool>(
+    dst: &mut [u8],
+    dst_stride: usize,
+    v0: (__m256i, __m256i, __m256i, __m256i),
+    v1: (__m256i, __m256i, __m256i, __m256i),
+) {
+    let rows = [v0.0, v0.1, v0.2, v0.3, v1.0, v1.1, v1.2, v1.3];
+    for (i, row) in rows.into_iter().enumerate() {
+        let dst_y = if FLOP { i } else { 7 - i };
+        _mm256_storeu_si256(
+            dst.get_unchecked_mut(dst_y * dst_stride..).as_mut_ptr() as *mut _,
+            row,
+        );
+    }
+}
+
 #[inline]
 #[target_feature(enable = "avx2")]
 pub(crate) fn avx_transpose_8x8_u32<const FLOP: bool, const FLIP: bool>(
@@ -93,78 +136,46 @@ pub(crate) fn avx_transpose_8x8_u32<const FLOP: bool, const FLIP: bool>(
     dst_stride: usize,
 ) {
     unsafe {
-        let row0 = _mm256_loadu_si256(src.get_unchecked(0..).as_ptr() as *const _);
-        let row1 = _mm256_loadu_si256(src.get_unchecked(src_stride..).as_ptr() as *const _);
-        let row2 = _mm256_loadu_si256(src.get_unchecked(2 * src_stride..).as_ptr() as *const _);
-        let row3 = _mm256_loadu_si256(src.get_unchecked(3 * src_stride..).as_ptr() as *const _);
-        let row4 = _mm256_loadu_si256(src.get_unchecked(4 * src_stride..).as_ptr() as *const _);
-        let row5 = _mm256_loadu_si256(src.get_unchecked(5 * src_stride..).as_ptr() as *const _);
-        let row6 = _mm256_loadu_si256(src.get_unchecked(6 * src_stride..).as_ptr() as *const _);
-        let row7 = _mm256_loadu_si256(src.get_unchecked(7 * src_stride..).as_ptr() as *const _);
-
-        let (v0, v1) =
-            avx_transpose_8x8_impl::<FLIP>((row0, row1, row2, row3), (row4, row5, row6, row7));
+        let (row0, row1) = avx_load_8x8_u32(src, src_stride);
+        let (v0, v1) = avx_transpose_8x8_impl::<FLIP>(row0, row1);
+        avx_store_8x8_u32::<FLOP>(dst, dst_stride, v0, v1);
+    }
+}
 
-        if FLOP {
-            _mm256_storeu_si256(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.0);
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v0.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.2,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.3,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.0,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.2,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.3,
-            );
-        } else {
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.0,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.2,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut _,
-                v0.3,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.0,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut _,
-                v1.1,
-            );
-            _mm256_storeu_si256(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v1.2,
-            );
-            _mm256_storeu_si256(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v1.3);
-        }
+/// Same as [avx_transpose_8x8_u32] but also reorders channels of every 4 channel 8-bit
+/// pixel by `KEY` from [crate::ChannelOrder] with one in-lane shuffle per row.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub(crate) fn avx_transpose_8x8_u32_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let (row0, row1) = avx_load_8x8_u32(src, src_stride);
+        let (v0, v1) = avx_transpose_8x8_impl::<FLIP>(row0, row1);
+        let mask = const { swizzle_mask_u8x4(KEY, false) };
+        let mask = _mm256_broadcastsi128_si256(_mm_loadu_si128(mask.as_ptr() as *const __m128i));
+        avx_store_8x8_u32::<FLOP>(
+            dst,
+            dst_stride,
+            (
+                _mm256_shuffle_epi8(v0.0, mask),
+                _mm256_shuffle_epi8(v0.1, mask),
+                _mm256_shuffle_epi8(v0.2, mask),
+                _mm256_shuffle_epi8(v0.3, mask),
+            ),
+            (
+                _mm256_shuffle_epi8(v1.0, mask),
+                _mm256_shuffle_epi8(v1.1, mask),
+                _mm256_shuffle_epi8(v1.2, mask),
+                _mm256_shuffle_epi8(v1.3, mask),
+            ),
+        );
     }
 }
@@ -26,6 +26,20 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+// Module is compiled only on toolchains where AVX-512 is stable, see `build.rs`.
+#![allow(clippy::incompatible_msrv)]
+
+mod u16x4_8x8;
+mod x16_u16;
 mod x16_u32;
+mod x16_u8;
+mod x32_u16;
+mod x32_u8;
+mod zip;
 
+pub(crate) use u16x4_8x8::avx512_transpose_u16x4_8x8;
+pub(crate) use x16_u16::avx512_transpose_16x16_u16;
 pub(crate) use x16_u32::avx512_transpose_16x16_u32;
+pub(crate) use x16_u8::avx512_transpose_16x16_u8;
+pub(crate) use x32_u16::avx512_transpose_32x32_u16;
+pub(crate) use x32_u8::avx512_transpose_32x32_u8;
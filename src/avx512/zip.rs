@@ -0,0 +1,162 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::x86_64::*;
+
+/// Builds `permutex2var` indices interleaving either low or high halves
+/// of two registers made of `lane` bytes wide elements.
+const fn zip_indices(lane: usize, high: bool) -> [u8; 64] {
+    let lanes = 64 / lane;
+    let mut indices = [0u8; 64];
+    let mut k = 0usize;
+    while k < lanes {
+        let half = if high { lanes / 2 } else { 0 };
+        indices[k * lane] = (half + k / 2 + (k % 2) * lanes) as u8;
+        k += 1;
+    }
+    indices
+}
+
+/// Full register width interleave of two `__m512i` made of `LANE` bytes elements.
+pub(crate) trait Avx512Zip {
+    const LANE: usize;
+    const LO: [u8; 64] = zip_indices(Self::LANE, false);
+    const HI: [u8; 64] = zip_indices(Self::LANE, true);
+
+    unsafe fn permute(a: __m512i, idx: __m512i, b: __m512i) -> __m512i;
+}
+
+/// Byte lanes, requires `avx512vbmi`.
+pub(crate) struct ZipU8 {}
+
+impl Avx512Zip for ZipU8 {
+    const LANE: usize = 1;
+
+    #[inline(always)]
+    unsafe fn permute(a: __m512i, idx: __m512i, b: __m512i) -> __m512i {
+        _mm512_permutex2var_epi8(a, idx, b)
+    }
+}
+
+/// Word lanes, requires `avx512bw`.
+pub(crate) struct ZipU16 {}
+
+impl Avx512Zip for ZipU16 {
+    const LANE: usize = 2;
+
+    #[inline(always)]
+    unsafe fn permute(a: __m512i, idx: __m512i, b: __m512i) -> __m512i {
+        _mm512_permutex2var_epi16(a, idx, b)
+    }
+}
+
+/// Quad word lanes, requires `avx512f`.
+pub(crate) struct ZipU64 {}
+
+impl Avx512Zip for ZipU64 {
+    const LANE: usize = 8;
+
+    #[inline(always)]
+    unsafe fn permute(a: __m512i, idx: __m512i, b: __m512i) -> __m512i {
+        _mm512_permutex2var_epi64(a, idx, b)
+    }
+}
+
+/// Transposes `N`x`N` block of `Z::LANE` bytes elements held in `R` registers,
+/// strides are expressed in `V` units.
+///
+/// Each register keeps `64 / (N * Z::LANE)` consecutive rows. Every interleave stage
+/// rotates bits of the element index left by one, so after `log2(N)` stages
+/// row and column bits are swapped, which is exactly a transpose.
+#[inline(always)]
+pub(crate) unsafe fn avx512_zip_transpose<
+    V: Copy,
+    Z: Avx512Zip,
+    const N: usize,
+    const R: usize,
+    const FLOP: bool,
+    const FLIP: bool,
+>(
+    src: &[V],
+    src_stride: usize,
+    dst: &mut [V],
+    dst_stride: usize,
+) {
+    let row_bytes = N * Z::LANE;
+    let rows_per_reg = 64 / row_bytes;
+    assert_eq!(R * rows_per_reg, N);
+
+    let mut v = [_mm512_setzero_si512(); R];
+
+    for (k, reg) in v.iter_mut().enumerate() {
+        for j in 0..rows_per_reg {
+            let row = k * rows_per_reg + j;
+            let src_row = if FLIP { N - 1 - row } else { row };
+            let ptr = src.get_unchecked(src_row * src_stride..).as_ptr() as *const u8;
+            *reg = match (rows_per_reg, j) {
+                (1, _) => _mm512_loadu_si512(ptr as *const _),
+                (2, 0) => _mm512_inserti64x4::<0>(*reg, _mm256_loadu_si256(ptr as *const _)),
+                (2, _) => _mm512_inserti64x4::<1>(*reg, _mm256_loadu_si256(ptr as *const _)),
+                (_, 0) => _mm512_inserti32x4::<0>(*reg, _mm_loadu_si128(ptr as *const _)),
+                (_, 1) => _mm512_inserti32x4::<1>(*reg, _mm_loadu_si128(ptr as *const _)),
+                (_, 2) => _mm512_inserti32x4::<2>(*reg, _mm_loadu_si128(ptr as *const _)),
+                (_, _) => _mm512_inserti32x4::<3>(*reg, _mm_loadu_si128(ptr as *const _)),
+            };
+        }
+    }
+
+    let lo = _mm512_loadu_si512(Z::LO.as_ptr() as *const _);
+    let hi = _mm512_loadu_si512(Z::HI.as_ptr() as *const _);
+
+    for _ in 0..N.trailing_zeros() {
+        let mut t = [_mm512_setzero_si512(); R];
+        for i in 0..R / 2 {
+            t[2 * i] = Z::permute(v[i], lo, v[i + R / 2]);
+            t[2 * i + 1] = Z::permute(v[i], hi, v[i + R / 2]);
+        }
+        v = t;
+    }
+
+    for (k, reg) in v.iter().enumerate() {
+        for j in 0..rows_per_reg {
+            let row = k * rows_per_reg + j;
+            let dst_row = if FLOP { row } else { N - 1 - row };
+            let ptr = dst.get_unchecked_mut(dst_row * dst_stride..).as_mut_ptr() as *mut u8;
+            match (rows_per_reg, j) {
+                (1, _) => _mm512_storeu_si512(ptr as *mut _, *reg),
+                (2, 0) => _mm256_storeu_si256(ptr as *mut _, _mm512_extracti64x4_epi64::<0>(*reg)),
+                (2, _) => _mm256_storeu_si256(ptr as *mut _, _mm512_extracti64x4_epi64::<1>(*reg)),
+                (_, 0) => _mm_storeu_si128(ptr as *mut _, _mm512_extracti32x4_epi32::<0>(*reg)),
+                (_, 1) => _mm_storeu_si128(ptr as *mut _, _mm512_extracti32x4_epi32::<1>(*reg)),
+                (_, 2) => _mm_storeu_si128(ptr as *mut _, _mm512_extracti32x4_epi32::<2>(*reg)),
+                (_, _) => _mm_storeu_si128(ptr as *mut _, _mm512_extracti32x4_epi32::<3>(*reg)),
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::yuv::{orient_plane, YuvSample};
+use crate::{CfaPattern, Orientation, TransposeError};
+use roxygen::roxygen;
+
+fn rotate_mosaic<V: YuvSample>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    pattern: CfaPattern,
+    orientation: Orientation,
+) -> Result<CfaPattern, TransposeError> {
+    orient_plane::<V, 1>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+    )?;
+    Ok(pattern.oriented(width, height, orientation))
+}
+
+/// Reorients an 8-bit Bayer raw mosaic and returns its new CFA pattern.
+///
+/// Samples are moved intact on the plane kernels, pattern of the result follows from
+/// the orientation and parity of the dimensions, so it must be used for demosaicing.
+#[roxygen]
+pub fn rotate_bayer8(
+    /// Source mosaic data.
+    input: &[u8],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u8],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the source mosaic in samples.
+    width: usize,
+    /// Height of the source mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_mosaic(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        orientation,
+    )
+}
+
+/// Flips an 8-bit Bayer raw mosaic, samples of every row are reversed, and returns its new CFA pattern.
+#[roxygen]
+pub fn flip_bayer8(
+    /// Source mosaic data.
+    input: &[u8],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u8],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the mosaic in samples.
+    width: usize,
+    /// Height of the mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_bayer8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops an 8-bit Bayer raw mosaic, order of rows is reversed, and returns its new CFA pattern.
+#[roxygen]
+pub fn flop_bayer8(
+    /// Source mosaic data.
+    input: &[u8],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u8],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the mosaic in samples.
+    width: usize,
+    /// Height of the mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_bayer8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        Orientation::MirrorVertical,
+    )
+}
+
+/// Reorients a 10, 12, 14 or 16-bit Bayer raw mosaic and returns its new CFA pattern.
+///
+/// Samples are moved intact on the 16-bit plane kernels, so both LSB and MSB aligned data
+/// keeps its alignment. Pattern of the result follows from the orientation and parity of
+/// the dimensions, so it must be used for demosaicing.
+#[roxygen]
+pub fn rotate_bayer16(
+    /// Source mosaic data.
+    input: &[u16],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u16],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the source mosaic in samples.
+    width: usize,
+    /// Height of the source mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_mosaic(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        orientation,
+    )
+}
+
+/// Flips a 10, 12, 14 or 16-bit Bayer raw mosaic, samples of every row are reversed,
+/// and returns its new CFA pattern.
+#[roxygen]
+pub fn flip_bayer16(
+    /// Source mosaic data.
+    input: &[u16],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u16],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the mosaic in samples.
+    width: usize,
+    /// Height of the mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_bayer16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops a 10, 12, 14 or 16-bit Bayer raw mosaic, order of rows is reversed,
+/// and returns its new CFA pattern.
+#[roxygen]
+pub fn flop_bayer16(
+    /// Source mosaic data.
+    input: &[u16],
+    /// Number of samples per row in `input`.
+    input_stride: usize,
+    /// Destination mosaic data.
+    output: &mut [u16],
+    /// Number of samples per row in `output`.
+    output_stride: usize,
+    /// Width of the mosaic in samples.
+    width: usize,
+    /// Height of the mosaic in samples.
+    height: usize,
+    /// CFA pattern of the source mosaic.
+    pattern: CfaPattern,
+) -> Result<CfaPattern, TransposeError> {
+    rotate_bayer16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        pattern,
+        Orientation::MirrorVertical,
+    )
+}
@@ -0,0 +1,546 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::utils::is_valid_image_len;
+use crate::{transpose_arbitrary_grouped, Orientation, TransposeError};
+use roxygen::roxygen;
+
+/// Index table stored in an 8-byte part of a compressed block
+#[derive(Copy, Clone)]
+enum IndexTable {
+    /// BC1 color block, 2-bit indices in bytes 4..8
+    Color,
+    /// BC4 block, also BC3 alpha and BC5 channels, 3-bit indices in bytes 2..8
+    Channel,
+}
+
+const BC1_PARTS: [(usize, IndexTable); 1] = [(0, IndexTable::Color)];
+const BC3_PARTS: [(usize, IndexTable); 2] = [(0, IndexTable::Channel), (8, IndexTable::Color)];
+const BC4_PARTS: [(usize, IndexTable); 1] = [(0, IndexTable::Channel)];
+const BC5_PARTS: [(usize, IndexTable); 2] = [(0, IndexTable::Channel), (8, IndexTable::Channel)];
+
+/// Position of texel `(x, y)` of a `width` x `height` image after reorienting
+const fn orient_texel(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> (usize, usize) {
+    match orientation {
+        Orientation::Identity => (x, y),
+        Orientation::MirrorHorizontal => (width - 1 - x, y),
+        Orientation::MirrorVertical => (x, height - 1 - y),
+        Orientation::Rotate180 => (width - 1 - x, height - 1 - y),
+        Orientation::Rotate90 => (height - 1 - y, x),
+        Orientation::Rotate270 => (y, width - 1 - x),
+        Orientation::Transpose => (y, x),
+        Orientation::Transverse => (height - 1 - y, width - 1 - x),
+    }
+}
+
+/// Source texel of each destination texel inside a block, images smaller than a block
+/// reorient only their valid texels
+fn texel_permutation(width: usize, height: usize, orientation: Orientation) -> [usize; 16] {
+    let (block_width, block_height) = (width.min(4), height.min(4));
+    let mut permutation = std::array::from_fn(|i| i);
+    for y in 0..block_height {
+        for x in 0..block_width {
+            let (dst_x, dst_y) = orient_texel(x, y, block_width, block_height, orientation);
+            permutation[dst_y * 4 + dst_x] = y * 4 + x;
+        }
+    }
+    permutation
+}
+
+#[inline(always)]
+fn permute_indices(indices: u64, bits: usize, permutation: &[usize; 16]) -> u64 {
+    let mask = (1u64 << bits) - 1;
+    permutation
+        .iter()
+        .enumerate()
+        .fold(0u64, |acc, (dst, &src)| {
+            acc | (((indices >> (src * bits)) & mask) << (dst * bits))
+        })
+}
+
+fn remap_indices(part: &mut [u8], table: IndexTable, permutation: &[usize; 16]) {
+    let (offset, bits) = match table {
+        IndexTable::Color => (4, 2),
+        IndexTable::Channel => (2, 3),
+    };
+    let mut bytes = [0u8; 8];
+    bytes[..8 - offset].copy_from_slice(&part[offset..8]);
+    let indices = permute_indices(u64::from_le_bytes(bytes), bits, permutation);
+    part[offset..8].copy_from_slice(&indices.to_le_bytes()[..8 - offset]);
+}
+
+fn rotate_blocks<const BLOCK: usize>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    parts: &[(usize, IndexTable)],
+) -> Result<(), TransposeError> {
+    // Blocks map onto whole blocks only when reversed axes have no partial trailing block
+    let (reverse_x, reverse_y) = orientation.reversed_axes();
+    if (reverse_x && width > 4 && width % 4 != 0) || (reverse_y && height > 4 && height % 4 != 0) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    let (blocks_x, blocks_y) = (width.div_ceil(4), height.div_ceil(4));
+    let (dst_blocks_x, dst_blocks_y) = if orientation.is_transposed() {
+        (blocks_y, blocks_x)
+    } else {
+        (blocks_x, blocks_y)
+    };
+    if !is_valid_image_len(input.len(), input_stride, blocks_x * BLOCK, blocks_y) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(
+        output.len(),
+        output_stride,
+        dst_blocks_x * BLOCK,
+        dst_blocks_y,
+    ) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < blocks_x * BLOCK || output_stride < dst_blocks_x * BLOCK {
+        return Err(TransposeError::MismatchDimensions);
+    }
+
+    if let Some((flip_mode, flop_mode)) = orientation.transpose_modes() {
+        transpose_arbitrary_grouped::<u8, BLOCK>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            blocks_x,
+            blocks_y,
+            flip_mode,
+            flop_mode,
+        )?;
+    } else {
+        let row_bytes = blocks_x * BLOCK;
+        for (y, dst) in output.chunks_mut(output_stride).enumerate() {
+            let src_y = if reverse_y { blocks_y - 1 - y } else { y };
+            let src = &input[src_y * input_stride..src_y * input_stride + row_bytes];
+            if reverse_x {
+                for (dst, src) in dst[..row_bytes]
+                    .chunks_exact_mut(BLOCK)
+                    .zip(src.chunks_exact(BLOCK).rev())
+                {
+                    dst.copy_from_slice(src);
+                }
+            } else {
+                dst[..row_bytes].copy_from_slice(src);
+            }
+        }
+    }
+
+    let permutation = texel_permutation(width, height, orientation);
+    for dst in output.chunks_mut(output_stride) {
+        for block in dst[..dst_blocks_x * BLOCK].chunks_exact_mut(BLOCK) {
+            for &(offset, table) in parts {
+                remap_indices(&mut block[offset..offset + 8], table, &permutation);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reorients a BC1 (DXT1) texture without decompressing it.
+///
+/// The grid of 8-byte 4x4 blocks is reoriented and index tables inside every block are remapped,
+/// endpoints are kept as is, so the result is lossless. Axes reversed by the orientation must
+/// be multiples of 4 texels unless the texture is smaller than a block, as mip tails are.
+#[roxygen]
+pub fn rotate_bc1(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the source texture in texels.
+    width: usize,
+    /// Height of the source texture in texels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_blocks::<8>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        &BC1_PARTS,
+    )
+}
+
+/// Flips a BC1 (DXT1) texture without decompressing it, texels of every row are reversed.
+///
+/// Width must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flip_bc1(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc1(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops a BC1 (DXT1) texture without decompressing it, order of rows is reversed.
+///
+/// Height must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flop_bc1(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc1(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorVertical,
+    )
+}
+
+/// Reorients a BC3 (DXT5) texture without decompressing it.
+///
+/// The grid of 16-byte 4x4 blocks is reoriented and index tables inside every block are remapped,
+/// endpoints are kept as is, so the result is lossless. Axes reversed by the orientation must
+/// be multiples of 4 texels unless the texture is smaller than a block, as mip tails are.
+#[roxygen]
+pub fn rotate_bc3(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the source texture in texels.
+    width: usize,
+    /// Height of the source texture in texels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_blocks::<16>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        &BC3_PARTS,
+    )
+}
+
+/// Flips a BC3 (DXT5) texture without decompressing it, texels of every row are reversed.
+///
+/// Width must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flip_bc3(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc3(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops a BC3 (DXT5) texture without decompressing it, order of rows is reversed.
+///
+/// Height must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flop_bc3(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc3(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorVertical,
+    )
+}
+
+/// Reorients a BC4 (ATI1/RGTC1) texture without decompressing it.
+///
+/// The grid of 8-byte 4x4 blocks is reoriented and index tables inside every block are remapped,
+/// endpoints are kept as is, so the result is lossless. Axes reversed by the orientation must
+/// be multiples of 4 texels unless the texture is smaller than a block, as mip tails are.
+#[roxygen]
+pub fn rotate_bc4(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the source texture in texels.
+    width: usize,
+    /// Height of the source texture in texels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_blocks::<8>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        &BC4_PARTS,
+    )
+}
+
+/// Flips a BC4 (ATI1/RGTC1) texture without decompressing it, texels of every row are reversed.
+///
+/// Width must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flip_bc4(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc4(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops a BC4 (ATI1/RGTC1) texture without decompressing it, order of rows is reversed.
+///
+/// Height must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flop_bc4(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc4(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorVertical,
+    )
+}
+
+/// Reorients a BC5 (ATI2/RGTC2) texture without decompressing it.
+///
+/// The grid of 16-byte 4x4 blocks is reoriented and index tables inside every block are remapped,
+/// endpoints are kept as is, so the result is lossless. Axes reversed by the orientation must
+/// be multiples of 4 texels unless the texture is smaller than a block, as mip tails are.
+#[roxygen]
+pub fn rotate_bc5(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the source texture in texels.
+    width: usize,
+    /// Height of the source texture in texels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_blocks::<16>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        &BC5_PARTS,
+    )
+}
+
+/// Flips a BC5 (ATI2/RGTC2) texture without decompressing it, texels of every row are reversed.
+///
+/// Width must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flip_bc5(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc5(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorHorizontal,
+    )
+}
+
+/// Flops a BC5 (ATI2/RGTC2) texture without decompressing it, order of rows is reversed.
+///
+/// Height must be a multiple of 4 texels unless the texture is smaller than a block.
+#[roxygen]
+pub fn flop_bc5(
+    /// Source texture data.
+    input: &[u8],
+    /// Number of bytes per row of blocks in `input`.
+    input_stride: usize,
+    /// Destination texture data.
+    output: &mut [u8],
+    /// Number of bytes per row of blocks in `output`.
+    output_stride: usize,
+    /// Width of the texture in texels.
+    width: usize,
+    /// Height of the texture in texels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate_bc5(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Orientation::MirrorVertical,
+    )
+}
@@ -0,0 +1,486 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::utils::is_valid_image_len;
+use crate::{BitOrder, FlipMode, FlopMode, TransposeError};
+use roxygen::roxygen;
+
+/// Bytes in a tile edge, a multiple of 8 so tiles hold whole 64x64 pixel blocks
+const TILE_BYTES: usize = 32;
+
+/// Mask of the first `count` pixels of `BITS` bits in a byte
+#[inline(always)]
+fn pixel_mask<const LSB: bool, const BITS: usize>(count: usize) -> u8 {
+    if LSB {
+        !(0xffu16 << (count * BITS)) as u8
+    } else {
+        (0xff00u16 >> (count * BITS)) as u8
+    }
+}
+
+/// Stores the first `count` pixels of `value`, the remaining bits of the byte are cleared
+#[inline(always)]
+fn store_partial<const LSB: bool, const BITS: usize>(dst: &mut u8, value: u8, count: usize) {
+    *dst = value & pixel_mask::<LSB, BITS>(count);
+}
+
+/// Stores a row of `width` pixels of `BITS` bits, bits past `width` in the last byte
+/// are cleared
+#[inline(always)]
+pub(crate) fn store_row<const LSB: bool, const BITS: usize>(
+    dst: &mut [u8],
+    src: &[u8],
+    width: usize,
+) {
+    let pixels_per_byte = 8 / BITS;
+    let full = width / pixels_per_byte;
+    dst[..full].copy_from_slice(&src[..full]);
+    if width % pixels_per_byte != 0 {
+        store_partial::<LSB, BITS>(&mut dst[full], src[full], width % pixels_per_byte);
+    }
+}
+
+/// Transposes an 8x8 bit matrix held as eight rows, the most significant byte is the top row
+/// and the most significant bit of each row is its leftmost column.
+///
+/// Rows with the least significant bit first work as well when stored in little endian order,
+/// the matrix is then rotated by 180 degrees which commutes with transposition.
+#[inline(always)]
+fn transpose_8x8(x: u64) -> u64 {
+    let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+    let x = x ^ t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+    let x = x ^ t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+    x ^ t ^ (t << 28)
+}
+
+/// Transposes an 8x8 byte matrix held as eight rows, the most significant byte of a row is
+/// its first column
+#[inline(always)]
+fn transpose_bytes(mut rows: [u64; 8]) -> [u64; 8] {
+    for r in 0..4 {
+        let (a, b) = (rows[r], rows[r + 4]);
+        rows[r] = (a & 0xffff_ffff_0000_0000) | (b >> 32);
+        rows[r + 4] = (a << 32) | (b & 0x0000_0000_ffff_ffff);
+    }
+    const HALVES: u64 = 0xffff_0000_ffff_0000;
+    for r in [0, 1, 4, 5] {
+        let (a, b) = (rows[r], rows[r + 2]);
+        rows[r] = (a & HALVES) | ((b >> 16) & !HALVES);
+        rows[r + 2] = ((a << 16) & HALVES) | (b & !HALVES);
+    }
+    const BYTES: u64 = 0xff00_ff00_ff00_ff00;
+    for r in [0, 2, 4, 6] {
+        let (a, b) = (rows[r], rows[r + 1]);
+        rows[r] = (a & BYTES) | ((b >> 8) & !BYTES);
+        rows[r + 1] = ((a << 8) & BYTES) | (b & !BYTES);
+    }
+    rows
+}
+
+type BitTransposer = fn(&[u8], usize, &mut [u8], usize, usize, usize);
+
+/// Transposes a full 64x64 pixel block, 8x8 bit blocks are gathered from and scattered to
+/// whole 64-bit row segments with byte matrix transposes
+#[inline(always)]
+fn transpose_64x64<const LSB: bool, const FLIP: bool, const FLOP: bool>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    out_byte: usize,
+    in_byte: usize,
+) {
+    // Indexed by source byte column, then by destination byte column
+    let mut blocks = [[0u64; 8]; 8];
+    for group in 0..8 {
+        let mut rows = [0u64; 8];
+        for (k, row) in rows.iter_mut().enumerate() {
+            // Least significant bit first blocks are stored bottom up, see `transpose_8x8`
+            let k = if LSB { 7 - k } else { k };
+            let y = (out_byte + group) * 8 + k;
+            let offset = if FLIP { height - 1 - y } else { y } * input_stride + in_byte;
+            *row = u64::from_be_bytes(input[offset..offset + 8].try_into().unwrap());
+        }
+        for (column, &block) in blocks.iter_mut().zip(transpose_bytes(rows).iter()) {
+            column[group] = transpose_8x8(block);
+        }
+    }
+
+    for (column, &blocks) in blocks.iter().enumerate() {
+        let rows = transpose_bytes(blocks);
+        for k in 0..8 {
+            let row = if LSB { rows[7 - k] } else { rows[k] };
+            let x = (in_byte + column) * 8 + k;
+            let offset = if FLOP { x } else { width - 1 - x } * output_stride + out_byte;
+            output[offset..offset + 8].copy_from_slice(&row.to_be_bytes());
+        }
+    }
+}
+
+/// Transposes a single 8x8 pixel block clipped to the image
+#[inline(always)]
+fn transpose_block<const LSB: bool, const FLIP: bool, const FLOP: bool>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    out_byte: usize,
+    in_byte: usize,
+) {
+    let first_y = out_byte * 8;
+    let rows = (height - first_y).min(8);
+    let mut bytes = [0u8; 8];
+    for (k, byte) in bytes[..rows].iter_mut().enumerate() {
+        let y = first_y + k;
+        *byte = input[if FLIP { height - 1 - y } else { y } * input_stride + in_byte];
+    }
+    let block = if LSB {
+        u64::from_le_bytes(bytes)
+    } else {
+        u64::from_be_bytes(bytes)
+    };
+    let transposed = transpose_8x8(block);
+    let columns = if LSB {
+        transposed.to_le_bytes()
+    } else {
+        transposed.to_be_bytes()
+    };
+
+    let first_x = in_byte * 8;
+    let count = (width - first_x).min(8);
+    for (k, &column) in columns[..count].iter().enumerate() {
+        let x = first_x + k;
+        let dst = &mut output[if FLOP { x } else { width - 1 - x } * output_stride + out_byte];
+        store_partial::<LSB, 1>(dst, column, rows);
+    }
+}
+
+fn transpose_bits<const LSB: bool, const FLIP: bool, const FLOP: bool>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    let input_bytes = width.div_ceil(8);
+    let output_bytes = height.div_ceil(8);
+
+    for tile_out in (0..output_bytes).step_by(TILE_BYTES) {
+        let tile_out_end = (tile_out + TILE_BYTES).min(output_bytes);
+        for tile_in in (0..input_bytes).step_by(TILE_BYTES) {
+            let tile_in_end = (tile_in + TILE_BYTES).min(input_bytes);
+            for out_byte in (tile_out..tile_out_end).step_by(8) {
+                for in_byte in (tile_in..tile_in_end).step_by(8) {
+                    if (out_byte + 8) * 8 <= height && (in_byte + 8) * 8 <= width {
+                        transpose_64x64::<LSB, FLIP, FLOP>(
+                            input,
+                            input_stride,
+                            output,
+                            output_stride,
+                            width,
+                            height,
+                            out_byte,
+                            in_byte,
+                        );
+                        continue;
+                    }
+                    for out_byte in out_byte..(out_byte + 8).min(tile_out_end) {
+                        for in_byte in in_byte..(in_byte + 8).min(tile_in_end) {
+                            transpose_block::<LSB, FLIP, FLOP>(
+                                input,
+                                input_stride,
+                                output,
+                                output_stride,
+                                width,
+                                height,
+                                out_byte,
+                                in_byte,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn select_transposer<const LSB: bool>(flip_mode: FlipMode, flop_mode: FlopMode) -> BitTransposer {
+    match (flip_mode, flop_mode) {
+        (FlipMode::NoFlip, FlopMode::NoFlop) => transpose_bits::<LSB, false, false>,
+        (FlipMode::NoFlip, FlopMode::Flop) => transpose_bits::<LSB, false, true>,
+        (FlipMode::Flip, FlopMode::NoFlop) => transpose_bits::<LSB, true, false>,
+        (FlipMode::Flip, FlopMode::Flop) => transpose_bits::<LSB, true, true>,
+    }
+}
+
+/// Reverses order of `BITS` bit pixels in a byte
+#[inline(always)]
+fn reverse_pixels<const BITS: usize>(byte: u8) -> u8 {
+    match BITS {
+        1 => byte.reverse_bits(),
+        2 => {
+            let reversed = byte.reverse_bits();
+            ((reversed & 0x55) << 1) | ((reversed >> 1) & 0x55)
+        }
+        _ => byte.rotate_left(4),
+    }
+}
+
+/// Reverses pixels of a packed row, bits past `width` in the last byte of `dst` are cleared
+#[inline(always)]
+fn reverse_row<const LSB: bool, const BITS: usize>(src: &[u8], dst: &mut [u8], width: usize) {
+    let pixels_per_byte = 8 / BITS;
+    let row_bytes = width.div_ceil(pixels_per_byte);
+    for (dst, &src) in dst.iter_mut().zip(src[..row_bytes].iter().rev()) {
+        *dst = reverse_pixels::<BITS>(src);
+    }
+    // Reversing whole bytes leaves the row shifted by the padding of the last byte
+    let padding = (row_bytes * pixels_per_byte - width) * BITS;
+    if padding == 0 {
+        return;
+    }
+    for i in 0..row_bytes - 1 {
+        dst[i] = if LSB {
+            (dst[i] >> padding) | (dst[i + 1] << (8 - padding))
+        } else {
+            (dst[i] << padding) | (dst[i + 1] >> (8 - padding))
+        };
+    }
+    dst[row_bytes - 1] = if LSB {
+        dst[row_bytes - 1] >> padding
+    } else {
+        dst[row_bytes - 1] << padding
+    };
+}
+
+fn mirror_bits<
+    const LSB: bool,
+    const BITS: usize,
+    const REVERSE_PIXELS: bool,
+    const REVERSE_ROWS: bool,
+>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), TransposeError> {
+    let row_bytes = width.div_ceil(8 / BITS);
+    if !is_valid_image_len(input.len(), input_stride, row_bytes, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, row_bytes, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < row_bytes || output_stride < row_bytes {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if row_bytes == 0 {
+        return Ok(());
+    }
+
+    let mut row = vec![0u8; row_bytes];
+    for (y, dst) in output.chunks_mut(output_stride).enumerate() {
+        let input_y = if REVERSE_ROWS { height - 1 - y } else { y };
+        let src = &input[input_y * input_stride..input_y * input_stride + row_bytes];
+        if REVERSE_PIXELS {
+            reverse_row::<LSB, BITS>(src, &mut row, width);
+            store_row::<LSB, BITS>(dst, &row, width);
+        } else {
+            store_row::<LSB, BITS>(dst, src, width);
+        }
+    }
+    Ok(())
+}
+
+/// Flips, flops or rotates by 180 degrees an image of `BITS` bits per pixel with packed rows
+pub(crate) fn mirror_packed<
+    const BITS: usize,
+    const REVERSE_PIXELS: bool,
+    const REVERSE_ROWS: bool,
+>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    let executor = match bit_order {
+        BitOrder::MsbFirst => mirror_bits::<false, BITS, REVERSE_PIXELS, REVERSE_ROWS>,
+        BitOrder::LsbFirst => mirror_bits::<true, BITS, REVERSE_PIXELS, REVERSE_ROWS>,
+    };
+    executor(input, input_stride, output, output_stride, width, height)
+}
+
+/// Transposes a 1 bit per pixel bitmap with packed rows
+///
+/// Rows hold `width.div_ceil(8)` bytes, 8x8 pixel blocks are transposed as 64-bit words.
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn transpose_bitmap(
+    /// Source bitmap.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination bitmap, `height` pixels wide and `width` pixels tall.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the source bitmap in pixels.
+    width: usize,
+    /// Height of the source bitmap in pixels.
+    height: usize,
+    /// Bit holding the leftmost pixel of each byte, shared by source and destination.
+    bit_order: BitOrder,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    let (input_bytes, output_bytes) = (width.div_ceil(8), height.div_ceil(8));
+    if !is_valid_image_len(input.len(), input_stride, input_bytes, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, output_bytes, width) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < input_bytes || output_stride < output_bytes {
+        return Err(TransposeError::MismatchDimensions);
+    }
+
+    let executor = match bit_order {
+        BitOrder::MsbFirst => select_transposer::<false>(flip_mode, flop_mode),
+        BitOrder::LsbFirst => select_transposer::<true>(flip_mode, flop_mode),
+    };
+    executor(input, input_stride, output, output_stride, width, height);
+    Ok(())
+}
+
+/// Flips a 1 bit per pixel bitmap, pixels of every row are reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flip_bitmap(
+    /// Source bitmap.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination bitmap.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the bitmap in pixels.
+    width: usize,
+    /// Height of the bitmap in pixels.
+    height: usize,
+    /// Bit holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<1, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Flops a 1 bit per pixel bitmap, order of rows is reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flop_bitmap(
+    /// Source bitmap.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination bitmap.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the bitmap in pixels.
+    width: usize,
+    /// Height of the bitmap in pixels.
+    height: usize,
+    /// Bit holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<1, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Rotates a 1 bit per pixel bitmap by 180 degrees
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn rotate180_bitmap(
+    /// Source bitmap.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination bitmap.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the bitmap in pixels.
+    width: usize,
+    /// Height of the bitmap in pixels.
+    height: usize,
+    /// Bit holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<1, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
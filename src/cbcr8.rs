@@ -26,8 +26,10 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::fused::FusedTranspose;
 #[allow(unused_imports)]
 use crate::rgba8::*;
+use crate::utils::is_valid_image_len;
 use crate::{FlipMode, FlopMode, TransposeError};
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 struct TransposePlaneBlockNeon4x4<const FLOP: bool, const FLIP: bool> {}
@@ -198,6 +200,78 @@ unsafe fn transpose_cbcr8_impl_ssse3<const FLOP: bool, const FLIP: bool>(
     )
 }
 
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposePlaneBlockAvx2_16x16<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u8>
+    for TransposePlaneBlockAvx2_16x16<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::avx::avx2_transpose_u8x2_16x16;
+        avx2_transpose_u8x2_16x16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+#[target_feature(enable = "avx2")]
+unsafe fn transpose_cbcr8_impl_avx2<const FLOP: bool, const FLIP: bool>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    const CN: usize = 2;
+
+    let mut y = 0usize;
+
+    y = transpose_executor::<u8, 16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx2_16x16::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u8, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u8, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_4x4::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
 pub(crate) fn transpose_cbcr8_chunked(
     input: &[u8],
     input_stride: usize,
@@ -208,10 +282,10 @@ pub(crate) fn transpose_cbcr8_chunked(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * 2, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * width {
+    if !is_valid_image_len(output.len(), output_stride, height * 2, width) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * 2 {
@@ -222,6 +296,22 @@ pub(crate) fn transpose_cbcr8_chunked(
     }
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
+        #[cfg(feature = "sve")]
+        if crate::sve::has_wide_sve() {
+            use crate::sve::sve_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u8, 2, false, false>,
+                    FlopMode::Flop => sve_transpose::<u8, 2, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u8, 2, false, true>,
+                    FlopMode::Flop => sve_transpose::<u8, 2, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
         let executor = match flip_mode {
             FlipMode::NoFlip => match flop_mode {
                 FlopMode::NoFlop => transpose_plane8_impl_neon::<false, false>,
@@ -242,6 +332,24 @@ pub(crate) fn transpose_cbcr8_chunked(
         any(feature = "sse", feature = "avx")
     ))]
     {
+        #[cfg(feature = "avx")]
+        if std::arch::is_x86_feature_detected!("avx2") {
+            let executor: unsafe fn(&[u8], usize, &mut [u8], usize, usize, usize) = match flip_mode
+            {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => transpose_cbcr8_impl_avx2::<false, false>,
+                    FlopMode::Flop => transpose_cbcr8_impl_avx2::<true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => transpose_cbcr8_impl_avx2::<false, true>,
+                    FlopMode::Flop => transpose_cbcr8_impl_avx2::<true, true>,
+                },
+            };
+
+            unsafe { executor(input, input_stride, output, output_stride, width, height) }
+            return Ok(());
+        }
+
         if std::arch::is_x86_feature_detected!("ssse3") {
             let executor: unsafe fn(&[u8], usize, &mut [u8], usize, usize, usize) = match flip_mode
             {
@@ -259,6 +367,24 @@ pub(crate) fn transpose_cbcr8_chunked(
             return Ok(());
         }
     }
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        if crate::rvv::has_rvv() {
+            use crate::rvv::rvv_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u8, 2, false, false>,
+                    FlopMode::Flop => rvv_transpose::<u8, 2, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u8, 2, false, true>,
+                    FlopMode::Flop => rvv_transpose::<u8, 2, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
+    }
     #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
     {
         use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
@@ -274,3 +400,80 @@ pub(crate) fn transpose_cbcr8_chunked(
         )
     }
 }
+
+/// Fused transpose of interleaved 8-bit chroma going through the block kernels of this module.
+pub(crate) struct CbCr8Fused {}
+
+impl FusedTranspose<u8> for CbCr8Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u8],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u8], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u8, O, 8, 2, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposePlaneBlockNeon8x8::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u8, O, 16, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposePlaneBlockAvx2_16x16::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u8, O, 8, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<2>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
@@ -0,0 +1,471 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::fused::{run_vectorized, transpose_fused_modes, FusedSample, FusedTranspose};
+use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
+use crate::utils::is_valid_image_len;
+use crate::{
+    transpose_plane, transpose_plane16, transpose_plane16_with_alpha, transpose_plane_f32,
+    transpose_plane_f32_with_alpha, transpose_plane_with_alpha, transpose_rgb, transpose_rgb16,
+    transpose_rgb_f32, transpose_rgba, transpose_rgba16, transpose_rgba_f32, FlipMode, FlopMode,
+    TransposeError,
+};
+use roxygen::roxygen;
+
+pub(crate) type TileTransposer<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    FlipMode,
+    FlopMode,
+) -> Result<(), TransposeError>;
+
+pub(crate) fn u8_transposer<const N: usize>() -> TileTransposer<u8> {
+    match N {
+        1 => transpose_plane,
+        2 => transpose_plane_with_alpha,
+        3 => transpose_rgb,
+        4 => transpose_rgba,
+        _ => transpose_arbitrary_grouped::<u8, N>,
+    }
+}
+
+pub(crate) fn u16_transposer<const N: usize>() -> TileTransposer<u16> {
+    match N {
+        1 => transpose_plane16,
+        2 => transpose_plane16_with_alpha,
+        3 => transpose_rgb16,
+        4 => transpose_rgba16,
+        _ => transpose_arbitrary_grouped::<u16, N>,
+    }
+}
+
+pub(crate) fn f32_transposer<const N: usize>() -> TileTransposer<f32> {
+    match N {
+        1 => transpose_plane_f32,
+        2 => transpose_plane_f32_with_alpha,
+        3 => transpose_rgb_f32,
+        4 => transpose_rgba_f32,
+        _ => transpose_arbitrary_grouped::<f32, N>,
+    }
+}
+
+fn check_converted_layout<const N: usize, const M: usize>(
+    input_len: usize,
+    input_stride: usize,
+    output_len: usize,
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), TransposeError> {
+    if !is_valid_image_len(input_len, input_stride, width * N, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output_len, output_stride, height * M, width) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < width * N {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if output_stride < height * M {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    Ok(())
+}
+
+/// Transposes image with block kernels of the source type and converts every transposed
+/// strip of `N` channel pixels straight into `M` channel destination pixels while it is
+/// still in L1, so source and destination are touched once.
+pub(crate) fn transpose_converted<I: FusedSample, O, const N: usize, const M: usize>(
+    input: &[I],
+    input_stride: usize,
+    output: &mut [O],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+    converter: impl Fn(&[I], &mut [O]),
+) -> Result<(), TransposeError> {
+    check_converted_layout::<N, M>(
+        input.len(),
+        input_stride,
+        output.len(),
+        output_stride,
+        width,
+        height,
+    )?;
+    I::transpose_fused::<O, N, M>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        &converter,
+    );
+    Ok(())
+}
+
+/// Same as [transpose_converted] with block kernels of `F` in place of the default ones
+/// of the source type.
+pub(crate) fn transpose_converted_with<
+    I,
+    F: FusedTranspose<I>,
+    O,
+    const N: usize,
+    const M: usize,
+>(
+    input: &[I],
+    input_stride: usize,
+    output: &mut [O],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+    converter: impl Fn(&[I], &mut [O]),
+) -> Result<(), TransposeError> {
+    check_converted_layout::<N, M>(
+        input.len(),
+        input_stride,
+        output.len(),
+        output_stride,
+        width,
+        height,
+    )?;
+    transpose_fused_modes::<I, F, O, M>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        &converter,
+    );
+    Ok(())
+}
+
+/// Copies rows in forward or reverse order converting each one from `src_channels` to
+/// `dst_channels` pixels, converter is responsible for mirroring pixels within a row.
+pub(crate) fn mirror_converted<I, O, const REVERSE_ROWS: bool>(
+    input: &[I],
+    input_stride: usize,
+    output: &mut [O],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    src_channels: usize,
+    dst_channels: usize,
+    converter: impl Fn(&[I], &mut [O]),
+) -> Result<(), TransposeError> {
+    if !is_valid_image_len(input.len(), input_stride, width * src_channels, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, width * dst_channels, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < width * src_channels {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if output_stride < width * dst_channels {
+        return Err(TransposeError::MismatchDimensions);
+    }
+
+    let rows = |(dst, src): (&mut [O], &[I])| {
+        converter(
+            &src[..width * src_channels],
+            &mut dst[..width * dst_channels],
+        )
+    };
+    run_vectorized(|| {
+        if REVERSE_ROWS {
+            output
+                .chunks_mut(output_stride)
+                .rev()
+                .zip(input.chunks(input_stride))
+                .for_each(rows);
+        } else {
+            output
+                .chunks_mut(output_stride)
+                .zip(input.chunks(input_stride))
+                .for_each(rows);
+        }
+    });
+    Ok(())
+}
+
+pub(crate) fn check_bit_depth(bit_depth: u32) -> Result<(), TransposeError> {
+    if !(8..=16).contains(&bit_depth) {
+        return Err(TransposeError::InvalidBitDepth);
+    }
+    Ok(())
+}
+
+/// Transposes an 8-bit image with `N` channels into a 32-bit float image.
+///
+/// Every sample is multiplied by `scale`, pass `1. / 255.` to normalize into `0..=1`
+/// or `1.` to keep the integer range.
+///
+/// # Performance
+///
+/// Blocks are transposed with the 8-bit SIMD kernels and converted while still in cache.
+#[roxygen]
+pub fn transpose_u8_to_f32<const N: usize>(
+    /// Source image data as a flat array of 8-bit samples.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed float data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (height * N for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Factor applied to every converted sample.
+    scale: f32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_converted::<u8, f32, N, N>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        |src, dst| {
+            for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+                *dst = src as f32 * scale;
+            }
+        },
+    )
+}
+
+/// Transposes a 16-bit image with `N` channels into a 32-bit float image.
+///
+/// Every sample is multiplied by `scale`, pass `1. / ((1 << bit_depth) - 1) as f32`
+/// to normalize into `0..=1` or `1.` to keep the integer range.
+///
+/// # Performance
+///
+/// Blocks are transposed with the 16-bit SIMD kernels and converted while still in cache.
+#[roxygen]
+pub fn transpose_u16_to_f32<const N: usize>(
+    /// Source image data as a flat array of 16-bit samples.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed float data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (height * N for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Factor applied to every converted sample.
+    scale: f32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_converted::<u16, f32, N, N>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        |src, dst| {
+            for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+                *dst = src as f32 * scale;
+            }
+        },
+    )
+}
+
+/// Transposes a 32-bit float image with `N` channels into an 8-bit image.
+///
+/// Every sample is multiplied by `scale`, clamped to `0..=255` and rounded to nearest,
+/// pass `255.` when source is normalized into `0..=1`. NaN becomes 0.
+///
+/// # Performance
+///
+/// Blocks are transposed with the float SIMD kernels and converted while still in cache.
+#[roxygen]
+pub fn transpose_f32_to_u8<const N: usize>(
+    /// Source image data as a flat array of 32-bit float samples.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed 8-bit data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height * N for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Factor applied to every sample before rounding.
+    scale: f32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_converted::<f32, u8, N, N>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        |src, dst| {
+            for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+                // Float to int casts saturate, adding a half rounds values that are already clamped.
+                *dst = ((src * scale).clamp(0., 255.) + 0.5) as u8;
+            }
+        },
+    )
+}
+
+/// Transposes a 16-bit image with `N` channels into an 8-bit image.
+///
+/// Samples are shifted right by `bit_depth - 8` and saturated to 255.
+///
+/// # Performance
+///
+/// Blocks are transposed with the 16-bit SIMD kernels and converted while still in cache.
+#[roxygen]
+pub fn transpose_u16_to_u8<const N: usize>(
+    /// Source image data as a flat array of 16-bit samples.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed 8-bit data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height * N for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Bit depth of the source samples, 8 to 16.
+    bit_depth: u32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    check_bit_depth(bit_depth)?;
+    let shift = bit_depth - 8;
+    transpose_converted::<u16, u8, N, N>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        |src, dst| {
+            for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+                *dst = (src >> shift).min(255) as u8;
+            }
+        },
+    )
+}
+
+/// Transposes an 8-bit image with `N` channels into a 16-bit image.
+///
+/// Samples are expanded to `bit_depth` by replicating their high bits into the low ones,
+/// so 255 maps to the maximum value of the target depth.
+///
+/// # Performance
+///
+/// Blocks are transposed with the 8-bit SIMD kernels and converted while still in cache.
+#[roxygen]
+pub fn transpose_u8_to_u16<const N: usize>(
+    /// Source image data as a flat array of 8-bit samples.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed 16-bit data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (height * N for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Bit depth of the destination samples, 8 to 16.
+    bit_depth: u32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    check_bit_depth(bit_depth)?;
+    let shift = bit_depth - 8;
+    transpose_converted::<u8, u16, N, N>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        |src, dst| {
+            for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+                let v = src as u16;
+                *dst = (v << shift) | (v >> (8 - shift));
+            }
+        },
+    )
+}
@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::utils::is_valid_image_len;
 use crate::TransposeError;
 use bytemuck::{AnyBitPattern, NoUninit, Pod};
 
@@ -43,8 +44,8 @@ trait Flipper<V: Copy> {
 macro_rules! reverse_copy_flatten {
     ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr) => {
         for (dst, src) in $output
-            .chunks_exact_mut($output_stride)
-            .zip($input.chunks_exact($input_stride))
+            .chunks_mut($output_stride)
+            .zip($input.chunks($input_stride))
         {
             let dst = &mut dst[0..$width];
             let src = &src[0..$width];
@@ -58,8 +59,8 @@ macro_rules! reverse_copy_flatten {
 macro_rules! reverse_copy {
     ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr, $cn: expr) => {
         for (dst, src) in $output
-            .chunks_exact_mut($output_stride)
-            .zip($input.chunks_exact($input_stride))
+            .chunks_mut($output_stride)
+            .zip($input.chunks($input_stride))
         {
             let dst = &mut dst[0..$width * $cn];
             let src = &src[0..$width * $cn];
@@ -95,116 +96,333 @@ where
     }
 }
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[inline(always)]
+#[allow(dead_code)]
+fn reverse_rows<V: Pod>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    reverse: impl Fn(&[u8], &mut [u8]),
+) {
+    for (dst, src) in output
+        .chunks_mut(output_stride)
+        .zip(input.chunks(input_stride))
+    {
+        reverse(
+            bytemuck::cast_slice(&src[0..width]),
+            bytemuck::cast_slice_mut(&mut dst[0..width]),
+        );
+    }
+}
+
+/// Selects row reversal kernel by pixel size in bytes
+/// and falls back to plain copy for pixel sizes that have no kernel.
+#[allow(unused_macros)]
+macro_rules! reverse_pixels_dispatch {
+    ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr, $cn: expr, $kernel: ident) => {
+        let row_width = $width * $cn;
+        match std::mem::size_of::<[V; $cn]>() {
+            1 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<1>,
+            ),
+            2 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<2>,
+            ),
+            3 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<3>,
+            ),
+            4 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<4>,
+            ),
+            6 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<6>,
+            ),
+            8 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<8>,
+            ),
+            12 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<12>,
+            ),
+            16 => reverse_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<16>,
+            ),
+            _ => CommonGroupedFlipper::<V, $cn> {
+                _phantom: std::marker::PhantomData,
+            }
+            .flip($input, $input_stride, $output, $output_stride, $width),
+        }
+    };
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
 #[derive(Debug, Copy, Clone, Default)]
 struct SSSE3GroupedFlipper<V: Copy, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-macro_rules! define_flipper_grouped_x86 {
-    ($flipper_type:ident, $feature: literal) => {
-        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
-        impl<V: Copy + Pod, const N: usize> $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            #[target_feature(enable = $feature)]
-            unsafe fn flip_impl(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                reverse_copy!(input, input_stride, output, output_stride, width, N);
-            }
-        }
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<V: Copy + Pod, const N: usize> SSSE3GroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "ssse3")]
+    unsafe fn flip_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::sse::sse_reverse_pixels;
+        reverse_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            sse_reverse_pixels
+        );
+    }
+}
 
-        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
-        impl<V: Copy + Pod, const N: usize> Flipper<V> for $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            fn flip(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                unsafe { self.flip_impl(input, input_stride, output, output_stride, width) }
-            }
-        }
-    };
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<V: Copy + Pod, const N: usize> Flipper<V> for SSSE3GroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    fn flip(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.flip_impl(input, input_stride, output, output_stride, width) }
+    }
 }
 
-macro_rules! define_flipper_grouped_aarch64 {
-    ($flipper_type: ident, $feature: literal) => {
-        #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
-        impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            #[target_feature(enable = $feature)]
-            unsafe fn flip_impl(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                reverse_copy!(input, input_stride, output, output_stride, width, N);
-            }
-        }
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+#[derive(Debug, Copy, Clone, Default)]
+struct Avx2GroupedFlipper<V: Copy, const N: usize> {
+    _phantom: std::marker::PhantomData<V>,
+}
 
-        #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
-        impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Flipper<V>
-            for $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            fn flip(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                unsafe { self.flip_impl(input, input_stride, output, output_stride, width) }
-            }
-        }
-    };
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<V: Copy + Pod, const N: usize> Avx2GroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "avx2")]
+    unsafe fn flip_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::avx::avx2_reverse_pixels;
+        reverse_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            avx2_reverse_pixels
+        );
+    }
 }
 
-define_flipper_grouped_x86!(SSSE3GroupedFlipper, "ssse3");
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<V: Copy + Pod, const N: usize> Flipper<V> for Avx2GroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    fn flip(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.flip_impl(input, input_stride, output, output_stride, width) }
+    }
+}
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct Sse41GroupedFlipper<V: Copy, const N: usize> {
+struct NeonGroupedFlipper<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_flipper_grouped_x86!(Sse41GroupedFlipper, "sse4.1");
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Flipper<V>
+    for NeonGroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    fn flip(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::neon::neon_reverse_pixels;
+        reverse_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            neon_reverse_pixels
+        );
+    }
+}
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct Avx2GroupedFlipper<V: Copy, const N: usize> {
+struct WasmGroupedFlipper<V: Copy, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_flipper_grouped_x86!(Avx2GroupedFlipper, "avx2");
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<V: Copy + Pod, const N: usize> WasmGroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "simd128")]
+    unsafe fn flip_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::wasm32::wasm_reverse_pixels;
+        reverse_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            wasm_reverse_pixels
+        );
+    }
+}
 
-#[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<V: Copy + Pod, const N: usize> Flipper<V> for WasmGroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    fn flip(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.flip_impl(input, input_stride, output, output_stride, width) }
+    }
+}
+
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct SveGroupedFlipper<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
+struct RvvGroupedFlipper<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_flipper_grouped_aarch64!(SveGroupedFlipper, "sve2");
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Flipper<V>
+    for RvvGroupedFlipper<V, N>
+where
+    [V; N]: Pod,
+{
+    fn flip(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::rvv::rvv_reverse_pixels;
+        reverse_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            rvv_reverse_pixels
+        );
+    }
+}
 
 #[derive(Debug, Copy, Clone, Default)]
 struct FlipperGroupedFactory<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
@@ -219,29 +437,40 @@ where
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
     fn make_flipper(&self) -> Box<dyn Flipper<V>> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx"))]
         if std::arch::is_x86_feature_detected!("avx2") {
             return Box::new(Avx2GroupedFlipper::<V, N>::default());
         }
-        if std::arch::is_x86_feature_detected!("sse4.1") {
-            return Box::new(Sse41GroupedFlipper::<V, N>::default());
-        }
+        #[cfg(feature = "sse")]
         if std::arch::is_x86_feature_detected!("ssse3") {
             return Box::new(SSSE3GroupedFlipper::<V, N>::default());
         }
         Box::new(CommonGroupedFlipper::<V, N>::default())
     }
 
-    #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
+    #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     fn make_flipper(&self) -> Box<dyn Flipper<V>> {
-        if std::arch::is_aarch64_feature_detected!("sve2") {
-            return Box::new(SveGroupedFlipper::<V, N>::default());
+        Box::new(NeonGroupedFlipper::<V, N>::default())
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    fn make_flipper(&self) -> Box<dyn Flipper<V>> {
+        Box::new(WasmGroupedFlipper::<V, N>::default())
+    }
+
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    fn make_flipper(&self) -> Box<dyn Flipper<V>> {
+        if crate::rvv::has_rvv() {
+            return Box::new(RvvGroupedFlipper::<V, N>::default());
         }
         Box::new(CommonGroupedFlipper::<V, N>::default())
     }
 
     #[cfg(not(any(
         all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"),
-        all(target_arch = "aarch64", feature = "unsafe")
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"),
+        all(target_arch = "riscv64", feature = "unsafe", feature = "rvv")
     )))]
     fn make_flipper(&self) -> Box<dyn Flipper<V>> {
         Box::new(CommonGroupedFlipper::<V, N>::default())
@@ -372,20 +601,20 @@ define_flipper_x86!(SSSE3Flipper, "ssse3");
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    feature = "nightly_avx512"
+    fast_transpose_avx512
 ))]
 #[derive(Debug, Copy, Clone, Default)]
 struct Avx512Flipper<V: Copy> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-#[cfg(feature = "nightly_avx512")]
+#[cfg(fast_transpose_avx512)]
 define_flipper_x86!(Avx512Flipper, "avx512bw");
 
 impl<V: Copy + Default + 'static> FlipperFactory<V> {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
     fn make_flipper(&self) -> Box<dyn Flipper<V>> {
-        #[cfg(feature = "nightly_avx512")]
+        #[cfg(fast_transpose_avx512)]
         if std::arch::is_x86_feature_detected!("avx512bw") {
             return Box::new(Avx512Flipper::<V>::default());
         }
@@ -439,10 +668,10 @@ pub fn flip_arbitrary<V: Copy + Default + 'static>(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width {
@@ -483,10 +712,10 @@ fn flip_arbitrary_image<V: Copy + Default + 'static + Pod, const N: usize>(
 where
     [V; N]: Pod,
 {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * N {
@@ -524,7 +753,7 @@ pub fn flip_plane(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    flip_arbitrary(input, input_stride, output, output_stride, width, height)
+    flip_arbitrary_image::<u8, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha flipping
@@ -620,7 +849,7 @@ pub fn flip_plane16(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    flip_arbitrary(input, input_stride, output, output_stride, width, height)
+    flip_arbitrary_image::<u16, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha image flipping
@@ -716,7 +945,7 @@ pub fn flip_plane_f32(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    flip_arbitrary(input, input_stride, output, output_stride, width, height)
+    flip_arbitrary_image::<f32, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha image flipping
@@ -27,9 +27,12 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 use crate::float32_invoker::TransposeExecutor;
+use crate::fused::FusedTranspose;
 #[allow(unused_imports)]
 use crate::rgba8::*;
 use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
+#[allow(unused_imports)]
+use crate::utils::is_valid_image_len;
 use crate::{FlipMode, FlopMode, TransposeError};
 use std::marker::PhantomData;
 
@@ -174,10 +177,10 @@ impl<const FLOP: bool, const FLIP: bool> Ssse3DefaultExecutor<FLOP, FLIP> {
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width * 2, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height * 2, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width * 2 {
@@ -255,10 +258,10 @@ impl<const FLOP: bool, const FLIP: bool> AvxDefaultExecutor<FLOP, FLIP> {
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width * 2, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height * 2, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width * 2 {
@@ -334,10 +337,10 @@ impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width * 2, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height * 2, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width * 2 {
@@ -429,10 +432,121 @@ fn make_avx2_default_executor(
     None
 }
 
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm2x2F32x2<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockWasm2x2F32x2<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_f32x2_2x2;
+        wasm_transpose_f32x2_2x2::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+#[derive(Copy, Clone, Default)]
+struct WasmDefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> WasmDefaultExecutor<FLOP, FLIP> {
+    #[target_feature(enable = "simd128")]
+    unsafe fn execute_impl(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * 2, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * 2, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * 2 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * 2 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor::<f32, 2, 2, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockWasm2x2F32x2::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 2, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for WasmDefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        unsafe { self.execute_impl(input, input_stride, output, output_stride, width, height) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+fn make_wasm_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Box<dyn TransposeExecutor<f32>> {
+    match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, false>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, true>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, true>::default()),
+        },
+    }
+}
+
 pub(crate) fn prepare_f32_cbcr_executor(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
 ) -> Box<dyn TransposeExecutor<f32>> {
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        use crate::float32_invoker::make_rvv_executor;
+        if let Some(executor) = make_rvv_executor::<2>(flip_mode, flop_mode) {
+            return executor;
+        }
+    }
     #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
     {
         if std::arch::is_x86_feature_detected!("avx2") {
@@ -455,9 +569,23 @@ pub(crate) fn prepare_f32_cbcr_executor(
     }
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
+        #[cfg(feature = "sve")]
+        {
+            use crate::float32_invoker::make_sve_executor;
+            if let Some(executor) = make_sve_executor::<2>(flip_mode, flop_mode) {
+                return executor;
+            }
+        }
         make_neon_default_executor(flip_mode, flop_mode)
     }
-    #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    {
+        make_wasm_default_executor(flip_mode, flop_mode)
+    }
+    #[cfg(not(any(
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+    )))]
     {
         Box::new(DefaultCbCrExecutor {
             flip_mode,
@@ -466,3 +594,96 @@ pub(crate) fn prepare_f32_cbcr_executor(
         })
     }
 }
+
+/// Fused transpose of interleaved float chroma going through the block kernels of this module.
+pub(crate) struct CbCrF32Fused {}
+
+impl FusedTranspose<f32> for CbCrF32Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[f32], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<f32, O, 4, 2, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4F32x2::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<f32, O, 2, 2, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm2x2F32x2::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<f32, O, 4, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx4x4F32x2::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<f32, O, 2, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSse2x2F32x2::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<2>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
@@ -26,9 +26,12 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::fused::FusedTranspose;
 #[allow(unused_imports)]
 use crate::rgba8::*;
-use crate::{transpose_arbitrary, FlipMode, FlopMode, TransposeError};
+#[allow(unused_imports)]
+use crate::utils::is_valid_image_len;
+use crate::{transpose_arbitrary, FlipMode, FlopMode, StoreHint, TransposeError};
 use std::marker::PhantomData;
 
 pub(crate) trait TransposeExecutor<F> {
@@ -104,22 +107,25 @@ impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
 }
 
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
-#[derive(Copy, Clone, Default)]
-struct NeonDefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+#[derive(Copy, Clone)]
+struct NeonDefaultExecutor<const FLOP: bool, const FLIP: bool> {
+    store_hint: StoreHint,
+}
 
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 pub(crate) fn make_neon_default_executor(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Box<dyn TransposeExecutor<f32>> {
     match flip_mode {
         FlipMode::NoFlip => match flop_mode {
-            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, false>::default()),
-            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, false>::default()),
+            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, false> { store_hint }),
+            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, false> { store_hint }),
         },
         FlipMode::Flip => match flop_mode {
-            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, true>::default()),
-            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, true>::default()),
+            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, true> { store_hint }),
+            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, true> { store_hint }),
         },
     }
 }
@@ -137,10 +143,10 @@ impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width {
@@ -152,6 +158,18 @@ impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
 
         let mut y = 0usize;
 
+        y = transpose_executor_streaming::<f32, 8, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockNeon8x8F32::<FLOP, FLIP> {},
+            self.store_hint,
+        );
+
         y = transpose_executor::<f32, 8, 1, FLOP, FLIP>(
             input,
             input_stride,
@@ -249,8 +267,10 @@ impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
     feature = "unsafe",
     feature = "sse"
 ))]
-#[derive(Copy, Clone, Default)]
-struct Ssse3DefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+#[derive(Copy, Clone)]
+struct Ssse3DefaultExecutor<const FLOP: bool, const FLIP: bool> {
+    store_hint: StoreHint,
+}
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
@@ -260,16 +280,17 @@ struct Ssse3DefaultExecutor<const FLOP: bool, const FLIP: bool> {}
 fn make_ssse3_default_executor(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Option<Box<dyn TransposeExecutor<f32>>> {
     if std::arch::is_x86_feature_detected!("ssse3") {
         return Some(match flip_mode {
             FlipMode::NoFlip => match flop_mode {
-                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, false>::default()),
-                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, false>::default()),
+                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, false> { store_hint }),
+                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, false> { store_hint }),
             },
             FlipMode::Flip => match flop_mode {
-                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, true>::default()),
-                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, true>::default()),
+                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, true> { store_hint }),
+                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, true> { store_hint }),
             },
         });
     }
@@ -292,10 +313,10 @@ impl<const FLOP: bool, const FLIP: bool> Ssse3DefaultExecutor<FLOP, FLIP> {
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width {
@@ -307,6 +328,18 @@ impl<const FLOP: bool, const FLIP: bool> Ssse3DefaultExecutor<FLOP, FLIP> {
 
         let mut y = 0usize;
 
+        y = transpose_executor_streaming::<f32, 8, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockSSSE38x8::<FLOP, FLIP> {},
+            self.store_hint,
+        );
+
         y = transpose_executor::<f32, 8, 1, FLOP, FLIP>(
             input,
             input_stride,
@@ -365,23 +398,26 @@ impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
 }
 
 #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
-#[derive(Copy, Clone, Default)]
-struct Avx2DefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+#[derive(Copy, Clone)]
+struct Avx2DefaultExecutor<const FLOP: bool, const FLIP: bool> {
+    store_hint: StoreHint,
+}
 
 #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
 pub(crate) fn make_avx2_default_executor(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Option<Box<dyn TransposeExecutor<f32>>> {
     if std::arch::is_x86_feature_detected!("avx2") {
         return Some(match flip_mode {
             FlipMode::NoFlip => match flop_mode {
-                FlopMode::NoFlop => Box::new(Avx2DefaultExecutor::<false, false>::default()),
-                FlopMode::Flop => Box::new(Avx2DefaultExecutor::<true, false>::default()),
+                FlopMode::NoFlop => Box::new(Avx2DefaultExecutor::<false, false> { store_hint }),
+                FlopMode::Flop => Box::new(Avx2DefaultExecutor::<true, false> { store_hint }),
             },
             FlipMode::Flip => match flop_mode {
-                FlopMode::NoFlop => Box::new(Avx2DefaultExecutor::<false, true>::default()),
-                FlopMode::Flop => Box::new(Avx2DefaultExecutor::<true, true>::default()),
+                FlopMode::NoFlop => Box::new(Avx2DefaultExecutor::<false, true> { store_hint }),
+                FlopMode::Flop => Box::new(Avx2DefaultExecutor::<true, true> { store_hint }),
             },
         });
     }
@@ -400,10 +436,10 @@ impl<const FLOP: bool, const FLIP: bool> Avx2DefaultExecutor<FLOP, FLIP> {
         width: usize,
         height: usize,
     ) -> Result<(), TransposeError> {
-        if input.len() != input_stride * height {
+        if !is_valid_image_len(input.len(), input_stride, width, height) {
             return Err(TransposeError::MismatchDimensions);
         }
-        if output.len() != output_stride * width {
+        if !is_valid_image_len(output.len(), output_stride, height, width) {
             return Err(TransposeError::MismatchDimensions);
         }
         if input_stride < width {
@@ -415,6 +451,18 @@ impl<const FLOP: bool, const FLIP: bool> Avx2DefaultExecutor<FLOP, FLIP> {
 
         let mut y = 0usize;
 
+        y = transpose_executor_streaming::<f32, 8, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockAvx28x8::<FLOP, FLIP> {},
+            self.store_hint,
+        );
+
         y = transpose_executor::<f32, 8, 1, FLOP, FLIP>(
             input,
             input_stride,
@@ -468,13 +516,408 @@ impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
     }
 }
 
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+struct TransposeBlockAvx512_16x16<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockAvx512_16x16<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::avx512::avx512_transpose_16x16_u32;
+        avx512_transpose_16x16_u32::<FLOP, FLIP>(
+            bytemuck::cast_slice(src),
+            src_stride * std::mem::size_of::<f32>(),
+            bytemuck::cast_slice_mut(dst),
+            dst_stride * std::mem::size_of::<f32>(),
+        );
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+#[derive(Copy, Clone)]
+struct Avx512DefaultExecutor<const FLOP: bool, const FLIP: bool> {
+    store_hint: StoreHint,
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+fn make_avx512_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+    store_hint: StoreHint,
+) -> Option<Box<dyn TransposeExecutor<f32>>> {
+    if std::arch::is_x86_feature_detected!("avx512f") {
+        return Some(match flip_mode {
+            FlipMode::NoFlip => match flop_mode {
+                FlopMode::NoFlop => Box::new(Avx512DefaultExecutor::<false, false> { store_hint }),
+                FlopMode::Flop => Box::new(Avx512DefaultExecutor::<true, false> { store_hint }),
+            },
+            FlipMode::Flip => match flop_mode {
+                FlopMode::NoFlop => Box::new(Avx512DefaultExecutor::<false, true> { store_hint }),
+                FlopMode::Flop => Box::new(Avx512DefaultExecutor::<true, true> { store_hint }),
+            },
+        });
+    }
+    None
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> Avx512DefaultExecutor<FLOP, FLIP> {
+    #[target_feature(enable = "avx512f")]
+    unsafe fn execute_impl(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor_streaming::<f32, 16, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockAvx512_16x16::<FLOP, FLIP> {},
+            self.store_hint,
+        );
+
+        y = transpose_executor::<f32, 16, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockAvx512_16x16::<FLOP, FLIP> {},
+        );
+
+        y = transpose_executor::<f32, 8, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockAvx28x8::<FLOP, FLIP> {},
+        );
+
+        y = transpose_executor::<f32, 4, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockSSSE34x4::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for Avx512DefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        unsafe { self.execute_impl(input, input_stride, output, output_stride, width, height) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32> for TransposeBlockWasm4x4<FLOP, FLIP> {
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_4x4_f32;
+        wasm_transpose_4x4_f32::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+#[derive(Copy, Clone, Default)]
+struct WasmDefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> WasmDefaultExecutor<FLOP, FLIP> {
+    #[target_feature(enable = "simd128")]
+    unsafe fn execute_impl(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor::<f32, 4, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockWasm4x4::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 1, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for WasmDefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        unsafe { self.execute_impl(input, input_stride, output, output_stride, width, height) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+fn make_wasm_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Box<dyn TransposeExecutor<f32>> {
+    match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, false>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, true>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, true>::default()),
+        },
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "sve"))]
+#[derive(Copy, Clone, Default)]
+struct SveExecutor<const CN: usize, const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "sve"))]
+impl<const CN: usize, const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for SveExecutor<CN, FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * CN, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * CN, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * CN {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * CN {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        use crate::sve::sve_transpose;
+        sve_transpose::<f32, CN, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+        );
+
+        Ok(())
+    }
+}
+
+/// Makes SVE executor for `CN` channels f32 image if CPU has SVE wider than NEON.
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "sve"))]
+pub(crate) fn make_sve_executor<const CN: usize>(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Option<Box<dyn TransposeExecutor<f32>>> {
+    if !crate::sve::has_wide_sve() {
+        return None;
+    }
+    Some(match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(SveExecutor::<CN, false, false>::default()),
+            FlopMode::Flop => Box::new(SveExecutor::<CN, true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(SveExecutor::<CN, false, true>::default()),
+            FlopMode::Flop => Box::new(SveExecutor::<CN, true, true>::default()),
+        },
+    })
+}
+
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+#[derive(Copy, Clone, Default)]
+struct RvvExecutor<const CN: usize, const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+impl<const CN: usize, const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for RvvExecutor<CN, FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * CN, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * CN, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * CN {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * CN {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        use crate::rvv::rvv_transpose;
+        rvv_transpose::<f32, CN, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+        );
+
+        Ok(())
+    }
+}
+
+/// Makes RVV executor for `CN` channels f32 image if RISC-V Vector extension is available.
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+pub(crate) fn make_rvv_executor<const CN: usize>(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Option<Box<dyn TransposeExecutor<f32>>> {
+    if !crate::rvv::has_rvv() {
+        return None;
+    }
+    Some(match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(RvvExecutor::<CN, false, false>::default()),
+            FlopMode::Flop => Box::new(RvvExecutor::<CN, true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(RvvExecutor::<CN, false, true>::default()),
+            FlopMode::Flop => Box::new(RvvExecutor::<CN, true, true>::default()),
+        },
+    })
+}
+
+#[allow(unused_variables)]
 pub(crate) fn prepare_f32_plane_executor(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Box<dyn TransposeExecutor<f32>> {
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        if let Some(executor) = make_rvv_executor::<1>(flip_mode, flop_mode) {
+            return executor;
+        }
+    }
+    #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+    {
+        if let Some(executor) = make_avx512_default_executor(flip_mode, flop_mode, store_hint) {
+            return executor;
+        }
+    }
     #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
     {
-        if let Some(executor) = make_avx2_default_executor(flip_mode, flop_mode) {
+        if let Some(executor) = make_avx2_default_executor(flip_mode, flop_mode, store_hint) {
             return executor;
         }
     }
@@ -484,15 +927,28 @@ pub(crate) fn prepare_f32_plane_executor(
         feature = "sse"
     ))]
     {
-        if let Some(executor) = make_ssse3_default_executor(flip_mode, flop_mode) {
+        if let Some(executor) = make_ssse3_default_executor(flip_mode, flop_mode, store_hint) {
             return executor;
         }
     }
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
-        make_neon_default_executor(flip_mode, flop_mode)
+        #[cfg(feature = "sve")]
+        {
+            if let Some(executor) = make_sve_executor::<1>(flip_mode, flop_mode) {
+                return executor;
+            }
+        }
+        make_neon_default_executor(flip_mode, flop_mode, store_hint)
+    }
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    {
+        make_wasm_default_executor(flip_mode, flop_mode)
     }
-    #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+    #[cfg(not(any(
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+    )))]
     {
         Box::new(DefaultExecutor {
             flip_mode,
@@ -501,3 +957,112 @@ pub(crate) fn prepare_f32_plane_executor(
         })
     }
 }
+
+/// Fused transpose of float planes going through the block kernels of this module.
+pub(crate) struct PlaneF32Fused {}
+
+impl FusedTranspose<f32> for PlaneF32Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[f32], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<f32, O, 8, 1, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon8x8F32::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<f32, O, 4, 1, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+            if std::arch::is_x86_feature_detected!("avx512f") {
+                unsafe {
+                    crate::fused::transpose_fused_avx512f::<f32, O, 16, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx512_16x16::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<f32, O, 8, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx28x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<f32, O, 8, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSSSE38x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<1>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
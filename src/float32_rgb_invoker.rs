@@ -0,0 +1,513 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::float32_invoker::TransposeExecutor;
+use crate::fused::FusedTranspose;
+#[allow(unused_imports)]
+use crate::rgba8::*;
+use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
+#[allow(unused_imports)]
+use crate::utils::is_valid_image_len;
+use crate::{FlipMode, FlopMode, TransposeError};
+use std::marker::PhantomData;
+
+#[allow(dead_code)]
+#[derive(Copy, Clone)]
+struct DefaultRgbExecutor<F> {
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+    _phantom: PhantomData<F>,
+}
+
+#[allow(dead_code)]
+impl<F: Copy> TransposeExecutor<F> for DefaultRgbExecutor<F> {
+    fn execute(
+        &self,
+        input: &[F],
+        input_stride: usize,
+        output: &mut [F],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        transpose_arbitrary_grouped::<F, 3>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            self.flip_mode,
+            self.flop_mode,
+        )
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+struct TransposeBlockNeon4x4F32x3<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockNeon4x4F32x3<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::neon::neon_transpose_f32x3_4x4;
+        neon_transpose_f32x3_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+struct TransposeBlockSse4x4F32x3<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockSse4x4F32x3<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::sse::ssse_transpose_f32x3_4x4;
+        ssse_transpose_f32x3_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+#[derive(Copy, Clone, Default)]
+struct NeonDefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+pub(crate) fn make_neon_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Box<dyn TransposeExecutor<f32>> {
+    match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, false>::default()),
+            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(NeonDefaultExecutor::<false, true>::default()),
+            FlopMode::Flop => Box::new(NeonDefaultExecutor::<true, true>::default()),
+        },
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+#[derive(Copy, Clone, Default)]
+struct Ssse3DefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool> Ssse3DefaultExecutor<FLOP, FLIP> {
+    #[target_feature(enable = "ssse3")]
+    unsafe fn execute_impl(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * 3, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * 3, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor::<f32, 4, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockSse4x4F32x3::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for Ssse3DefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        unsafe { self.execute_impl(input, input_stride, output, output_stride, width, height) }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for NeonDefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * 3, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * 3, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor::<f32, 4, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockNeon4x4F32x3::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+fn make_ssse3_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Option<Box<dyn TransposeExecutor<f32>>> {
+    if std::arch::is_x86_feature_detected!("ssse3") {
+        return Some(match flip_mode {
+            FlipMode::NoFlip => match flop_mode {
+                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, false>::default()),
+                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, false>::default()),
+            },
+            FlipMode::Flip => match flop_mode {
+                FlopMode::NoFlop => Box::new(Ssse3DefaultExecutor::<false, true>::default()),
+                FlopMode::Flop => Box::new(Ssse3DefaultExecutor::<true, true>::default()),
+            },
+        });
+    }
+    None
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4F32x3<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockWasm4x4F32x3<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_f32x3_4x4;
+        wasm_transpose_f32x3_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+#[derive(Copy, Clone, Default)]
+struct WasmDefaultExecutor<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> WasmDefaultExecutor<FLOP, FLIP> {
+    #[target_feature(enable = "simd128")]
+    unsafe fn execute_impl(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        if !is_valid_image_len(input.len(), input_stride, width * 3, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if !is_valid_image_len(output.len(), output_stride, height * 3, width) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if input_stride < width * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if output_stride < height * 3 {
+            return Err(TransposeError::MismatchDimensions);
+        }
+
+        let mut y = 0usize;
+
+        y = transpose_executor::<f32, 4, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+            TransposeBlockWasm4x4F32x3::<FLOP, FLIP> {},
+        );
+
+        transpose_section::<f32, 3, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            y,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeExecutor<f32>
+    for WasmDefaultExecutor<FLOP, FLIP>
+{
+    fn execute(
+        &self,
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [f32],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), TransposeError> {
+        unsafe { self.execute_impl(input, input_stride, output, output_stride, width, height) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+fn make_wasm_default_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Box<dyn TransposeExecutor<f32>> {
+    match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, false>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, false>::default()),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => Box::new(WasmDefaultExecutor::<false, true>::default()),
+            FlopMode::Flop => Box::new(WasmDefaultExecutor::<true, true>::default()),
+        },
+    }
+}
+
+pub(crate) fn prepare_f32_rgb_executor(
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Box<dyn TransposeExecutor<f32>> {
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        use crate::float32_invoker::make_rvv_executor;
+        if let Some(executor) = make_rvv_executor::<3>(flip_mode, flop_mode) {
+            return executor;
+        }
+    }
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ))]
+    {
+        if std::arch::is_x86_feature_detected!("ssse3") {
+            if let Some(executor) = make_ssse3_default_executor(flip_mode, flop_mode) {
+                return executor;
+            }
+        }
+    }
+    #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+    {
+        make_neon_default_executor(flip_mode, flop_mode)
+    }
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    {
+        make_wasm_default_executor(flip_mode, flop_mode)
+    }
+    #[cfg(not(any(
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+    )))]
+    {
+        Box::new(DefaultRgbExecutor {
+            flip_mode,
+            flop_mode,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// Fused transpose of float RGB going through the block kernels of this module.
+pub(crate) struct RgbF32Fused {}
+
+impl FusedTranspose<f32> for RgbF32Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[f32], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<f32, O, 4, 3, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4F32x3::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<f32, O, 4, 3, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4F32x3::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<f32, O, 4, 3, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSse4x4F32x3::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<3>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
@@ -0,0 +1,349 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::fused::FusedTranspose;
+#[allow(unused_imports)]
+use crate::rgba8::*;
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+struct TransposeBlockNeon4x4F32x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockNeon4x4F32x4<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::neon::neon_transpose_f32x4_4x4;
+        neon_transpose_f32x4_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+struct TransposeBlockSse4x4F32x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockSse4x4F32x4<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::sse::sse_transpose_f32x4_4x4;
+        sse_transpose_f32x4_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposeBlockAvx4x4F32x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockAvx4x4F32x4<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::avx::avx2_transpose_f32x4_4x4;
+        unsafe { avx2_transpose_f32x4_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4F32x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<f32>
+    for TransposeBlockWasm4x4F32x4<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_f32x4_4x4;
+        wasm_transpose_f32x4_4x4::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+struct TransposeBlockNeon4x4F32x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<f32>
+    for TransposeBlockNeon4x4F32x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::neon::neon_transpose_f32x4_4x4_swizzled;
+        neon_transpose_f32x4_4x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+struct TransposeBlockSse4x4F32x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<f32>
+    for TransposeBlockSse4x4F32x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::sse::sse_transpose_f32x4_4x4_swizzled;
+        sse_transpose_f32x4_4x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposeBlockAvx4x4F32x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<f32>
+    for TransposeBlockAvx4x4F32x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::avx::avx2_transpose_f32x4_4x4_swizzled;
+        unsafe {
+            avx2_transpose_f32x4_4x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride)
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4F32x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<f32>
+    for TransposeBlockWasm4x4F32x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[f32], src_stride: usize, dst: &mut [f32], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_f32x4_4x4_swizzled;
+        wasm_transpose_f32x4_4x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+/// Fused transpose of float RGBA going through the block kernels of this module.
+pub(crate) struct RgbaF32Fused {}
+
+impl FusedTranspose<f32> for RgbaF32Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[f32], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<f32, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4F32x4::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<f32, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4F32x4::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<f32, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx4x4F32x4::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<f32, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSse4x4F32x4::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<4>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
+
+/// Fused transpose of float RGBA whose channels are reordered by `KEY` from
+/// [crate::ChannelOrder] inside the block kernels, so converter only copies.
+pub(crate) struct RgbaF32Swizzled<const KEY: usize> {}
+
+impl<const KEY: usize> FusedTranspose<f32> for RgbaF32Swizzled<KEY> {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[f32],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[f32], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<f32, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4F32x4Swizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<f32, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4F32x4Swizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<f32, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx4x4F32x4Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<f32, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSse4x4F32x4Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::transpose_fused::<f32, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockScalarSwizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+    }
+}
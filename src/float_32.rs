@@ -29,8 +29,9 @@
 #![forbid(unsafe_code)]
 use crate::float32_cbcr_invoker::prepare_f32_cbcr_executor;
 use crate::float32_invoker::prepare_f32_plane_executor;
+use crate::float32_rgb_invoker::prepare_f32_rgb_executor;
 use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
-use crate::{FlipMode, FlopMode, TransposeError};
+use crate::{FlipMode, FlopMode, StoreHint, TransposeError};
 use roxygen::roxygen;
 
 /// Transposes a single-channel 32-bit float image.
@@ -62,7 +63,37 @@ pub fn transpose_plane_f32(
     /// Vertical mirroring mode for rotation control.
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    let executor = prepare_f32_plane_executor(flip_mode, flop_mode);
+    let executor = prepare_f32_plane_executor(flip_mode, flop_mode, StoreHint::Temporal);
+    executor.execute(input, input_stride, output, output_stride, width, height)
+}
+
+/// Transposes a single-channel 32-bit float image, choosing how destination rows are written.
+///
+/// Same as [transpose_plane_f32], with `store_hint` deciding whether destination rows bypass the cache.
+/// Streaming stores pay off only when the destination is far larger than the cache
+/// and is not read back soon after, measure before enabling.
+#[roxygen]
+pub fn transpose_plane_f32_hinted(
+    /// Source image data as a flat array of 32-bit float pixels.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (height for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+    /// Whether destination rows go through the cache or are streamed past it.
+    store_hint: StoreHint,
+) -> Result<(), TransposeError> {
+    let executor = prepare_f32_plane_executor(flip_mode, flop_mode, store_hint);
     executor.execute(input, input_stride, output, output_stride, width, height)
 }
 
@@ -127,16 +158,8 @@ pub fn transpose_rgb_f32(
     /// Vertical mirroring mode for rotation control.
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    transpose_arbitrary_grouped::<f32, 3>(
-        input,
-        input_stride,
-        output,
-        output_stride,
-        width,
-        height,
-        flip_mode,
-        flop_mode,
-    )
+    let executor = prepare_f32_rgb_executor(flip_mode, flop_mode);
+    executor.execute(input, input_stride, output, output_stride, width, height)
 }
 
 /// Transposes a 32-bit float RGBA image.
@@ -168,6 +191,13 @@ pub fn transpose_rgba_f32(
     /// Vertical mirroring mode for rotation control.
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        use crate::float32_invoker::make_rvv_executor;
+        if let Some(executor) = make_rvv_executor::<4>(flip_mode, flop_mode) {
+            return executor.execute(input, input_stride, output, output_stride, width, height);
+        }
+    }
     transpose_arbitrary_grouped::<f32, 4>(
         input,
         input_stride,
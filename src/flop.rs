@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::utils::is_valid_image_len;
 use crate::TransposeError;
 use bytemuck::{AnyBitPattern, NoUninit, Pod};
 
@@ -43,9 +44,9 @@ trait Flopper<V: Copy> {
 macro_rules! flop_grouped_copy {
     ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr, $cn: expr) => {
         for (dst, src) in $output
-            .chunks_exact_mut($output_stride)
+            .chunks_mut($output_stride)
             .rev()
-            .zip($input.chunks_exact($input_stride))
+            .zip($input.chunks($input_stride))
         {
             let dst = &mut dst[0..$width * $cn];
             let src = &src[0..$width * $cn];
@@ -98,9 +99,9 @@ impl<V: Copy> Flopper<V> for CommonFlopper<V> {
         width: usize,
     ) {
         for (dst, src) in output
-            .chunks_exact_mut(output_stride)
+            .chunks_mut(output_stride)
             .rev()
-            .zip(input.chunks_exact(input_stride))
+            .zip(input.chunks(input_stride))
         {
             let dst = &mut dst[0..width];
             let src = &src[0..width];
@@ -124,7 +125,7 @@ struct FlopperAvx2GroupedFactory<V: Copy + Pod + NoUninit + AnyBitPattern, const
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    feature = "nightly_avx512"
+    fast_transpose_avx512
 ))]
 #[derive(Debug, Copy, Clone, Default)]
 struct FlopperAvx512GroupedFactory<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
@@ -153,7 +154,7 @@ where
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    feature = "nightly_avx512"
+    fast_transpose_avx512
 ))]
 impl<V: Copy + 'static + Copy + Pod + NoUninit + AnyBitPattern, const N: usize>
     FlopperAvx512GroupedFactory<V, N>
@@ -176,7 +177,7 @@ where
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    feature = "nightly_avx512"
+    fast_transpose_avx512
 ))]
 impl<V: Copy + 'static + Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Flopper<V>
     for FlopperAvx512GroupedFactory<V, N>
@@ -223,7 +224,7 @@ where
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
     fn make_flipper(&self) -> Box<dyn Flopper<V>> {
-        #[cfg(feature = "nightly_avx512")]
+        #[cfg(fast_transpose_avx512)]
         if std::arch::is_x86_feature_detected!("avx512f") {
             return Box::new(FlopperAvx512GroupedFactory::<V, N>::default());
         }
@@ -268,10 +269,10 @@ pub fn flop_arbitrary<V: Copy + Default>(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width {
@@ -311,10 +312,10 @@ fn flop_arbitrary_grouped<V: Copy + Default + Pod, const N: usize>(
 where
     [V; N]: Pod,
 {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * N {
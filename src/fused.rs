@@ -0,0 +1,488 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::cbcr8::CbCr8Fused;
+use crate::float32_cbcr_invoker::CbCrF32Fused;
+use crate::float32_invoker::PlaneF32Fused;
+use crate::float32_rgb_invoker::RgbF32Fused;
+use crate::float32_rgba_invoker::RgbaF32Fused;
+use crate::plane16::Plane16Fused;
+use crate::plane8::Plane8Fused;
+use crate::rgb16::Rgb16Fused;
+use crate::rgb8::Rgb8Fused;
+use crate::rgba16::Rgba16Fused;
+use crate::rgba8::{CbCr16Fused, Rgba8Fused, TransposeBlock};
+use crate::{FlipMode, FlopMode};
+
+/// Bytes of transposed samples gathered before conversion, a strip of blocks stays in L1
+/// while it is converted, and destination rows are written in runs of a whole strip.
+const STRIP_BYTES: usize = 16 * 1024;
+
+const fn strip_len<V, const BLOCK_SIZE: usize, const N: usize>() -> usize {
+    let column_bytes = BLOCK_SIZE * N * std::mem::size_of::<V>();
+    let blocks = STRIP_BYTES / column_bytes / BLOCK_SIZE;
+    if blocks == 0 {
+        BLOCK_SIZE
+    } else {
+        blocks * BLOCK_SIZE
+    }
+}
+
+/// Plain block transpose for pixel layouts without a SIMD kernel.
+pub(crate) struct TransposeBlockScalar<
+    const BLOCK_SIZE: usize,
+    const N: usize,
+    const FLOP: bool,
+    const FLIP: bool,
+> {}
+
+impl<V: Copy, const BLOCK_SIZE: usize, const N: usize, const FLOP: bool, const FLIP: bool>
+    TransposeBlock<V> for TransposeBlockScalar<BLOCK_SIZE, N, FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[V], src_stride: usize, dst: &mut [V], dst_stride: usize) {
+        for y in 0..BLOCK_SIZE {
+            let src_y = if FLIP { BLOCK_SIZE - 1 - y } else { y };
+            for x in 0..BLOCK_SIZE {
+                let dst_x = if FLOP { x } else { BLOCK_SIZE - 1 - x };
+                dst[dst_x * dst_stride + y * N..][..N]
+                    .copy_from_slice(&src[src_y * src_stride + x * N..][..N]);
+            }
+        }
+    }
+}
+
+/// Transposes a strip of source rows block by block with `exec` into a scratch that stays
+/// in L1, then hands every transposed strip row to `converter` that writes `M` channel
+/// destination pixels, so samples are converted straight out of the block kernels.
+///
+/// Partial blocks on the right and bottom edges are padded into a full block, so every
+/// pixel goes through the kernel and no scalar section is needed.
+#[inline(always)]
+pub(crate) fn transpose_fused<
+    I: Copy + Default,
+    O,
+    const BLOCK_SIZE: usize,
+    const N: usize,
+    const M: usize,
+    const FLOP: bool,
+    const FLIP: bool,
+>(
+    input: &[I],
+    input_stride: usize,
+    output: &mut [O],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    exec: impl TransposeBlock<I>,
+    converter: &impl Fn(&[I], &mut [O]),
+) {
+    let strip = strip_len::<I, BLOCK_SIZE, N>();
+    let strip_stride = strip * N;
+    let block_stride = BLOCK_SIZE * N;
+
+    let mut scratch = vec![I::default(); BLOCK_SIZE * strip_stride];
+    let mut src_block = vec![I::default(); BLOCK_SIZE * block_stride];
+    let mut dst_block = vec![I::default(); BLOCK_SIZE * block_stride];
+
+    for strip_y in (0..height).step_by(strip) {
+        let strip_height = strip.min(height - strip_y);
+        let strip_end = strip_y + strip_height;
+        // Strip covers destination columns from `strip_y`, mirrored from the end when flipped.
+        let dst_column = if FLIP { height - strip_end } else { strip_y };
+
+        for x in (0..width).step_by(BLOCK_SIZE) {
+            let block_width = BLOCK_SIZE.min(width - x);
+            let dst_row = if FLOP { x } else { width - x - block_width };
+
+            for y in (strip_y..strip_end).step_by(BLOCK_SIZE) {
+                let block_height = BLOCK_SIZE.min(strip_end - y);
+                let offset = if FLIP {
+                    strip_end - y - block_height
+                } else {
+                    y - strip_y
+                } * N;
+                let src = &input[y * input_stride + x * N..];
+
+                if block_width == BLOCK_SIZE && block_height == BLOCK_SIZE {
+                    exec.transpose_block(src, input_stride, &mut scratch[offset..], strip_stride);
+                    continue;
+                }
+
+                // Source is placed so that its transposed pixels start at the top left corner.
+                let pad_y = if FLIP { BLOCK_SIZE - block_height } else { 0 };
+                let pad_x = if FLOP { 0 } else { BLOCK_SIZE - block_width };
+                for (dst, src) in src_block[pad_y * block_stride..]
+                    .chunks_exact_mut(block_stride)
+                    .zip(src.chunks(input_stride))
+                    .take(block_height)
+                {
+                    dst[pad_x * N..(pad_x + block_width) * N]
+                        .copy_from_slice(&src[..block_width * N]);
+                }
+                exec.transpose_block(&src_block, block_stride, &mut dst_block, block_stride);
+                for (dst, src) in scratch[offset..]
+                    .chunks_mut(strip_stride)
+                    .zip(dst_block.chunks_exact(block_stride))
+                    .take(block_width)
+                {
+                    dst[..block_height * N].copy_from_slice(&src[..block_height * N]);
+                }
+            }
+
+            for (src, dst) in scratch
+                .chunks_exact(strip_stride)
+                .zip(output[dst_row * output_stride..].chunks_mut(output_stride))
+                .take(block_width)
+            {
+                converter(
+                    &src[..strip_height * N],
+                    &mut dst[dst_column * M..(dst_column + strip_height) * M],
+                );
+            }
+        }
+    }
+}
+
+macro_rules! define_fused_executor {
+    ($(#[$meta: meta])* $name: ident, $feature: literal) => {
+        $(#[$meta])*
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn $name<
+            I: Copy + Default,
+            O,
+            const BLOCK_SIZE: usize,
+            const N: usize,
+            const M: usize,
+            const FLOP: bool,
+            const FLIP: bool,
+        >(
+            input: &[I],
+            input_stride: usize,
+            output: &mut [O],
+            output_stride: usize,
+            width: usize,
+            height: usize,
+            exec: impl TransposeBlock<I>,
+            converter: &impl Fn(&[I], &mut [O]),
+        ) {
+            transpose_fused::<I, O, BLOCK_SIZE, N, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                exec,
+                converter,
+            )
+        }
+    };
+}
+
+define_fused_executor!(
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ))]
+    transpose_fused_ssse3,
+    "ssse3"
+);
+define_fused_executor!(
+    #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+    transpose_fused_avx2,
+    "avx2"
+);
+define_fused_executor!(
+    #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+    transpose_fused_avx512,
+    "avx512bw"
+);
+define_fused_executor!(
+    #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+    transpose_fused_avx512f,
+    "avx512f"
+);
+define_fused_executor!(
+    #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+    transpose_fused_avx512vbmi,
+    "avx512bw,avx512vbmi"
+);
+define_fused_executor!(
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    transpose_fused_wasm,
+    "simd128"
+);
+
+macro_rules! define_vectorized_runner {
+    ($(#[$meta: meta])* $name: ident, $feature: literal) => {
+        $(#[$meta])*
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(f: impl FnOnce()) {
+            f()
+        }
+    };
+}
+
+define_vectorized_runner!(
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ))]
+    run_sse41,
+    "sse4.1"
+);
+define_vectorized_runner!(
+    #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+    run_avx2,
+    "avx2"
+);
+define_vectorized_runner!(
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    run_wasm,
+    "simd128"
+);
+
+/// Runs `f` with the widest vector extension available, so row converters inlined into it
+/// are vectorized the same way as in the fused block executors.
+pub(crate) fn run_vectorized(f: impl FnOnce()) {
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    unsafe {
+        run_wasm(f)
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")))]
+    {
+        #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+        if std::arch::is_x86_feature_detected!("avx2") {
+            unsafe { run_avx2(f) };
+            return;
+        }
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            feature = "unsafe",
+            feature = "sse"
+        ))]
+        if std::arch::is_x86_feature_detected!("sse4.1") {
+            unsafe { run_sse41(f) };
+            return;
+        }
+        f()
+    }
+}
+
+/// Pixel layout that transposes with its own block kernels while converting.
+pub(crate) trait FusedTranspose<V> {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[V],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[V], &mut [O]),
+    );
+}
+
+/// `N` channel layout of any sample type going through [TransposeBlockScalar].
+pub(crate) struct ScalarFused<const N: usize> {}
+
+impl<V: Copy + Default, const N: usize> FusedTranspose<V> for ScalarFused<N> {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[V],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[V], &mut [O]),
+    ) {
+        transpose_fused::<V, O, 8, N, M, FLOP, FLIP>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            TransposeBlockScalar::<8, N, FLOP, FLIP> {},
+            converter,
+        )
+    }
+}
+
+/// Selects [FusedTranspose::transpose] instance for flip and flop modes.
+pub(crate) fn transpose_fused_modes<V, F: FusedTranspose<V>, O, const M: usize>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [O],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+    converter: &impl Fn(&[V], &mut [O]),
+) {
+    match flip_mode {
+        FlipMode::NoFlip => match flop_mode {
+            FlopMode::NoFlop => F::transpose::<O, M, false, false>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            ),
+            FlopMode::Flop => F::transpose::<O, M, true, false>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            ),
+        },
+        FlipMode::Flip => match flop_mode {
+            FlopMode::NoFlop => F::transpose::<O, M, false, true>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            ),
+            FlopMode::Flop => F::transpose::<O, M, true, true>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            ),
+        },
+    }
+}
+
+/// Sample type whose pixel layouts pick their own fused block kernels.
+pub(crate) trait FusedSample: Copy + Default {
+    fn transpose_fused<O, const N: usize, const M: usize>(
+        input: &[Self],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        flip_mode: FlipMode,
+        flop_mode: FlopMode,
+        converter: &impl Fn(&[Self], &mut [O]),
+    );
+}
+
+macro_rules! define_fused_sample {
+    ($sample: ty, $plane: ty, $two: ty, $three: ty, $four: ty) => {
+        impl FusedSample for $sample {
+            fn transpose_fused<O, const N: usize, const M: usize>(
+                input: &[Self],
+                input_stride: usize,
+                output: &mut [O],
+                output_stride: usize,
+                width: usize,
+                height: usize,
+                flip_mode: FlipMode,
+                flop_mode: FlopMode,
+                converter: &impl Fn(&[Self], &mut [O]),
+            ) {
+                match N {
+                    1 => transpose_fused_modes::<$sample, $plane, O, M>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                        converter,
+                    ),
+                    2 => transpose_fused_modes::<$sample, $two, O, M>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                        converter,
+                    ),
+                    3 => transpose_fused_modes::<$sample, $three, O, M>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                        converter,
+                    ),
+                    4 => transpose_fused_modes::<$sample, $four, O, M>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                        converter,
+                    ),
+                    _ => transpose_fused_modes::<$sample, ScalarFused<N>, O, M>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                        converter,
+                    ),
+                }
+            }
+        }
+    };
+}
+
+define_fused_sample!(u8, Plane8Fused, CbCr8Fused, Rgb8Fused, Rgba8Fused);
+define_fused_sample!(u16, Plane16Fused, CbCr16Fused, Rgb16Fused, Rgba16Fused);
+define_fused_sample!(f32, PlaneF32Fused, CbCrF32Fused, RgbF32Fused, RgbaF32Fused);
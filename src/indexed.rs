@@ -0,0 +1,446 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::bitmap::{mirror_packed, store_row};
+use crate::utils::is_valid_image_len;
+use crate::{transpose_plane, BitOrder, FlipMode, FlopMode, TransposeError};
+use roxygen::roxygen;
+
+/// Pixels in a tile edge, tiles are unpacked to a byte per pixel for the plane kernels
+const TILE: usize = 256;
+
+/// Shift of the pixel at `index` within a byte
+#[inline(always)]
+const fn pixel_shift<const LSB: bool, const BITS: usize>(index: usize) -> usize {
+    if LSB {
+        index * BITS
+    } else {
+        8 - BITS - index * BITS
+    }
+}
+
+/// Unpacks `dst.len()` pixels of `BITS` bits starting at the first pixel of `src`
+#[inline(always)]
+fn unpack<const LSB: bool, const BITS: usize>(src: &[u8], dst: &mut [u8]) {
+    let pixels_per_byte = 8 / BITS;
+    let mask = (1u8 << BITS) - 1;
+    let (full, tail) = dst.split_at_mut(dst.len() / pixels_per_byte * pixels_per_byte);
+    for (dst, &byte) in full.chunks_exact_mut(pixels_per_byte).zip(src) {
+        for (index, dst) in dst.iter_mut().enumerate() {
+            *dst = (byte >> pixel_shift::<LSB, BITS>(index)) & mask;
+        }
+    }
+    if !tail.is_empty() {
+        let byte = src[full.len() / pixels_per_byte];
+        for (index, dst) in tail.iter_mut().enumerate() {
+            *dst = (byte >> pixel_shift::<LSB, BITS>(index)) & mask;
+        }
+    }
+}
+
+/// Packs pixels of `BITS` bits, a trailing partial byte is padded with zeros
+#[inline(always)]
+fn pack<const LSB: bool, const BITS: usize>(src: &[u8], dst: &mut [u8]) {
+    let pixels_per_byte = 8 / BITS;
+    let pack_byte = |pixels: &[u8]| -> u8 {
+        pixels
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (index, &pixel)| {
+                byte | (pixel << pixel_shift::<LSB, BITS>(index))
+            })
+    };
+    let chunks = src.chunks_exact(pixels_per_byte);
+    let tail = chunks.remainder();
+    let full = src.len() / pixels_per_byte;
+    for (dst, pixels) in dst.iter_mut().zip(chunks) {
+        *dst = pack_byte(pixels);
+    }
+    if !tail.is_empty() {
+        dst[full] = pack_byte(tail);
+    }
+}
+
+fn transpose_packed<const LSB: bool, const BITS: usize>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    let pixels_per_byte = 8 / BITS;
+    let input_bytes = width.div_ceil(pixels_per_byte);
+    let output_bytes = height.div_ceil(pixels_per_byte);
+    if !is_valid_image_len(input.len(), input_stride, input_bytes, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, output_bytes, width) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < input_bytes || output_stride < output_bytes {
+        return Err(TransposeError::MismatchDimensions);
+    }
+
+    let mut unpacked = vec![0u8; TILE * TILE];
+    let mut transposed = vec![0u8; TILE * TILE];
+    let mut packed = vec![0u8; TILE / pixels_per_byte];
+
+    // Tiles start at byte aligned source columns and byte aligned destination columns
+    for tile_x in (0..width).step_by(TILE) {
+        let tile_width = (width - tile_x).min(TILE);
+        let dst_row = match flop_mode {
+            FlopMode::Flop => tile_x,
+            FlopMode::NoFlop => width - tile_x - tile_width,
+        };
+        for dst_column in (0..height).step_by(TILE) {
+            let tile_height = (height - dst_column).min(TILE);
+            let tile_y = match flip_mode {
+                FlipMode::Flip => height - dst_column - tile_height,
+                FlipMode::NoFlip => dst_column,
+            };
+
+            let tile_len = tile_width * tile_height;
+            for (y, dst) in unpacked[..tile_len]
+                .chunks_exact_mut(tile_width)
+                .enumerate()
+            {
+                let offset = (tile_y + y) * input_stride + tile_x / pixels_per_byte;
+                unpack::<LSB, BITS>(&input[offset..], dst);
+            }
+            transpose_plane(
+                &unpacked[..tile_len],
+                tile_width,
+                &mut transposed[..tile_len],
+                tile_height,
+                tile_width,
+                tile_height,
+                flip_mode,
+                flop_mode,
+            )?;
+            for (row, src) in transposed[..tile_len].chunks_exact(tile_height).enumerate() {
+                pack::<LSB, BITS>(src, &mut packed);
+                let offset = (dst_row + row) * output_stride + dst_column / pixels_per_byte;
+                store_row::<LSB, BITS>(&mut output[offset..], &packed, tile_height);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transpose_indexed<const BITS: usize>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    bit_order: BitOrder,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    let executor = match bit_order {
+        BitOrder::MsbFirst => transpose_packed::<false, BITS>,
+        BitOrder::LsbFirst => transpose_packed::<true, BITS>,
+    };
+    executor(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Transposes an image of 2 bits per pixel with packed rows, e.g. a palettized PNG
+///
+/// Rows hold `width.div_ceil(4)` bytes. Bits past the image width in the last byte of
+/// destination rows are set to zero.
+#[roxygen]
+pub fn transpose_indexed2(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image, `height` pixels wide and `width` pixels tall.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte, shared by source and destination.
+    bit_order: BitOrder,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_indexed::<2>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of 2 bits per pixel with packed rows, pixels of every row are reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flip_indexed2(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<2, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Flops an image of 2 bits per pixel with packed rows, order of rows is reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flop_indexed2(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<2, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Rotates an image of 2 bits per pixel with packed rows by 180 degrees
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn rotate180_indexed2(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<2, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Transposes an image of 4 bits per pixel with packed rows, e.g. a palettized PNG
+///
+/// Rows hold `width.div_ceil(2)` bytes. Bits past the image width in the last byte of
+/// destination rows are set to zero.
+#[roxygen]
+pub fn transpose_indexed4(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image, `height` pixels wide and `width` pixels tall.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte, shared by source and destination.
+    bit_order: BitOrder,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_indexed::<4>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of 4 bits per pixel with packed rows, pixels of every row are reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flip_indexed4(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<4, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Flops an image of 4 bits per pixel with packed rows, order of rows is reversed
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn flop_indexed4(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<4, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
+
+/// Rotates an image of 4 bits per pixel with packed rows by 180 degrees
+///
+/// Bits past the image width in the last byte of destination rows are set to zero.
+#[roxygen]
+pub fn rotate180_indexed4(
+    /// Source image.
+    input: &[u8],
+    /// Number of bytes per row in `input`.
+    input_stride: usize,
+    /// Destination image.
+    output: &mut [u8],
+    /// Number of bytes per row in `output`.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+    /// Bits holding the leftmost pixel of each byte.
+    bit_order: BitOrder,
+) -> Result<(), TransposeError> {
+    mirror_packed::<4, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        bit_order,
+    )
+}
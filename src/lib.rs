@@ -67,13 +67,6 @@
 #![allow(clippy::too_many_arguments)]
 #![cfg_attr(not(feature = "unsafe"), forbid(unsafe_code))]
 #![allow(stable_features)]
-#![cfg_attr(
-    all(
-        feature = "nightly_avx512",
-        any(target_arch = "x86", target_arch = "x86_64")
-    ),
-    feature(cfg_version)
-)]
 #![cfg_attr(
     all(
         feature = "nightly_avx512",
@@ -93,55 +86,150 @@ extern crate core;
 
 #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
 mod avx;
-#[cfg(all(target_arch = "x86_64", feature = "nightly_avx512"))]
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
 mod avx512;
+mod bayer;
+mod bcn;
+mod bitmap;
 mod cbcr8;
+mod convert;
 mod flip;
 mod float32_cbcr_invoker;
 mod float32_invoker;
+mod float32_rgb_invoker;
+mod float32_rgba_invoker;
 mod float_32;
 mod flop;
+mod fused;
+mod indexed;
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 mod neon;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ),
+    all(target_arch = "aarch64", feature = "unsafe", feature = "neon")
+))]
+mod non_temporal;
+mod packed16;
+mod packed_yuv;
 mod plane16;
 mod plane8;
+mod premultiply;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ),
+    all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+))]
+mod reverse_masks;
+mod rgb10a2;
+mod rgb16;
+mod rgb8;
 mod rgba16;
 mod rgba8;
 mod rotate180;
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+mod rvv;
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
     feature = "unsafe",
     feature = "sse"
 ))]
 mod sse;
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "sve"))]
+mod sve;
+mod swizzle;
+mod tiled;
 mod transpose_arbitrary;
 mod transpose_arbitrary_group;
 mod unsigned_16;
 mod unsigned_8;
 mod utils;
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+mod wasm32;
+mod yuv;
 
+pub use bayer::{
+    flip_bayer16, flip_bayer8, flop_bayer16, flop_bayer8, rotate_bayer16, rotate_bayer8,
+};
+pub use bcn::{
+    flip_bc1, flip_bc3, flip_bc4, flip_bc5, flop_bc1, flop_bc3, flop_bc4, flop_bc5, rotate_bc1,
+    rotate_bc3, rotate_bc4, rotate_bc5,
+};
+pub use bitmap::{flip_bitmap, flop_bitmap, rotate180_bitmap, transpose_bitmap};
+pub use convert::{
+    transpose_f32_to_u8, transpose_u16_to_f32, transpose_u16_to_u8, transpose_u8_to_f32,
+    transpose_u8_to_u16,
+};
 pub use flip::{
     flip_arbitrary, flip_plane, flip_plane16, flip_plane16_with_alpha, flip_plane_f32,
     flip_plane_f32_with_alpha, flip_plane_with_alpha, flip_rgb, flip_rgb16, flip_rgb_f32,
     flip_rgba, flip_rgba16, flip_rgba_f32,
 };
 pub use float_32::{
-    transpose_plane_f32, transpose_plane_f32_with_alpha, transpose_rgb_f32, transpose_rgba_f32,
+    transpose_plane_f32, transpose_plane_f32_hinted, transpose_plane_f32_with_alpha,
+    transpose_rgb_f32, transpose_rgba_f32,
 };
 pub use flop::{
     flop_arbitrary, flop_plane, flop_plane16, flop_plane16_with_alpha, flop_plane_f32,
     flop_plane_f32_with_alpha, flop_plane_with_alpha, flop_rgb, flop_rgb16, flop_rgb_f32,
     flop_rgba, flop_rgba16, flop_rgba_f32,
 };
+pub use indexed::{
+    flip_indexed2, flip_indexed4, flop_indexed2, flop_indexed4, rotate180_indexed2,
+    rotate180_indexed4, transpose_indexed2, transpose_indexed4,
+};
+pub use packed16::{
+    flip_rgb565, flip_rgba4444, flip_rgba5551, flop_rgb565, flop_rgba4444, flop_rgba5551,
+    rotate180_rgb565, rotate180_rgba4444, rotate180_rgba5551, rotate_rgb565_to_rgba8,
+    rotate_rgba4444_to_rgba8, rotate_rgba5551_to_rgba8, transpose_rgb565, transpose_rgba4444,
+    transpose_rgba5551,
+};
+pub use packed_yuv::{
+    rotate_uyvy, rotate_uyvy_to_i420, rotate_uyvy_to_planar, rotate_v210, rotate_yuy2,
+    rotate_yuy2_to_i420, rotate_yuy2_to_planar,
+};
+pub use premultiply::{
+    flip_rgba16_alpha, flip_rgba_alpha, flip_rgba_f32_alpha, flop_rgba16_alpha, flop_rgba_alpha,
+    flop_rgba_f32_alpha, rotate180_rgba16_alpha, rotate180_rgba_alpha, rotate180_rgba_f32_alpha,
+    transpose_rgba16_alpha, transpose_rgba_alpha, transpose_rgba_f32_alpha,
+};
+pub use rgb10a2::{flip_rgb10a2, flop_rgb10a2, rotate180_rgb10a2, transpose_rgb10a2};
 pub use rotate180::{
     rotate180_arbitrary, rotate180_plane, rotate180_plane16, rotate180_plane16_with_alpha,
     rotate180_plane_f32, rotate180_plane_f32_with_alpha, rotate180_plane_with_alpha, rotate180_rgb,
     rotate180_rgb16, rotate180_rgb_f32, rotate180_rgba, rotate180_rgba16, rotate180_rgba_f32,
 };
+pub use swizzle::{
+    flip_rgba16_swizzled, flip_rgba_f32_swizzled, flip_rgba_swizzled, flop_rgba16_swizzled,
+    flop_rgba_f32_swizzled, flop_rgba_swizzled, rotate180_rgba16_swizzled,
+    rotate180_rgba_f32_swizzled, rotate180_rgba_swizzled, transpose_rgba16_swizzled,
+    transpose_rgba_f32_swizzled, transpose_rgba_swizzled,
+};
+pub use tiled::{
+    rotate_from_tiled, rotate_from_tiled16, rotate_from_tiled_f32, rotate_to_tiled,
+    rotate_to_tiled16, rotate_to_tiled_f32,
+};
 pub use transpose_arbitrary::transpose_arbitrary;
 pub use transpose_arbitrary_group::transpose_arbitrary_grouped;
 pub use unsigned_16::{
-    transpose_plane16, transpose_plane16_with_alpha, transpose_rgb16, transpose_rgba16,
+    transpose_plane16, transpose_plane16_hinted, transpose_plane16_with_alpha, transpose_rgb16,
+    transpose_rgba16,
+};
+pub use unsigned_8::{
+    transpose_plane, transpose_plane_hinted, transpose_plane_with_alpha, transpose_rgb,
+    transpose_rgba, transpose_rgba_hinted,
+};
+pub use utils::{
+    AlphaMode, BitOrder, CfaPattern, ChannelOrder, FlipMode, FlopMode, Orientation, Rect,
+    StoreHint, TileLayout, TransposeError,
+};
+pub use yuv::{
+    rotate_i010, rotate_i012, rotate_i420, rotate_i422, rotate_i444, rotate_nv12, rotate_p010,
+    rotate_p016,
 };
-pub use unsigned_8::{transpose_plane, transpose_plane_with_alpha, transpose_rgb, transpose_rgba};
-pub use utils::{FlipMode, FlopMode, TransposeError};
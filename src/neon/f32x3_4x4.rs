@@ -0,0 +1,68 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::neon::x4_f32::neon_transpose_4x4_impl_f32;
+use std::arch::aarch64::*;
+
+#[inline]
+pub(crate) fn neon_transpose_f32x3_4x4<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = vld3q_f32(src.get_unchecked(0..).as_ptr());
+        let row1 = vld3q_f32(src.get_unchecked(src_stride..).as_ptr());
+        let row2 = vld3q_f32(src.get_unchecked(2 * src_stride..).as_ptr());
+        let row3 = vld3q_f32(src.get_unchecked(3 * src_stride..).as_ptr());
+
+        // Channels are deinterleaved, so every one of them is transposed as a plane
+        let r = neon_transpose_4x4_impl_f32::<FLIP>(float32x4x4_t(row0.0, row1.0, row2.0, row3.0));
+        let g = neon_transpose_4x4_impl_f32::<FLIP>(float32x4x4_t(row0.1, row1.1, row2.1, row3.1));
+        let b = neon_transpose_4x4_impl_f32::<FLIP>(float32x4x4_t(row0.2, row1.2, row2.2, row3.2));
+
+        let v0 = float32x4x3_t(r.0, g.0, b.0);
+        let v1 = float32x4x3_t(r.1, g.1, b.1);
+        let v2 = float32x4x3_t(r.2, g.2, b.2);
+        let v3 = float32x4x3_t(r.3, g.3, b.3);
+
+        if FLOP {
+            vst3q_f32(dst.get_unchecked_mut(0..).as_mut_ptr(), v0);
+            vst3q_f32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v1);
+            vst3q_f32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v2);
+            vst3q_f32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v3);
+        } else {
+            vst3q_f32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v0);
+            vst3q_f32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v1);
+            vst3q_f32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v2);
+            vst3q_f32(dst.get_unchecked_mut(0..).as_mut_ptr(), v3);
+        }
+    }
+}
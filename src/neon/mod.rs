@@ -28,14 +28,20 @@
  */
 mod f32x2_2x2;
 mod f32x2_4x4;
+mod f32x3_4x4;
+mod f32x4_4x4;
+mod premultiply;
+mod reverse;
 mod u16_4x4;
 mod u16_8x8;
+mod u16x3_8x8;
 mod u16x4_2x2;
 mod u16x4_4x4;
 mod u8_16x16;
 mod u8_8x8;
 mod u8x2_4x4;
 mod u8x2_8x8;
+mod u8x3_8x8;
 mod utils;
 mod x4_f32;
 mod x4_u32;
@@ -44,15 +50,21 @@ mod x8_u32;
 
 pub(crate) use f32x2_2x2::neon_transpose_f32x2_2x2;
 pub(crate) use f32x2_4x4::neon_transpose_f32x2_4x4;
+pub(crate) use f32x3_4x4::neon_transpose_f32x3_4x4;
+pub(crate) use f32x4_4x4::{neon_transpose_f32x4_4x4, neon_transpose_f32x4_4x4_swizzled};
+pub(crate) use premultiply::{neon_premultiply_u8, neon_unpremultiply_u8};
+pub(crate) use reverse::neon_reverse_pixels;
 pub(crate) use u16_4x4::neon_transpose_4x4_u16;
 pub(crate) use u16_8x8::neon_transpose_8x8_u16;
-pub(crate) use u16x4_2x2::neon_transpose_u16x4_2x2;
+pub(crate) use u16x3_8x8::neon_transpose_u16x3_8x8;
+pub(crate) use u16x4_2x2::{neon_transpose_u16x4_2x2, neon_transpose_u16x4_2x2_swizzled};
 pub(crate) use u16x4_4x4::neon_transpose_u16x4_4x4;
 pub(crate) use u8_16x16::neon_transpose_u8_16x16;
 pub(crate) use u8_8x8::neon_transpose_u8_8x8;
 pub(crate) use u8x2_4x4::neon_transpose_u8x2_4x4;
 pub(crate) use u8x2_8x8::neon_transpose_u8x2_8x8;
+pub(crate) use u8x3_8x8::neon_transpose_u8x3_8x8;
 pub(crate) use x4_f32::neon_transpose_4x4_f32;
-pub(crate) use x4_u32::neon_transpose_4x4_u8x4;
+pub(crate) use x4_u32::{neon_transpose_4x4_u8x4, neon_transpose_4x4_u8x4_swizzled};
 pub(crate) use x8_f32::neon_transpose_8x8_f32;
 pub(crate) use x8_u32::neon_transpose_4x4_u8x4x8;
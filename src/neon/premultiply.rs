@@ -0,0 +1,152 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_reverse_u8(v: uint8x16_t) -> uint8x16_t {
+    let v = vrev64q_u8(v);
+    vextq_u8::<8>(v, v)
+}
+
+/// Rounded division by 255 of products from `vmull_u8` narrowed back to 8 bits.
+#[inline(always)]
+unsafe fn neon_div_255(t: uint16x8_t) -> uint8x8_t {
+    vraddhn_u16(t, vrshrq_n_u16::<8>(t))
+}
+
+/// Premultiplies color of RGBA8 pixels in runs of 16 on deinterleaved channels.
+/// Returns how many pixels were written, pixels land in reverse order when `REVERSE` is set.
+#[inline]
+pub(crate) fn neon_premultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 16;
+    unsafe {
+        for i in 0..chunks {
+            let v = vld4q_u8(src.get_unchecked(i * 64..).as_ptr());
+            let a = v.3;
+            let scale = |c: uint8x16_t| {
+                vcombine_u8(
+                    neon_div_255(vmull_u8(vget_low_u8(c), vget_low_u8(a))),
+                    neon_div_255(vmull_high_u8(c, a)),
+                )
+            };
+            let mut v = uint8x16x4_t(scale(v.0), scale(v.1), scale(v.2), a);
+            let dst_px = if REVERSE {
+                v = uint8x16x4_t(
+                    neon_reverse_u8(v.0),
+                    neon_reverse_u8(v.1),
+                    neon_reverse_u8(v.2),
+                    neon_reverse_u8(v.3),
+                );
+                pixels - 16 * (i + 1)
+            } else {
+                16 * i
+            };
+            vst4q_u8(dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr(), v);
+        }
+    }
+    chunks * 16
+}
+
+/// Divides 4 colors by their alphas, rounded and saturated.
+///
+/// Quotient of `c * 255 + a / 2` stays below 2^16, so the refined float reciprocal lands
+/// within one of it and the remainder corrects that.
+#[inline(always)]
+unsafe fn neon_unpremultiply_x4(c: uint32x4_t, a: uint32x4_t, recip: float32x4_t) -> uint32x4_t {
+    let n = vmlaq_n_u32(vshrq_n_u32::<1>(a), c, 255);
+    let q = vcvtq_u32_f32(vmulq_f32(vcvtq_f32_u32(n), recip));
+    let r = vreinterpretq_s32_u32(vsubq_u32(n, vmulq_u32(q, a)));
+    // Masks are all ones where set, so adding one steps down and subtracting one steps up.
+    let q = vaddq_u32(q, vcltzq_s32(r));
+    let q = vsubq_u32(q, vcgeq_s32(r, vreinterpretq_s32_u32(a)));
+    let q = vminq_u32(q, vdupq_n_u32(255));
+    vandq_u32(q, vtstq_u32(a, a))
+}
+
+#[inline(always)]
+unsafe fn neon_recip(a: uint32x4_t) -> float32x4_t {
+    let af = vcvtq_f32_u32(a);
+    let recip = vrecpeq_f32(af);
+    let recip = vmulq_f32(recip, vrecpsq_f32(af, recip));
+    vmulq_f32(recip, vrecpsq_f32(af, recip))
+}
+
+/// Unpremultiplies color of RGBA8 pixels in runs of 16 on deinterleaved channels, see
+/// [neon_unpremultiply_x4]. Returns how many pixels were written, pixels land in reverse
+/// order when `REVERSE` is set.
+#[inline]
+pub(crate) fn neon_unpremultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 16;
+    unsafe {
+        for i in 0..chunks {
+            let v = vld4q_u8(src.get_unchecked(i * 64..).as_ptr());
+            let a16 = (vmovl_u8(vget_low_u8(v.3)), vmovl_high_u8(v.3));
+            let a = [
+                vmovl_u16(vget_low_u16(a16.0)),
+                vmovl_high_u16(a16.0),
+                vmovl_u16(vget_low_u16(a16.1)),
+                vmovl_high_u16(a16.1),
+            ];
+            let recip = [
+                neon_recip(a[0]),
+                neon_recip(a[1]),
+                neon_recip(a[2]),
+                neon_recip(a[3]),
+            ];
+            let divide = |c: uint8x16_t| {
+                let c16 = (vmovl_u8(vget_low_u8(c)), vmovl_high_u8(c));
+                let q0 = neon_unpremultiply_x4(vmovl_u16(vget_low_u16(c16.0)), a[0], recip[0]);
+                let q1 = neon_unpremultiply_x4(vmovl_high_u16(c16.0), a[1], recip[1]);
+                let q2 = neon_unpremultiply_x4(vmovl_u16(vget_low_u16(c16.1)), a[2], recip[2]);
+                let q3 = neon_unpremultiply_x4(vmovl_high_u16(c16.1), a[3], recip[3]);
+                vcombine_u8(
+                    vmovn_u16(vcombine_u16(vmovn_u32(q0), vmovn_u32(q1))),
+                    vmovn_u16(vcombine_u16(vmovn_u32(q2), vmovn_u32(q3))),
+                )
+            };
+            let mut v = uint8x16x4_t(divide(v.0), divide(v.1), divide(v.2), v.3);
+            let dst_px = if REVERSE {
+                v = uint8x16x4_t(
+                    neon_reverse_u8(v.0),
+                    neon_reverse_u8(v.1),
+                    neon_reverse_u8(v.2),
+                    neon_reverse_u8(v.3),
+                );
+                pixels - 16 * (i + 1)
+            } else {
+                16 * i
+            };
+            vst4q_u8(dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr(), v);
+        }
+    }
+    chunks * 16
+}
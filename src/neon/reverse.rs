@@ -0,0 +1,134 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::neon::utils::{vrev128_u16, vrev128_u32, vrev128_u8, vrev128q_u64};
+use std::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn neon_reverse_reg<const PX: usize>(v: uint8x16_t) -> uint8x16_t {
+    match PX {
+        1 => vrev128_u8(v),
+        2 => vreinterpretq_u8_u16(vrev128_u16(vreinterpretq_u16_u8(v))),
+        4 => vreinterpretq_u8_u32(vrev128_u32(vreinterpretq_u32_u8(v))),
+        8 => vreinterpretq_u8_u64(vrev128q_u64(vreinterpretq_u64_u8(v))),
+        _ => v,
+    }
+}
+
+/// Reverses order of `PX` bytes wide pixels from `src` into `dst`.
+///
+/// Source is read forward and destination is written backward,
+/// `src` and `dst` are expected to have the same length.
+#[inline(always)]
+pub(crate) fn neon_reverse_pixels<const PX: usize>(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len(), dst.len());
+    let width = src.len() / PX;
+    let mut x = 0usize;
+
+    unsafe {
+        match PX {
+            1 | 2 | 4 | 8 | 16 => {
+                let pixels_per_reg = 16 / PX;
+
+                while x + 4 * pixels_per_reg <= width {
+                    let rows = vld1q_u8_x4(src.get_unchecked(x * PX..).as_ptr());
+                    vst1q_u8_x4(
+                        dst.get_unchecked_mut((width - x - 4 * pixels_per_reg) * PX..)
+                            .as_mut_ptr(),
+                        uint8x16x4_t(
+                            neon_reverse_reg::<PX>(rows.3),
+                            neon_reverse_reg::<PX>(rows.2),
+                            neon_reverse_reg::<PX>(rows.1),
+                            neon_reverse_reg::<PX>(rows.0),
+                        ),
+                    );
+                    x += 4 * pixels_per_reg;
+                }
+
+                while x + pixels_per_reg <= width {
+                    let row0 = vld1q_u8(src.get_unchecked(x * PX..).as_ptr());
+                    vst1q_u8(
+                        dst.get_unchecked_mut((width - x - pixels_per_reg) * PX..)
+                            .as_mut_ptr(),
+                        neon_reverse_reg::<PX>(row0),
+                    );
+                    x += pixels_per_reg;
+                }
+            }
+            3 => {
+                while x + 16 <= width {
+                    let rows = vld3q_u8(src.get_unchecked(x * PX..).as_ptr());
+                    vst3q_u8(
+                        dst.get_unchecked_mut((width - x - 16) * PX..).as_mut_ptr(),
+                        uint8x16x3_t(vrev128_u8(rows.0), vrev128_u8(rows.1), vrev128_u8(rows.2)),
+                    );
+                    x += 16;
+                }
+            }
+            6 => {
+                while x + 8 <= width {
+                    let rows = vld3q_u16(src.get_unchecked(x * PX..).as_ptr() as *const u16);
+                    vst3q_u16(
+                        dst.get_unchecked_mut((width - x - 8) * PX..).as_mut_ptr() as *mut u16,
+                        uint16x8x3_t(
+                            vrev128_u16(rows.0),
+                            vrev128_u16(rows.1),
+                            vrev128_u16(rows.2),
+                        ),
+                    );
+                    x += 8;
+                }
+            }
+            12 => {
+                while x + 4 <= width {
+                    let rows = vld3q_u32(src.get_unchecked(x * PX..).as_ptr() as *const u32);
+                    vst3q_u32(
+                        dst.get_unchecked_mut((width - x - 4) * PX..).as_mut_ptr() as *mut u32,
+                        uint32x4x3_t(
+                            vrev128_u32(rows.0),
+                            vrev128_u32(rows.1),
+                            vrev128_u32(rows.2),
+                        ),
+                    );
+                    x += 4;
+                }
+            }
+            _ => {}
+        }
+
+        while x < width {
+            std::ptr::copy_nonoverlapping(
+                src.get_unchecked(x * PX..).as_ptr(),
+                dst.get_unchecked_mut((width - x - 1) * PX..).as_mut_ptr(),
+                PX,
+            );
+            x += 1;
+        }
+    }
+}
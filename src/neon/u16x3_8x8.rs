@@ -0,0 +1,93 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::neon::u16_8x8::neon_transpose_u16_4x4_impl;
+use std::arch::aarch64::*;
+
+#[inline]
+pub(crate) fn neon_transpose_u16x3_8x8<const FLOP: bool, const FLIP: bool>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = vld3q_u16(src.get_unchecked(0..).as_ptr());
+        let row1 = vld3q_u16(src.get_unchecked(src_stride..).as_ptr());
+        let row2 = vld3q_u16(src.get_unchecked(2 * src_stride..).as_ptr());
+        let row3 = vld3q_u16(src.get_unchecked(3 * src_stride..).as_ptr());
+        let row4 = vld3q_u16(src.get_unchecked(4 * src_stride..).as_ptr());
+        let row5 = vld3q_u16(src.get_unchecked(5 * src_stride..).as_ptr());
+        let row6 = vld3q_u16(src.get_unchecked(6 * src_stride..).as_ptr());
+        let row7 = vld3q_u16(src.get_unchecked(7 * src_stride..).as_ptr());
+
+        // Channels are deinterleaved, so every one of them is transposed as a plane
+        let (r0, r1) = neon_transpose_u16_4x4_impl::<FLIP>(
+            uint16x8x4_t(row0.0, row1.0, row2.0, row3.0),
+            uint16x8x4_t(row4.0, row5.0, row6.0, row7.0),
+        );
+        let (g0, g1) = neon_transpose_u16_4x4_impl::<FLIP>(
+            uint16x8x4_t(row0.1, row1.1, row2.1, row3.1),
+            uint16x8x4_t(row4.1, row5.1, row6.1, row7.1),
+        );
+        let (b0, b1) = neon_transpose_u16_4x4_impl::<FLIP>(
+            uint16x8x4_t(row0.2, row1.2, row2.2, row3.2),
+            uint16x8x4_t(row4.2, row5.2, row6.2, row7.2),
+        );
+
+        let v0 = uint16x8x3_t(r0.0, g0.0, b0.0);
+        let v1 = uint16x8x3_t(r0.1, g0.1, b0.1);
+        let v2 = uint16x8x3_t(r0.2, g0.2, b0.2);
+        let v3 = uint16x8x3_t(r0.3, g0.3, b0.3);
+        let v4 = uint16x8x3_t(r1.0, g1.0, b1.0);
+        let v5 = uint16x8x3_t(r1.1, g1.1, b1.1);
+        let v6 = uint16x8x3_t(r1.2, g1.2, b1.2);
+        let v7 = uint16x8x3_t(r1.3, g1.3, b1.3);
+
+        if FLOP {
+            vst3q_u16(dst.get_unchecked_mut(0..).as_mut_ptr(), v0);
+            vst3q_u16(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v1);
+            vst3q_u16(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v2);
+            vst3q_u16(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v3);
+            vst3q_u16(dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr(), v4);
+            vst3q_u16(dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr(), v5);
+            vst3q_u16(dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr(), v6);
+            vst3q_u16(dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr(), v7);
+        } else {
+            vst3q_u16(dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr(), v0);
+            vst3q_u16(dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr(), v1);
+            vst3q_u16(dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr(), v2);
+            vst3q_u16(dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr(), v3);
+            vst3q_u16(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v4);
+            vst3q_u16(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v5);
+            vst3q_u16(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v6);
+            vst3q_u16(dst.get_unchecked_mut(0..).as_mut_ptr(), v7);
+        }
+    }
+}
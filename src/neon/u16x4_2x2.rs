@@ -28,6 +28,7 @@
  */
 
 use crate::neon::utils::{vrev128q_u64, xvld1q_u16_u64, xvst1q_u16_u64};
+use crate::utils::swizzle_mask_samples;
 use std::arch::aarch64::*;
 
 #[inline(always)]
@@ -44,6 +45,21 @@ pub(crate) unsafe fn neon_transpose_u64_2x2_impl<const FLIP: bool>(
     }
 }
 
+#[inline(always)]
+unsafe fn neon_store_u16x4_2x2<const FLOP: bool>(
+    dst: &mut [u16],
+    dst_stride: usize,
+    v0: uint64x2x2_t,
+) {
+    if FLOP {
+        xvst1q_u16_u64(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.0);
+        xvst1q_u16_u64(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.1);
+    } else {
+        xvst1q_u16_u64(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.0);
+        xvst1q_u16_u64(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.1);
+    }
+}
+
 #[inline]
 pub(crate) fn neon_transpose_u16x4_2x2<const FLOP: bool, const FLIP: bool>(
     src: &[u16],
@@ -56,13 +72,32 @@ pub(crate) fn neon_transpose_u16x4_2x2<const FLOP: bool, const FLIP: bool>(
         let row1 = xvld1q_u16_u64(src.get_unchecked(src_stride..).as_ptr());
 
         let v0 = neon_transpose_u64_2x2_impl::<FLIP>(uint64x2x2_t(row0, row1));
+        neon_store_u16x4_2x2::<FLOP>(dst, dst_stride, v0);
+    }
+}
 
-        if FLOP {
-            xvst1q_u16_u64(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.0);
-            xvst1q_u16_u64(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.1);
-        } else {
-            xvst1q_u16_u64(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.0);
-            xvst1q_u16_u64(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.1);
-        }
+/// Same as [neon_transpose_u16x4_2x2] but also reorders channels of every 4 channel 16-bit
+/// pixel by `KEY` from [crate::ChannelOrder] with one table lookup per row.
+#[inline]
+pub(crate) fn neon_transpose_u16x4_2x2_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = xvld1q_u16_u64(src.get_unchecked(0..).as_ptr());
+        let row1 = xvld1q_u16_u64(src.get_unchecked(src_stride..).as_ptr());
+
+        let v0 = neon_transpose_u64_2x2_impl::<FLIP>(uint64x2x2_t(row0, row1));
+        let mask = const { swizzle_mask_samples(KEY, 2) };
+        let mask = vld1q_u8(mask.as_ptr());
+        let shuffle =
+            |v: uint64x2_t| vreinterpretq_u64_u8(vqtbl1q_u8(vreinterpretq_u8_u64(v), mask));
+        neon_store_u16x4_2x2::<FLOP>(dst, dst_stride, uint64x2x2_t(shuffle(v0.0), shuffle(v0.1)));
     }
 }
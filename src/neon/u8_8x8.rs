@@ -30,7 +30,7 @@
 use std::arch::aarch64::*;
 
 #[inline(always)]
-unsafe fn neon_transpose_8x8_impl<const FLIP: bool>(
+pub(crate) unsafe fn neon_transpose_8x8_impl<const FLIP: bool>(
     v0: uint8x8x4_t,
     v1: uint8x8x4_t,
 ) -> (uint8x8x4_t, uint8x8x4_t) {
@@ -28,6 +28,7 @@
  */
 
 use crate::neon::utils::{vrev128_u32, vtrnq_s64_to_u32, xvld1q_u8_u32, xvst1q_u8_u32};
+use crate::utils::swizzle_mask_u8x4;
 use std::arch::aarch64::*;
 
 #[inline(always)]
@@ -67,6 +68,35 @@ pub(crate) unsafe fn neon_transpose_4x4_impl<const FLIP: bool>(v0: uint32x4x4_t)
     }
 }
 
+#[inline(always)]
+unsafe fn neon_load_4x4_u8x4(src: &[u8], src_stride: usize) -> uint32x4x4_t {
+    uint32x4x4_t(
+        xvld1q_u8_u32(src.get_unchecked(0..).as_ptr()),
+        xvld1q_u8_u32(src.get_unchecked(src_stride..).as_ptr()),
+        xvld1q_u8_u32(src.get_unchecked(2 * src_stride..).as_ptr()),
+        xvld1q_u8_u32(src.get_unchecked(3 * src_stride..).as_ptr()),
+    )
+}
+
+#[inline(always)]
+unsafe fn neon_store_4x4_u8x4<const FLOP: bool>(
+    dst: &mut [u8],
+    dst_stride: usize,
+    v0: uint32x4x4_t,
+) {
+    if FLOP {
+        xvst1q_u8_u32(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.0);
+        xvst1q_u8_u32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.1);
+        xvst1q_u8_u32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v0.2);
+        xvst1q_u8_u32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v0.3);
+    } else {
+        xvst1q_u8_u32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v0.0);
+        xvst1q_u8_u32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v0.1);
+        xvst1q_u8_u32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.2);
+        xvst1q_u8_u32(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.3);
+    }
+}
+
 #[inline]
 pub(crate) fn neon_transpose_4x4_u8x4<const FLOP: bool, const FLIP: bool>(
     src: &[u8],
@@ -75,23 +105,34 @@ pub(crate) fn neon_transpose_4x4_u8x4<const FLOP: bool, const FLIP: bool>(
     dst_stride: usize,
 ) {
     unsafe {
-        let row0 = xvld1q_u8_u32(src.get_unchecked(0..).as_ptr());
-        let row1 = xvld1q_u8_u32(src.get_unchecked(src_stride..).as_ptr());
-        let row2 = xvld1q_u8_u32(src.get_unchecked(2 * src_stride..).as_ptr());
-        let row3 = xvld1q_u8_u32(src.get_unchecked(3 * src_stride..).as_ptr());
-
-        let v0 = neon_transpose_4x4_impl::<FLIP>(uint32x4x4_t(row0, row1, row2, row3));
+        let v0 = neon_transpose_4x4_impl::<FLIP>(neon_load_4x4_u8x4(src, src_stride));
+        neon_store_4x4_u8x4::<FLOP>(dst, dst_stride, v0);
+    }
+}
 
-        if FLOP {
-            xvst1q_u8_u32(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.0);
-            xvst1q_u8_u32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.1);
-            xvst1q_u8_u32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v0.2);
-            xvst1q_u8_u32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v0.3);
-        } else {
-            xvst1q_u8_u32(dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr(), v0.0);
-            xvst1q_u8_u32(dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr(), v0.1);
-            xvst1q_u8_u32(dst.get_unchecked_mut(dst_stride..).as_mut_ptr(), v0.2);
-            xvst1q_u8_u32(dst.get_unchecked_mut(0..).as_mut_ptr(), v0.3);
-        }
+/// Same as [neon_transpose_4x4_u8x4] but also reorders channels of every pixel by `KEY`
+/// from [crate::ChannelOrder], the reorder shares the table lookup that flips pixels.
+#[inline]
+pub(crate) fn neon_transpose_4x4_u8x4_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let v0 = neon_transpose_4x4_impl::<false>(neon_load_4x4_u8x4(src, src_stride));
+        let mask = const { swizzle_mask_u8x4(KEY, FLIP) };
+        let mask = vld1q_u8(mask.as_ptr());
+        let shuffle =
+            |v: uint32x4_t| vreinterpretq_u32_u8(vqtbl1q_u8(vreinterpretq_u8_u32(v), mask));
+        neon_store_4x4_u8x4::<FLOP>(
+            dst,
+            dst_stride,
+            uint32x4x4_t(shuffle(v0.0), shuffle(v0.1), shuffle(v0.2), shuffle(v0.3)),
+        );
     }
 }
@@ -0,0 +1,183 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::rgba8::{tile_size, TransposeBlock};
+use crate::utils::use_non_temporal_stores;
+use crate::StoreHint;
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Bytes streamed per destination row, one full cache line so write combining
+/// buffers are flushed without partial writes.
+const STREAM_ROW_BYTES: usize = 64;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline(always)]
+unsafe fn stream_row(src: *const u8, dst: *mut u8, size: usize) {
+    // `movntdq` needs 16 byte aligned destination, unaligned head and tail go through the cache
+    let head = dst.align_offset(16).min(size);
+    std::ptr::copy_nonoverlapping(src, dst, head);
+    let mut i = head;
+    while i + 16 <= size {
+        let v = _mm_loadu_si128(src.add(i) as *const _);
+        _mm_stream_si128(dst.add(i) as *mut _, v);
+        i += 16;
+    }
+    std::ptr::copy_nonoverlapping(src.add(i), dst.add(i), size - i);
+}
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+unsafe fn stream_row(src: *const u8, dst: *mut u8, size: usize) {
+    let mut i = 0usize;
+    while i + 32 <= size {
+        let v0 = vld1q_u8(src.add(i));
+        let v1 = vld1q_u8(src.add(i + 16));
+        std::arch::asm!(
+            "stnp {0:q}, {1:q}, [{2}]",
+            in(vreg) v0,
+            in(vreg) v1,
+            in(reg) dst.add(i),
+            options(nostack, preserves_flags)
+        );
+        i += 32;
+    }
+    std::ptr::copy_nonoverlapping(src.add(i), dst.add(i), size - i);
+}
+
+/// Same contract as [crate::rgba8::transpose_executor], but transposes bands of rows into
+/// a scratch tile and streams every output row segment past the cache.
+///
+/// Does nothing and returns `start_y` unless `store_hint` resolves to non-temporal stores
+/// for this destination.
+pub(crate) fn transpose_executor_streaming<
+    V: Copy + Default,
+    const BLOCK_SIZE: usize,
+    const CN: usize,
+    const FLOP: bool,
+    const FLIP: bool,
+>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    start_y: usize,
+    exec: impl TransposeBlock<V>,
+    store_hint: StoreHint,
+) -> usize {
+    if !use_non_temporal_stores(store_hint, std::mem::size_of_val(output)) {
+        return start_y;
+    }
+
+    let pixel_size = CN * std::mem::size_of::<V>();
+    let band = (STREAM_ROW_BYTES / pixel_size).max(1).div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    let scratch_stride = band * CN;
+
+    let mut scratch = vec![V::default(); BLOCK_SIZE * scratch_stride];
+
+    // Bands are grouped into tiles like in the cached executor, so consecutive bands stream
+    // into the same destination pages instead of touching every page of the image each time.
+    let tile = tile_size::<V, BLOCK_SIZE, CN>();
+    let tile_rows = tile.div_ceil(band) * band;
+    let end_y = start_y + (height - start_y) / band * band;
+    let end_x = width / BLOCK_SIZE * BLOCK_SIZE;
+
+    unsafe {
+        let mut tile_y = start_y;
+
+        while tile_y < end_y {
+            let tile_end_y = (tile_y + tile_rows).min(end_y);
+
+            let mut tile_x = 0usize;
+
+            while tile_x < end_x {
+                let tile_end_x = (tile_x + tile).min(end_x);
+
+                let mut y = tile_y;
+
+                while y < tile_end_y {
+                    let mut x = tile_x;
+
+                    while x < tile_end_x {
+                        for b in (0..band).step_by(BLOCK_SIZE) {
+                            let yy = y + b;
+                            let input_y = if FLIP { height - BLOCK_SIZE - yy } else { yy };
+                            let src = input.get_unchecked(input_stride * input_y + x * CN..);
+                            let dst = scratch.get_unchecked_mut(b * CN..);
+                            exec.transpose_block(src, input_stride, dst, scratch_stride);
+                        }
+
+                        let output_x = if FLOP { x } else { width - BLOCK_SIZE - x };
+
+                        for j in 0..BLOCK_SIZE {
+                            let src = scratch.get_unchecked(j * scratch_stride..);
+                            let dst =
+                                output.get_unchecked_mut(y * CN + (output_x + j) * output_stride..);
+                            stream_row(
+                                src.as_ptr() as *const u8,
+                                dst.as_mut_ptr() as *mut u8,
+                                scratch_stride * std::mem::size_of::<V>(),
+                            );
+                        }
+
+                        x += BLOCK_SIZE;
+                    }
+
+                    y += band;
+                }
+
+                tile_x = tile_end_x;
+            }
+
+            for x in end_x..width {
+                let output_x = if FLOP { x } else { width - 1 - x };
+                for yy in tile_y..tile_end_y {
+                    let input_y = if FLIP { height - 1 - yy } else { yy };
+                    let src = input.get_unchecked(x * CN + input_y * input_stride..);
+                    let dst = output.get_unchecked_mut(yy * CN + output_x * output_stride..);
+                    std::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), CN);
+                }
+            }
+
+            tile_y = tile_end_y;
+        }
+
+        // Makes streamed stores globally visible before the caller continues
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        _mm_sfence();
+    }
+
+    end_y
+}
@@ -0,0 +1,539 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::convert::{mirror_converted, transpose_converted};
+use crate::{
+    flip_plane16, flop_plane16, rotate180_plane16, transpose_plane16, FlipMode, FlopMode,
+    Orientation, TransposeError,
+};
+use roxygen::roxygen;
+
+/// Widens a 5-bit channel to 8 bits by bit replication
+#[inline(always)]
+fn expand5(value: u16) -> u8 {
+    ((value << 3) | (value >> 2)) as u8
+}
+
+/// Widens a 6-bit channel to 8 bits by bit replication
+#[inline(always)]
+fn expand6(value: u16) -> u8 {
+    ((value << 2) | (value >> 4)) as u8
+}
+
+/// Widens a 4-bit channel to 8 bits by bit replication
+#[inline(always)]
+fn expand4(value: u16) -> u8 {
+    (value * 17) as u8
+}
+
+#[inline(always)]
+fn unpack_rgb565(pixel: u16) -> [u8; 4] {
+    [
+        expand5(pixel >> 11),
+        expand6((pixel >> 5) & 0x3f),
+        expand5(pixel & 0x1f),
+        255,
+    ]
+}
+
+#[inline(always)]
+fn unpack_rgba4444(pixel: u16) -> [u8; 4] {
+    [
+        expand4(pixel >> 12),
+        expand4((pixel >> 8) & 0xf),
+        expand4((pixel >> 4) & 0xf),
+        expand4(pixel & 0xf),
+    ]
+}
+
+#[inline(always)]
+fn unpack_rgba5551(pixel: u16) -> [u8; 4] {
+    [
+        expand5(pixel >> 11),
+        expand5((pixel >> 6) & 0x1f),
+        expand5((pixel >> 1) & 0x1f),
+        if pixel & 1 != 0 { 255 } else { 0 },
+    ]
+}
+
+#[inline(always)]
+fn unpack_row<const REVERSE: bool>(src: &[u16], dst: &mut [u8], unpacker: impl Fn(u16) -> [u8; 4]) {
+    let dst: &mut [[u8; 4]] = bytemuck::cast_slice_mut(dst);
+    if REVERSE {
+        for (dst, &src) in dst.iter_mut().zip(src.iter().rev()) {
+            *dst = unpacker(src);
+        }
+    } else {
+        for (dst, &src) in dst.iter_mut().zip(src) {
+            *dst = unpacker(src);
+        }
+    }
+}
+
+fn rotate_unpacked(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    unpacker: impl Fn(u16) -> [u8; 4] + Copy,
+) -> Result<(), TransposeError> {
+    let forward = |src: &[u16], dst: &mut [u8]| unpack_row::<false>(src, dst, unpacker);
+    let reverse = |src: &[u16], dst: &mut [u8]| unpack_row::<true>(src, dst, unpacker);
+    if let Some((flip_mode, flop_mode)) = orientation.transpose_modes() {
+        return transpose_converted::<u16, u8, 1, 4>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            flip_mode,
+            flop_mode,
+            forward,
+        );
+    }
+    match orientation {
+        Orientation::MirrorHorizontal => mirror_converted::<u16, u8, false>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            1,
+            4,
+            reverse,
+        ),
+        Orientation::MirrorVertical => mirror_converted::<u16, u8, true>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            1,
+            4,
+            forward,
+        ),
+        Orientation::Rotate180 => mirror_converted::<u16, u8, true>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            1,
+            4,
+            reverse,
+        ),
+        _ => mirror_converted::<u16, u8, false>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            1,
+            4,
+            forward,
+        ),
+    }
+}
+
+/// Transposes an image of packed 16-bit RGB565 pixels, red in bits 11..16, green in bits 5..11 and blue in bits 0..5.
+///
+/// Pixels are moved as whole words with the 16-bit plane kernels.
+#[roxygen]
+pub fn transpose_rgb565(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_plane16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of packed 16-bit RGB565 pixels, pixels of every row are reversed.
+#[roxygen]
+pub fn flip_rgb565(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flip_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Flops an image of packed 16-bit RGB565 pixels, order of rows is reversed.
+#[roxygen]
+pub fn flop_rgb565(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flop_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Rotates an image of packed 16-bit RGB565 pixels by 180 degrees.
+#[roxygen]
+pub fn rotate180_rgb565(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate180_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Transposes an image of packed 16-bit RGBA4444 pixels, red in bits 12..16, green in 8..12, blue in 4..8 and alpha in 0..4.
+///
+/// Pixels are moved as whole words with the 16-bit plane kernels.
+#[roxygen]
+pub fn transpose_rgba4444(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_plane16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of packed 16-bit RGBA4444 pixels, pixels of every row are reversed.
+#[roxygen]
+pub fn flip_rgba4444(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flip_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Flops an image of packed 16-bit RGBA4444 pixels, order of rows is reversed.
+#[roxygen]
+pub fn flop_rgba4444(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flop_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Rotates an image of packed 16-bit RGBA4444 pixels by 180 degrees.
+#[roxygen]
+pub fn rotate180_rgba4444(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate180_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Transposes an image of packed 16-bit RGBA5551 pixels, red in bits 11..16, green in 6..11, blue in 1..6 and alpha in bit 0.
+///
+/// Pixels are moved as whole words with the 16-bit plane kernels.
+#[roxygen]
+pub fn transpose_rgba5551(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_plane16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of packed 16-bit RGBA5551 pixels, pixels of every row are reversed.
+#[roxygen]
+pub fn flip_rgba5551(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flip_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Flops an image of packed 16-bit RGBA5551 pixels, order of rows is reversed.
+#[roxygen]
+pub fn flop_rgba5551(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flop_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Rotates an image of packed 16-bit RGBA5551 pixels by 180 degrees.
+#[roxygen]
+pub fn rotate180_rgba5551(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate180_plane16(input, input_stride, output, output_stride, width, height)
+}
+
+/// Reorients an image of packed 16-bit RGB565 pixels unpacking it to RGBA8 in the same pass.
+///
+/// Channels are widened to 8 bits by bit replication. Alpha is opaque.
+#[roxygen]
+pub fn rotate_rgb565_to_rgba8(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination RGBA8 image data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output.
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_unpacked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        unpack_rgb565,
+    )
+}
+
+/// Reorients an image of packed 16-bit RGBA4444 pixels unpacking it to RGBA8 in the same pass.
+///
+/// Channels are widened to 8 bits by bit replication.
+#[roxygen]
+pub fn rotate_rgba4444_to_rgba8(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination RGBA8 image data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output.
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_unpacked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        unpack_rgba4444,
+    )
+}
+
+/// Reorients an image of packed 16-bit RGBA5551 pixels unpacking it to RGBA8 in the same pass.
+///
+/// Channels are widened to 8 bits by bit replication.
+#[roxygen]
+pub fn rotate_rgba5551_to_rgba8(
+    /// Source image data, one 16-bit word per pixel.
+    input: &[u16],
+    /// Number of u16 elements per row in the input.
+    input_stride: usize,
+    /// Destination RGBA8 image data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output.
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_unpacked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        orientation,
+        unpack_rgba5551,
+    )
+}
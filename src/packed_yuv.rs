@@ -0,0 +1,744 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::utils::is_valid_image_len;
+use crate::yuv::{check_plane, orient_chroma_422, orient_plane, oriented_size, YuvSample};
+use crate::{FlopMode, Orientation, TransposeError};
+use roxygen::roxygen;
+
+/// Source columns of luma and chroma pairs transposed at once, even so both chroma
+/// samples of a macropixel land in the same strip
+const STRIP_COLUMNS: usize = 16;
+
+/// Transposed source columns `x0..x0 + count` of a packed 4:2:2 frame, each one a row
+/// of `height` luma and chroma pairs in destination order
+struct ColumnStrip<'a> {
+    pairs: &'a [u8],
+    x0: usize,
+    count: usize,
+    height: usize,
+    flop: bool,
+}
+
+impl ColumnStrip<'_> {
+    fn columns(&self) -> std::ops::Range<usize> {
+        self.x0..self.x0 + self.count
+    }
+
+    /// Pairs of source column `x`
+    fn column(&self, x: usize) -> &[u8] {
+        let row = if self.flop {
+            x - self.x0
+        } else {
+            self.x0 + self.count - 1 - x
+        };
+        &self.pairs[row * self.height * 2..(row + 1) * self.height * 2]
+    }
+}
+
+/// Index of `index` out of `len` after optional reversal
+#[inline(always)]
+const fn mirrored(index: usize, len: usize, reversed: bool) -> usize {
+    if reversed {
+        len - 1 - index
+    } else {
+        index
+    }
+}
+
+/// Checks a packed 4:2:2 source frame, odd widths still store a full trailing macropixel
+fn check_packed(
+    src: &[u8],
+    src_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), TransposeError> {
+    check_plane(src, src_stride, width.div_ceil(2) * 4, height)
+}
+
+/// Transposes a packed 4:2:2 frame viewed as an image of luma and chroma pairs with the two
+/// channel plane kernels, a strip of source columns at a time into one scratch buffer, and
+/// hands every strip to `strip` to split into the destination.
+///
+/// A transposed row holds a single source column, so its chroma is entirely U or entirely V.
+fn transpose_strips(
+    src: &[u8],
+    src_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    mut strip: impl FnMut(&ColumnStrip),
+) -> Result<(), TransposeError> {
+    let padded_width = width.div_ceil(2) * 2;
+    let flop = matches!(orientation.transpose_modes(), Some((_, FlopMode::Flop)));
+    let pairs_stride = height * 2;
+    let mut pairs = vec![0u8; pairs_stride * STRIP_COLUMNS.min(padded_width)];
+    for x0 in (0..padded_width).step_by(STRIP_COLUMNS) {
+        let count = STRIP_COLUMNS.min(padded_width - x0);
+        let pairs = &mut pairs[..pairs_stride * count];
+        orient_plane::<u8, 2>(
+            &src[x0 * 2..(height - 1) * src_stride + (x0 + count) * 2],
+            src_stride,
+            pairs,
+            pairs_stride,
+            count,
+            height,
+            orientation,
+        )?;
+        strip(&ColumnStrip {
+            pairs,
+            x0,
+            count,
+            height,
+            flop,
+        });
+    }
+    Ok(())
+}
+
+/// Source row written into destination row `y` by orientations that keep axes
+#[inline(always)]
+fn mirrored_row(
+    src: &[u8],
+    src_stride: usize,
+    y: usize,
+    height: usize,
+    orientation: Orientation,
+) -> &[u8] {
+    let y = mirrored(y, height, orientation.reversed_axes().1);
+    &src[y * src_stride..]
+}
+
+/// Writes luma of packed source row `src` into `dst` for orientations that keep axes
+#[inline(always)]
+fn split_luma_row<const Y_FIRST: bool>(src: &[u8], dst: &mut [u8], width: usize, reversed: bool) {
+    let y_index = if Y_FIRST { 0 } else { 1 };
+    for (x, dst) in dst[..width].iter_mut().enumerate() {
+        *dst = src[mirrored(x, width, reversed) * 2 + y_index];
+    }
+}
+
+/// Chroma of macropixel `x` of packed source row `src`
+#[inline(always)]
+fn macropixel_chroma<const Y_FIRST: bool>(src: &[u8], x: usize) -> (u8, u8) {
+    let c_index = if Y_FIRST { 1 } else { 0 };
+    (src[x * 4 + c_index], src[x * 4 + 2 + c_index])
+}
+
+fn rotate_packed<const Y_FIRST: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_packed(src, src_stride, width, height)?;
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let dst_chroma_width = dst_width.div_ceil(2);
+    check_packed(dst, dst_stride, dst_width, dst_height)?;
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    let (y_index, c_index) = if Y_FIRST { (0, 1) } else { (1, 0) };
+    let pack = |y0: u8, u: u8, y1: u8, v: u8| {
+        if Y_FIRST {
+            [y0, u, y1, v]
+        } else {
+            [u, y0, v, y1]
+        }
+    };
+
+    if orientation.is_transposed() {
+        return transpose_strips(src, src_stride, width, height, orientation, |strip| {
+            // Destination row of source column `x` takes chroma of the macropixel holding `x`,
+            // 4:4:0 chroma is resampled to 4:2:2 by averaging horizontal pairs.
+            for x in strip.columns().filter(|&x| x < width) {
+                let y = mirrored(x, width, !strip.flop);
+                let (luma, u, v) = (strip.column(x), strip.column(x & !1), strip.column(x | 1));
+                let dst = &mut dst[y * dst_stride..y * dst_stride + dst_chroma_width * 4];
+                for (((dst, luma), u), v) in dst
+                    .chunks_exact_mut(4)
+                    .zip(luma.chunks(4))
+                    .zip(u.chunks(4))
+                    .zip(v.chunks(4))
+                {
+                    // Odd heights repeat the last sample into the padding
+                    let last = luma.len() - 2;
+                    dst.copy_from_slice(&pack(
+                        luma[y_index],
+                        u8::average(u[c_index], u[last + c_index]),
+                        luma[last + y_index],
+                        u8::average(v[c_index], v[last + c_index]),
+                    ));
+                }
+            }
+        });
+    }
+
+    let reversed = orientation.reversed_axes().0;
+    for (y, dst) in dst.chunks_mut(dst_stride).enumerate() {
+        let src = mirrored_row(src, src_stride, y, height, orientation);
+        let luma = |x: usize| src[mirrored(x, width, reversed) * 2 + y_index];
+        for (x, dst) in dst[..dst_chroma_width * 4].chunks_exact_mut(4).enumerate() {
+            let (u, v) = macropixel_chroma::<Y_FIRST>(src, mirrored(x, dst_chroma_width, reversed));
+            // Odd widths repeat the last luma sample into the padding
+            let y1 = luma((x * 2 + 1).min(width - 1));
+            dst.copy_from_slice(&pack(luma(x * 2), u, y1, v));
+        }
+    }
+    Ok(())
+}
+
+fn rotate_packed_to_planar<const Y_FIRST: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst_y: &mut [u8],
+    dst_stride_y: usize,
+    dst_u: &mut [u8],
+    dst_stride_u: usize,
+    dst_v: &mut [u8],
+    dst_stride_v: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_packed(src, src_stride, width, height)?;
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let (chroma_width, chroma_height) = oriented_size(width.div_ceil(2), height, orientation);
+    check_plane(dst_y, dst_stride_y, dst_width, dst_height)?;
+    check_plane(dst_u, dst_stride_u, chroma_width, chroma_height)?;
+    check_plane(dst_v, dst_stride_v, chroma_width, chroma_height)?;
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    let (y_index, c_index) = if Y_FIRST { (0, 1) } else { (1, 0) };
+
+    if orientation.is_transposed() {
+        return transpose_strips(src, src_stride, width, height, orientation, |strip| {
+            for x in strip.columns() {
+                let column = strip.column(x);
+                if x < width {
+                    let y = mirrored(x, width, !strip.flop) * dst_stride_y;
+                    for (dst, pair) in dst_y[y..y + height].iter_mut().zip(column.chunks_exact(2)) {
+                        *dst = pair[y_index];
+                    }
+                }
+                let (plane, stride) = if x % 2 == 0 {
+                    (&mut *dst_u, dst_stride_u)
+                } else {
+                    (&mut *dst_v, dst_stride_v)
+                };
+                let y = mirrored(x / 2, chroma_height, !strip.flop) * stride;
+                for (dst, pair) in plane[y..y + height].iter_mut().zip(column.chunks_exact(2)) {
+                    *dst = pair[c_index];
+                }
+            }
+        });
+    }
+
+    let reversed = orientation.reversed_axes().0;
+    for (y, ((dst_y, dst_u), dst_v)) in dst_y
+        .chunks_mut(dst_stride_y)
+        .zip(dst_u.chunks_mut(dst_stride_u))
+        .zip(dst_v.chunks_mut(dst_stride_v))
+        .enumerate()
+    {
+        let src = mirrored_row(src, src_stride, y, height, orientation);
+        split_luma_row::<Y_FIRST>(src, dst_y, width, reversed);
+        for (x, (u, v)) in dst_u[..chroma_width]
+            .iter_mut()
+            .zip(dst_v[..chroma_width].iter_mut())
+            .enumerate()
+        {
+            (*u, *v) = macropixel_chroma::<Y_FIRST>(src, mirrored(x, chroma_width, reversed));
+        }
+    }
+    Ok(())
+}
+
+fn rotate_packed_to_i420<const Y_FIRST: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst_y: &mut [u8],
+    dst_stride_y: usize,
+    dst_u: &mut [u8],
+    dst_stride_u: usize,
+    dst_v: &mut [u8],
+    dst_stride_v: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_packed(src, src_stride, width, height)?;
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let (chroma_width, chroma_height) = (dst_width.div_ceil(2), dst_height.div_ceil(2));
+    check_plane(dst_y, dst_stride_y, dst_width, dst_height)?;
+    check_plane(dst_u, dst_stride_u, chroma_width, chroma_height)?;
+    check_plane(dst_v, dst_stride_v, chroma_width, chroma_height)?;
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    let (y_index, c_index) = if Y_FIRST { (0, 1) } else { (1, 0) };
+
+    // Transposed chroma is subsampled vertically already, so it is averaged horizontally,
+    // otherwise vertically.
+    if orientation.is_transposed() {
+        return transpose_strips(src, src_stride, width, height, orientation, |strip| {
+            for x in strip.columns() {
+                let column = strip.column(x);
+                if x < width {
+                    let y = mirrored(x, width, !strip.flop) * dst_stride_y;
+                    for (dst, pair) in dst_y[y..y + height].iter_mut().zip(column.chunks_exact(2)) {
+                        *dst = pair[y_index];
+                    }
+                }
+                let (plane, stride) = if x % 2 == 0 {
+                    (&mut *dst_u, dst_stride_u)
+                } else {
+                    (&mut *dst_v, dst_stride_v)
+                };
+                let y = mirrored(x / 2, chroma_height, !strip.flop) * stride;
+                for (dst, pairs) in plane[y..y + chroma_width].iter_mut().zip(column.chunks(4)) {
+                    *dst = u8::average(pairs[c_index], pairs[pairs.len() - 2 + c_index]);
+                }
+            }
+        });
+    }
+
+    let reversed = orientation.reversed_axes().0;
+    for (y, dst) in dst_y.chunks_mut(dst_stride_y).enumerate() {
+        let src = mirrored_row(src, src_stride, y, height, orientation);
+        split_luma_row::<Y_FIRST>(src, dst, width, reversed);
+    }
+    for (y, (dst_u, dst_v)) in dst_u
+        .chunks_mut(dst_stride_u)
+        .zip(dst_v.chunks_mut(dst_stride_v))
+        .enumerate()
+    {
+        let top = mirrored_row(src, src_stride, y * 2, height, orientation);
+        let bottom = mirrored_row(
+            src,
+            src_stride,
+            (y * 2 + 1).min(height - 1),
+            height,
+            orientation,
+        );
+        for (x, (u, v)) in dst_u[..chroma_width]
+            .iter_mut()
+            .zip(dst_v[..chroma_width].iter_mut())
+            .enumerate()
+        {
+            let x = mirrored(x, chroma_width, reversed);
+            let (top, bottom) = (
+                macropixel_chroma::<Y_FIRST>(top, x),
+                macropixel_chroma::<Y_FIRST>(bottom, x),
+            );
+            (*u, *v) = (u8::average(top.0, bottom.0), u8::average(top.1, bottom.1));
+        }
+    }
+    Ok(())
+}
+
+/// Reorients a packed YUY2 (YUV 4:2:2, `Y0 U Y1 V` macropixels) frame keeping it YUY2.
+///
+/// Rows hold `width.div_ceil(2)` macropixels of 4 bytes. Transposing orientations would turn
+/// chroma into 4:4:0, so it is resampled back to 4:2:2 by averaging horizontal pairs and
+/// repeating rows, the same way as [rotate_i422](crate::rotate_i422) does.
+#[roxygen]
+pub fn rotate_yuy2(
+    /// Source packed YUY2 frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination packed YUY2 frame.
+    dst: &mut [u8],
+    /// Number of bytes per row in `dst`.
+    dst_stride: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed::<true>(src, src_stride, dst, dst_stride, width, height, orientation)
+}
+
+/// Reorients a packed YUY2 frame into planar YUV keeping every chroma sample.
+///
+/// Chroma planes are 4:4:0, i.e. `height` x `width.div_ceil(2)`, for transposing orientations
+/// and 4:2:2, i.e. `width.div_ceil(2)` x `height`, otherwise.
+#[roxygen]
+pub fn rotate_yuy2_to_planar(
+    /// Source packed YUY2 frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed_to_planar::<true>(
+        src,
+        src_stride,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a packed YUY2 frame into I420 (YUV 4:2:0 planar).
+///
+/// Chroma is averaged down to 4:2:0 along whichever axis remains full resolution after
+/// reorienting, so chroma planes are `dst_width.div_ceil(2)` x `dst_height.div_ceil(2)`
+/// of the reoriented frame.
+#[roxygen]
+pub fn rotate_yuy2_to_i420(
+    /// Source packed YUY2 frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed_to_i420::<true>(
+        src,
+        src_stride,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a packed UYVY (YUV 4:2:2, `U Y0 V Y1` macropixels) frame keeping it UYVY.
+///
+/// Rows hold `width.div_ceil(2)` macropixels of 4 bytes. Transposing orientations would turn
+/// chroma into 4:4:0, so it is resampled back to 4:2:2 by averaging horizontal pairs and
+/// repeating rows, the same way as [rotate_i422](crate::rotate_i422) does.
+#[roxygen]
+pub fn rotate_uyvy(
+    /// Source packed UYVY frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination packed UYVY frame.
+    dst: &mut [u8],
+    /// Number of bytes per row in `dst`.
+    dst_stride: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed::<false>(src, src_stride, dst, dst_stride, width, height, orientation)
+}
+
+/// Reorients a packed UYVY frame into planar YUV keeping every chroma sample.
+///
+/// Chroma planes are 4:4:0, i.e. `height` x `width.div_ceil(2)`, for transposing orientations
+/// and 4:2:2, i.e. `width.div_ceil(2)` x `height`, otherwise.
+#[roxygen]
+pub fn rotate_uyvy_to_planar(
+    /// Source packed UYVY frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed_to_planar::<false>(
+        src,
+        src_stride,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a packed UYVY frame into I420 (YUV 4:2:0 planar).
+///
+/// Chroma is averaged down to 4:2:0 along whichever axis remains full resolution after
+/// reorienting, so chroma planes are `dst_width.div_ceil(2)` x `dst_height.div_ceil(2)`
+/// of the reoriented frame.
+#[roxygen]
+pub fn rotate_uyvy_to_i420(
+    /// Source packed UYVY frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_packed_to_i420::<false>(
+        src,
+        src_stride,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Pixels held by a V210 group of four little endian 32-bit words
+const V210_GROUP_PIXELS: usize = 6;
+/// Bytes in a V210 group
+const V210_GROUP_BYTES: usize = 16;
+
+/// Unpacks V210 groups into luma and 4:2:2 chroma rows padded to whole groups
+fn unpack_v210_row(src: &[u8], y: &mut [u16], u: &mut [u16], v: &mut [u16]) {
+    for (((group, y), u), v) in src
+        .chunks_exact(V210_GROUP_BYTES)
+        .zip(y.chunks_exact_mut(6))
+        .zip(u.chunks_exact_mut(3))
+        .zip(v.chunks_exact_mut(3))
+    {
+        let mut words = [0u32; 4];
+        for (word, bytes) in words.iter_mut().zip(group.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        let sample = |word: usize, index: usize| ((words[word] >> (index * 10)) & 0x3ff) as u16;
+        y.copy_from_slice(&[
+            sample(0, 1),
+            sample(1, 0),
+            sample(1, 2),
+            sample(2, 1),
+            sample(3, 0),
+            sample(3, 2),
+        ]);
+        u.copy_from_slice(&[sample(0, 0), sample(1, 1), sample(2, 2)]);
+        v.copy_from_slice(&[sample(0, 2), sample(2, 0), sample(3, 1)]);
+    }
+}
+
+/// Packs luma and 4:2:2 chroma rows into V210 groups, samples past the rows are zero
+fn pack_v210_row(y: &[u16], u: &[u16], v: &[u16], dst: &mut [u8]) {
+    let at = |plane: &[u16], index: usize| plane.get(index).copied().unwrap_or(0) as u32;
+    for (group, dst) in dst.chunks_exact_mut(V210_GROUP_BYTES).enumerate() {
+        let (luma, chroma) = (group * 6, group * 3);
+        let words = [
+            at(u, chroma) | (at(y, luma) << 10) | (at(v, chroma) << 20),
+            at(y, luma + 1) | (at(u, chroma + 1) << 10) | (at(y, luma + 2) << 20),
+            at(v, chroma + 1) | (at(y, luma + 3) << 10) | (at(u, chroma + 2) << 20),
+            at(y, luma + 4) | (at(v, chroma + 2) << 10) | (at(y, luma + 5) << 20),
+        ];
+        for (dst, word) in dst.chunks_exact_mut(4).zip(words) {
+            dst.copy_from_slice(&word.to_le_bytes());
+        }
+    }
+}
+
+/// Reorients a V210 (10-bit YUV 4:2:2, 6 pixels in 16 bytes) frame keeping it V210.
+///
+/// Rows hold `width.div_ceil(6)` groups of 16 bytes, strides are usually aligned to 128 bytes.
+/// The frame is unpacked to 10-bit planes, reoriented and repacked; transposing orientations
+/// resample chroma back to 4:2:2 the same way as [rotate_i422](crate::rotate_i422) does.
+/// Samples past the destination width in the last group are written as zero.
+#[roxygen]
+pub fn rotate_v210(
+    /// Source V210 frame.
+    src: &[u8],
+    /// Number of bytes per row in `src`.
+    src_stride: usize,
+    /// Destination V210 frame.
+    dst: &mut [u8],
+    /// Number of bytes per row in `dst`.
+    dst_stride: usize,
+    /// Width of the source frame in pixels.
+    width: usize,
+    /// Height of the source frame in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    let src_groups = width.div_ceil(V210_GROUP_PIXELS);
+    let src_row_bytes = src_groups * V210_GROUP_BYTES;
+    if !is_valid_image_len(src.len(), src_stride, src_row_bytes, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if src_stride < src_row_bytes {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let dst_groups = dst_width.div_ceil(V210_GROUP_PIXELS);
+    let dst_row_bytes = dst_groups * V210_GROUP_BYTES;
+    if !is_valid_image_len(dst.len(), dst_stride, dst_row_bytes, dst_height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if dst_stride < dst_row_bytes {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+
+    let (luma_stride, chroma_stride) = (src_groups * 6, src_groups * 3);
+    let mut y = vec![0u16; luma_stride * height];
+    let mut u = vec![0u16; chroma_stride * height];
+    let mut v = vec![0u16; chroma_stride * height];
+    for (((src, y), u), v) in src
+        .chunks(src_stride)
+        .zip(y.chunks_exact_mut(luma_stride))
+        .zip(u.chunks_exact_mut(chroma_stride))
+        .zip(v.chunks_exact_mut(chroma_stride))
+    {
+        unpack_v210_row(&src[..src_row_bytes], y, u, v);
+    }
+
+    let dst_chroma_width = dst_width.div_ceil(2);
+    let mut dst_y = vec![0u16; dst_width * dst_height];
+    let mut dst_u = vec![0u16; dst_chroma_width * dst_height];
+    let mut dst_v = vec![0u16; dst_chroma_width * dst_height];
+    orient_plane::<u16, 1>(
+        &y,
+        luma_stride,
+        &mut dst_y,
+        dst_width,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_chroma_422(
+        &u,
+        chroma_stride,
+        &mut dst_u,
+        dst_chroma_width,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_chroma_422(
+        &v,
+        chroma_stride,
+        &mut dst_v,
+        dst_chroma_width,
+        width,
+        height,
+        orientation,
+    )?;
+
+    for (((dst, y), u), v) in dst
+        .chunks_mut(dst_stride)
+        .zip(dst_y.chunks_exact(dst_width))
+        .zip(dst_u.chunks_exact(dst_chroma_width))
+        .zip(dst_v.chunks_exact(dst_chroma_width))
+    {
+        pack_v210_row(y, u, v, &mut dst[..dst_row_bytes]);
+    }
+    Ok(())
+}
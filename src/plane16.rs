@@ -26,9 +26,11 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::fused::FusedTranspose;
 #[allow(unused_imports)]
 use crate::rgba8::*;
-use crate::{FlipMode, FlopMode, TransposeError};
+use crate::utils::is_valid_image_len;
+use crate::{FlipMode, FlopMode, StoreHint, TransposeError};
 
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 struct TransposePlaneBlockNeon4x4<const FLOP: bool, const FLIP: bool> {}
@@ -110,11 +112,24 @@ fn transpose_plane16_impl_neon<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 1;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockNeon8x8::<FLOP, FLIP> {},
+        store_hint,
+    );
+
     y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
         input,
         input_stride,
@@ -161,11 +176,109 @@ unsafe fn transpose_plane16_impl_ssse3<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
+) {
+    const CN: usize = 1;
+
+    let mut y = 0usize;
+
+    y = transpose_executor_streaming::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+        store_hint,
+    );
+
+    y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_4x4::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposePlaneBlockAvx2_16x16<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposePlaneBlockAvx2_16x16<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::avx::avx2_transpose_u16_16x16;
+        avx2_transpose_u16_16x16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+#[target_feature(enable = "avx2")]
+unsafe fn transpose_plane16_impl_avx2<const FLOP: bool, const FLIP: bool>(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u16],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 1;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u16, 16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx2_16x16::<FLOP, FLIP> {},
+        store_hint,
+    );
+
+    y = transpose_executor::<u16, 16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx2_16x16::<FLOP, FLIP> {},
+    );
+
     y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
         input,
         input_stride,
@@ -199,6 +312,192 @@ unsafe fn transpose_plane16_impl_ssse3<const FLOP: bool, const FLIP: bool>(
     )
 }
 
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+struct TransposePlaneBlockAvx512_32x32<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposePlaneBlockAvx512_32x32<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::avx512::avx512_transpose_32x32_u16;
+        avx512_transpose_32x32_u16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+struct TransposePlaneBlockAvx512_16x16<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposePlaneBlockAvx512_16x16<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::avx512::avx512_transpose_16x16_u16;
+        avx512_transpose_16x16_u16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+#[target_feature(enable = "avx512bw")]
+unsafe fn transpose_plane16_impl_avx512<const FLOP: bool, const FLIP: bool>(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u16],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    store_hint: StoreHint,
+) {
+    const CN: usize = 1;
+
+    let mut y = 0usize;
+
+    y = transpose_executor_streaming::<u16, 32, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx512_32x32::<FLOP, FLIP> {},
+        store_hint,
+    );
+
+    y = transpose_executor::<u16, 32, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx512_32x32::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockAvx512_16x16::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockSSSe3_4x4::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposePlaneBlockWasm8x8<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposePlaneBlockWasm8x8<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_8x8_u16;
+        wasm_transpose_8x8_u16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposePlaneBlockWasm4x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposePlaneBlockWasm4x4<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_4x4_u16;
+        wasm_transpose_4x4_u16::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+#[target_feature(enable = "simd128")]
+unsafe fn transpose_plane16_impl_wasm<const FLOP: bool, const FLIP: bool>(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u16],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    const CN: usize = 1;
+
+    let mut y = 0usize;
+
+    y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockWasm8x8::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposePlaneBlockWasm4x4::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
+#[allow(unused_variables)]
 pub(crate) fn transpose_plane16_chunked(
     input: &[u16],
     input_stride: usize,
@@ -208,11 +507,12 @@ pub(crate) fn transpose_plane16_chunked(
     height: usize,
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * width {
+    if !is_valid_image_len(output.len(), output_stride, height, width) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width {
@@ -224,6 +524,22 @@ pub(crate) fn transpose_plane16_chunked(
 
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
+        #[cfg(feature = "sve")]
+        if crate::sve::has_wide_sve() {
+            use crate::sve::sve_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u16, 1, false, false>,
+                    FlopMode::Flop => sve_transpose::<u16, 1, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u16, 1, false, true>,
+                    FlopMode::Flop => sve_transpose::<u16, 1, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
         let executor = match flip_mode {
             FlipMode::NoFlip => match flop_mode {
                 FlopMode::NoFlop => transpose_plane16_impl_neon::<false, false>,
@@ -234,7 +550,15 @@ pub(crate) fn transpose_plane16_chunked(
                 FlopMode::Flop => transpose_plane16_impl_neon::<true, true>,
             },
         };
-        executor(input, input_stride, output, output_stride, width, height);
+        executor(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            store_hint,
+        );
         Ok(())
     }
     #[cfg(all(
@@ -243,24 +567,123 @@ pub(crate) fn transpose_plane16_chunked(
         any(feature = "sse", feature = "avx")
     ))]
     {
+        #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+        if std::arch::is_x86_feature_detected!("avx512bw") {
+            let executor: StreamingTransposer<u16> = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_avx512::<false, false>,
+                    FlopMode::Flop => transpose_plane16_impl_avx512::<true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_avx512::<false, true>,
+                    FlopMode::Flop => transpose_plane16_impl_avx512::<true, true>,
+                },
+            };
+
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            }
+            return Ok(());
+        }
+
+        #[cfg(feature = "avx")]
+        if std::arch::is_x86_feature_detected!("avx2") {
+            let executor: StreamingTransposer<u16> = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_avx2::<false, false>,
+                    FlopMode::Flop => transpose_plane16_impl_avx2::<true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_avx2::<false, true>,
+                    FlopMode::Flop => transpose_plane16_impl_avx2::<true, true>,
+                },
+            };
+
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            }
+            return Ok(());
+        }
+
         if std::arch::is_x86_feature_detected!("ssse3") {
-            let executor: unsafe fn(&[u16], usize, &mut [u16], usize, usize, usize) =
-                match flip_mode {
-                    FlipMode::NoFlip => match flop_mode {
-                        FlopMode::NoFlop => transpose_plane16_impl_ssse3::<false, false>,
-                        FlopMode::Flop => transpose_plane16_impl_ssse3::<true, false>,
-                    },
-                    FlipMode::Flip => match flop_mode {
-                        FlopMode::NoFlop => transpose_plane16_impl_ssse3::<false, true>,
-                        FlopMode::Flop => transpose_plane16_impl_ssse3::<true, true>,
-                    },
-                };
-
-            unsafe { executor(input, input_stride, output, output_stride, width, height) };
+            let executor: StreamingTransposer<u16> = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_ssse3::<false, false>,
+                    FlopMode::Flop => transpose_plane16_impl_ssse3::<true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => transpose_plane16_impl_ssse3::<false, true>,
+                    FlopMode::Flop => transpose_plane16_impl_ssse3::<true, true>,
+                },
+            };
+
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            };
             return Ok(());
         }
     }
-    #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    {
+        let executor: unsafe fn(&[u16], usize, &mut [u16], usize, usize, usize) = match flip_mode {
+            FlipMode::NoFlip => match flop_mode {
+                FlopMode::NoFlop => transpose_plane16_impl_wasm::<false, false>,
+                FlopMode::Flop => transpose_plane16_impl_wasm::<true, false>,
+            },
+            FlipMode::Flip => match flop_mode {
+                FlopMode::NoFlop => transpose_plane16_impl_wasm::<false, true>,
+                FlopMode::Flop => transpose_plane16_impl_wasm::<true, true>,
+            },
+        };
+        unsafe { executor(input, input_stride, output, output_stride, width, height) }
+        Ok(())
+    }
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        if crate::rvv::has_rvv() {
+            use crate::rvv::rvv_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u16, 1, false, false>,
+                    FlopMode::Flop => rvv_transpose::<u16, 1, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u16, 1, false, true>,
+                    FlopMode::Flop => rvv_transpose::<u16, 1, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
+    }
+    #[cfg(not(any(
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+    )))]
     {
         use crate::transpose_arbitrary::transpose_arbitrary;
         transpose_arbitrary::<u16>(
@@ -275,3 +698,112 @@ pub(crate) fn transpose_plane16_chunked(
         )
     }
 }
+
+/// Fused transpose of 16-bit planes going through the block kernels of this module.
+pub(crate) struct Plane16Fused {}
+
+impl FusedTranspose<u16> for Plane16Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u16],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u16], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u16, O, 8, 1, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposePlaneBlockNeon8x8::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<u16, O, 8, 1, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposePlaneBlockWasm8x8::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+            if std::arch::is_x86_feature_detected!("avx512bw") {
+                unsafe {
+                    crate::fused::transpose_fused_avx512::<u16, O, 32, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposePlaneBlockAvx512_32x32::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u16, O, 16, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposePlaneBlockAvx2_16x16::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u16, O, 8, 1, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposePlaneBlockSSSe3_8x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<1>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
@@ -0,0 +1,791 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::convert::{check_bit_depth, mirror_converted, transpose_converted};
+use crate::fused::FusedSample;
+use crate::{AlphaMode, FlipMode, FlopMode, TransposeError};
+use bytemuck::Pod;
+use roxygen::roxygen;
+
+/// Applies `f` to every RGBA pixel, writing pixels in reverse order when `REVERSE` is set
+#[inline(always)]
+fn map_pixels<V: Pod, const REVERSE: bool>(src: &[V], dst: &mut [V], f: impl Fn([V; 4]) -> [V; 4])
+where
+    [V; 4]: Pod,
+{
+    let src: &[[V; 4]] = bytemuck::cast_slice(src);
+    let dst: &mut [[V; 4]] = bytemuck::cast_slice_mut(dst);
+    if REVERSE {
+        for (dst, &src) in dst.iter_mut().rev().zip(src.iter()) {
+            *dst = f(src);
+        }
+    } else {
+        for (dst, &src) in dst.iter_mut().zip(src.iter()) {
+            *dst = f(src);
+        }
+    }
+}
+
+#[inline(always)]
+fn premultiply_u8(px: [u8; 4]) -> [u8; 4] {
+    // Alpha lane is scaled by 255 and stays as is, so every lane runs the same
+    // exact rounded division by 255.
+    let factor = [px[3], px[3], px[3], u8::MAX];
+    std::array::from_fn(|i| {
+        let v = px[i] as u16 * factor[i] as u16 + 128;
+        ((v + (v >> 8)) >> 8) as u8
+    })
+}
+
+#[inline(always)]
+fn unpremultiply_u8(px: [u8; 4]) -> [u8; 4] {
+    let px = unpremultiply_u16(px.map(|v| v as u16), u8::MAX as u16);
+    px.map(|v| v as u8)
+}
+
+#[inline(always)]
+fn premultiply_u16(px: [u16; 4], max: u16, bit_depth: u32) -> [u16; 4] {
+    let half = 1u32 << (bit_depth - 1);
+    let a = px[3].min(max) as u32;
+    let factor = [a, a, a, max as u32];
+    std::array::from_fn(|i| {
+        // Exact rounded division by `2^bit_depth - 1` of products up to `max * max`
+        let t = px[i].min(max) as u32 * factor[i] + half;
+        ((t + (t >> bit_depth)) >> bit_depth) as u16
+    })
+}
+
+#[inline(always)]
+fn unpremultiply_u16(px: [u16; 4], max: u16) -> [u16; 4] {
+    let a = px[3] as u32;
+    let recip = if a == 0 { 0. } else { 1. / a as f32 };
+    let div = |c: u16| {
+        let c = c as u32;
+        let n = c * max as u32 + (a >> 1);
+        // Below alpha the quotient is under 2^16, so float reciprocal lands within one
+        // of it and the remainder corrects that, colors at or above alpha saturate.
+        let q = (n as f32 * recip) as u32;
+        let r = n.wrapping_sub(q.wrapping_mul(a)) as i32;
+        let q = q
+            .wrapping_add((r >= a as i32) as u32)
+            .wrapping_sub((r < 0) as u32);
+        if a == 0 {
+            0
+        } else if c >= a {
+            max
+        } else {
+            q as u16
+        }
+    };
+    [div(px[0]), div(px[1]), div(px[2]), px[3]]
+}
+
+#[inline(always)]
+fn premultiply_f32(px: [f32; 4]) -> [f32; 4] {
+    let a = px[3];
+    [px[0] * a, px[1] * a, px[2] * a, a]
+}
+
+#[inline(always)]
+fn unpremultiply_f32(px: [f32; 4]) -> [f32; 4] {
+    let a = px[3];
+    if a == 0. {
+        return [0., 0., 0., a];
+    }
+    [px[0] / a, px[1] / a, px[2] / a, a]
+}
+
+/// Splits off pixels of a row not yet written after `done` pixels, from the back of `dst`
+/// when `REVERSE` is set
+#[inline(always)]
+fn remaining_row<'a, 'b, const REVERSE: bool>(
+    src: &'a [u8],
+    dst: &'b mut [u8],
+    done: usize,
+) -> (&'a [u8], &'b mut [u8]) {
+    let pixels = src.len().min(dst.len()) / 4;
+    let src = &src[done * 4..pixels * 4];
+    if REVERSE {
+        (src, &mut dst[..(pixels - done) * 4])
+    } else {
+        (src, &mut dst[done * 4..pixels * 4])
+    }
+}
+
+/// Scales a row of RGBA8 pixels by alpha on the widest SIMD kernels available, pixels the
+/// kernels leave over are scaled one by one. Writes pixels in reverse order when `REVERSE` is set.
+#[inline(always)]
+fn alpha_row_u8<const PREMULTIPLY: bool, const REVERSE: bool>(src: &[u8], dst: &mut [u8]) {
+    #[allow(unused_mut)]
+    let mut done = 0usize;
+    #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+    {
+        done += if PREMULTIPLY {
+            crate::neon::neon_premultiply_u8::<REVERSE>(src, dst)
+        } else {
+            crate::neon::neon_unpremultiply_u8::<REVERSE>(src, dst)
+        };
+    }
+    #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        let (src, dst) = remaining_row::<REVERSE>(src, dst, done);
+        done += unsafe {
+            if PREMULTIPLY {
+                crate::avx::avx2_premultiply_u8::<REVERSE>(src, dst)
+            } else {
+                crate::avx::avx2_unpremultiply_u8::<REVERSE>(src, dst)
+            }
+        };
+    }
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ))]
+    if std::arch::is_x86_feature_detected!("sse4.1") {
+        let (src, dst) = remaining_row::<REVERSE>(src, dst, done);
+        done += unsafe {
+            if PREMULTIPLY {
+                crate::sse::sse_premultiply_u8::<REVERSE>(src, dst)
+            } else {
+                crate::sse::sse_unpremultiply_u8::<REVERSE>(src, dst)
+            }
+        };
+    }
+    let (src, dst) = remaining_row::<REVERSE>(src, dst, done);
+    if PREMULTIPLY {
+        map_pixels::<u8, REVERSE>(src, dst, premultiply_u8);
+    } else {
+        map_pixels::<u8, REVERSE>(src, dst, unpremultiply_u8);
+    }
+}
+
+/// Geometric operation performed while pixels are scaled by alpha
+#[derive(Copy, Clone)]
+enum Layout {
+    Transpose(FlipMode, FlopMode),
+    Flip,
+    Flop,
+    Rotate180,
+}
+
+/// Converts a row of RGBA pixels while it is copied
+trait AlphaRow<V>: Copy {
+    /// Writes converted pixels of `src` into `dst`, in reverse order when `REVERSE` is set
+    fn convert<const REVERSE: bool>(&self, src: &[V], dst: &mut [V]);
+}
+
+/// Applies a pixel function to every pixel, left for the compiler to vectorize
+#[derive(Copy, Clone)]
+struct PixelRow<F>(F);
+
+impl<V: Pod, F: Fn([V; 4]) -> [V; 4] + Copy> AlphaRow<V> for PixelRow<F>
+where
+    [V; 4]: Pod,
+{
+    #[inline(always)]
+    fn convert<const REVERSE: bool>(&self, src: &[V], dst: &mut [V]) {
+        map_pixels::<V, REVERSE>(src, dst, self.0)
+    }
+}
+
+/// Runs RGBA8 rows through the SIMD alpha kernels, see [alpha_row_u8]
+#[derive(Copy, Clone)]
+struct Rgba8AlphaRow<const PREMULTIPLY: bool>;
+
+impl<const PREMULTIPLY: bool> AlphaRow<u8> for Rgba8AlphaRow<PREMULTIPLY> {
+    #[inline(always)]
+    fn convert<const REVERSE: bool>(&self, src: &[u8], dst: &mut [u8]) {
+        alpha_row_u8::<PREMULTIPLY, REVERSE>(src, dst)
+    }
+}
+
+/// Runs transpose or mirror of RGBA image converting every row with `row` while copying it
+#[inline(always)]
+fn alpha_converted<V: Pod + FusedSample>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    layout: Layout,
+    row: impl AlphaRow<V>,
+) -> Result<(), TransposeError> {
+    macro_rules! mirror {
+        ($pixels:expr, $rows:expr) => {
+            mirror_converted::<V, V, $rows>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                4,
+                4,
+                |src, dst| row.convert::<$pixels>(src, dst),
+            )
+        };
+    }
+    match layout {
+        Layout::Transpose(flip_mode, flop_mode) => transpose_converted::<V, V, 4, 4>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            flip_mode,
+            flop_mode,
+            |src, dst| row.convert::<false>(src, dst),
+        ),
+        Layout::Flip => mirror!(true, false),
+        Layout::Flop => mirror!(false, true),
+        Layout::Rotate180 => mirror!(true, true),
+    }
+}
+
+fn alpha_converted_u8(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    layout: Layout,
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    match alpha_mode {
+        AlphaMode::Premultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            Rgba8AlphaRow::<true>,
+        ),
+        AlphaMode::Unpremultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            Rgba8AlphaRow::<false>,
+        ),
+    }
+}
+
+fn alpha_converted_u16(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u16],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    layout: Layout,
+    alpha_mode: AlphaMode,
+    bit_depth: u32,
+) -> Result<(), TransposeError> {
+    check_bit_depth(bit_depth)?;
+    let max = ((1u32 << bit_depth) - 1) as u16;
+    match alpha_mode {
+        AlphaMode::Premultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            PixelRow(move |px| premultiply_u16(px, max, bit_depth)),
+        ),
+        AlphaMode::Unpremultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            PixelRow(move |px| unpremultiply_u16(px, max)),
+        ),
+    }
+}
+
+fn alpha_converted_f32(
+    input: &[f32],
+    input_stride: usize,
+    output: &mut [f32],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    layout: Layout,
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    match alpha_mode {
+        AlphaMode::Premultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            PixelRow(premultiply_f32),
+        ),
+        AlphaMode::Unpremultiply => alpha_converted(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            layout,
+            PixelRow(unpremultiply_f32),
+        ),
+    }
+}
+
+/// Transposes an 8-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Integer results are rounded to nearest.
+///
+/// # Performance
+///
+/// Blocks are transposed with the SIMD kernels and scaled by alpha while still in cache,
+/// color is scaled on SSE4.1, AVX2 or NEON kernels.
+#[roxygen]
+pub fn transpose_rgba_alpha(
+    /// Source RGBA image data.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_u8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Transpose(flip_mode, flop_mode),
+        alpha_mode,
+    )
+}
+
+/// Transposes a 16-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Alpha range is derived from `bit_depth`, integer results are rounded to nearest.
+///
+/// # Performance
+///
+/// Blocks are transposed with the SIMD kernels and scaled by alpha while still in cache,
+/// scaling itself is left for the compiler to vectorize.
+#[roxygen]
+pub fn transpose_rgba16_alpha(
+    /// Source RGBA image data.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (height * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Bit depth of the samples, 8 to 16.
+    bit_depth: u32,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_u16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Transpose(flip_mode, flop_mode),
+        alpha_mode,
+        bit_depth,
+    )
+}
+
+/// Transposes a 32-bit float RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// # Performance
+///
+/// Blocks are transposed with the SIMD kernels and scaled by alpha while still in cache,
+/// scaling itself is left for the compiler to vectorize.
+#[roxygen]
+pub fn transpose_rgba_f32_alpha(
+    /// Source RGBA image data.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (height * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_f32(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Transpose(flip_mode, flop_mode),
+        alpha_mode,
+    )
+}
+
+/// Flips an 8-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Integer results are rounded to nearest.
+#[roxygen]
+pub fn flip_rgba_alpha(
+    /// Source RGBA image data.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_u8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flip,
+        alpha_mode,
+    )
+}
+
+/// Flips a 16-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Alpha range is derived from `bit_depth`, integer results are rounded to nearest.
+#[roxygen]
+pub fn flip_rgba16_alpha(
+    /// Source RGBA image data.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Bit depth of the samples, 8 to 16.
+    bit_depth: u32,
+) -> Result<(), TransposeError> {
+    alpha_converted_u16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flip,
+        alpha_mode,
+        bit_depth,
+    )
+}
+
+/// Flips a 32-bit float RGBA image while premultiplying or unpremultiplying its color channels.
+#[roxygen]
+pub fn flip_rgba_f32_alpha(
+    /// Source RGBA image data.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_f32(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flip,
+        alpha_mode,
+    )
+}
+
+/// Flops an 8-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Integer results are rounded to nearest.
+#[roxygen]
+pub fn flop_rgba_alpha(
+    /// Source RGBA image data.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_u8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flop,
+        alpha_mode,
+    )
+}
+
+/// Flops a 16-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Alpha range is derived from `bit_depth`, integer results are rounded to nearest.
+#[roxygen]
+pub fn flop_rgba16_alpha(
+    /// Source RGBA image data.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Bit depth of the samples, 8 to 16.
+    bit_depth: u32,
+) -> Result<(), TransposeError> {
+    alpha_converted_u16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flop,
+        alpha_mode,
+        bit_depth,
+    )
+}
+
+/// Flops a 32-bit float RGBA image while premultiplying or unpremultiplying its color channels.
+#[roxygen]
+pub fn flop_rgba_f32_alpha(
+    /// Source RGBA image data.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_f32(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Flop,
+        alpha_mode,
+    )
+}
+
+/// Rotates by 180 degrees an 8-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Integer results are rounded to nearest.
+#[roxygen]
+pub fn rotate180_rgba_alpha(
+    /// Source RGBA image data.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_u8(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Rotate180,
+        alpha_mode,
+    )
+}
+
+/// Rotates by 180 degrees a 16-bit RGBA image while premultiplying or unpremultiplying its color channels.
+///
+/// Alpha range is derived from `bit_depth`, integer results are rounded to nearest.
+#[roxygen]
+pub fn rotate180_rgba16_alpha(
+    /// Source RGBA image data.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+    /// Bit depth of the samples, 8 to 16.
+    bit_depth: u32,
+) -> Result<(), TransposeError> {
+    alpha_converted_u16(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Rotate180,
+        alpha_mode,
+        bit_depth,
+    )
+}
+
+/// Rotates by 180 degrees a 32-bit float RGBA image while premultiplying or unpremultiplying its color channels.
+#[roxygen]
+pub fn rotate180_rgba_f32_alpha(
+    /// Source RGBA image data.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for RGBA data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Whether color channels are multiplied or divided by alpha.
+    alpha_mode: AlphaMode,
+) -> Result<(), TransposeError> {
+    alpha_converted_f32(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        Layout::Rotate180,
+        alpha_mode,
+    )
+}
@@ -0,0 +1,74 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+/// Builds byte shuffle mask (`pshufb` or `i8x16.swizzle`) that moves bytes
+/// from register `in_reg` into register `out_reg`
+/// when a block of `regs` registers is reversed pixel by pixel,
+/// where each pixel is `px` bytes wide.
+const fn make_reverse_mask(px: usize, regs: usize, out_reg: usize, in_reg: usize) -> [i8; 16] {
+    let pixels = regs * 16 / px;
+    let mut mask = [-128i8; 16];
+    let mut i = 0usize;
+    while i < 16 {
+        let out_byte = out_reg * 16 + i;
+        if out_byte / px < pixels {
+            let src_byte = (pixels - 1 - out_byte / px) * px + out_byte % px;
+            if src_byte / 16 == in_reg {
+                mask[i] = (src_byte % 16) as i8;
+            }
+        }
+        i += 1;
+    }
+    mask
+}
+
+pub(crate) struct ReverseMasks<const PX: usize> {}
+
+impl<const PX: usize> ReverseMasks<PX> {
+    // Pixels of size 1, 2, 4, 8, 16 fits in a single register.
+    pub(crate) const SINGLE: [i8; 16] = make_reverse_mask(PX, 1, 0, 0);
+    // Pixels of size 3, 6, 12 fits in 3 registers.
+    pub(crate) const TRIPLE: [[[i8; 16]; 3]; 3] = [
+        [
+            make_reverse_mask(PX, 3, 0, 0),
+            make_reverse_mask(PX, 3, 0, 1),
+            make_reverse_mask(PX, 3, 0, 2),
+        ],
+        [
+            make_reverse_mask(PX, 3, 1, 0),
+            make_reverse_mask(PX, 3, 1, 1),
+            make_reverse_mask(PX, 3, 1, 2),
+        ],
+        [
+            make_reverse_mask(PX, 3, 2, 0),
+            make_reverse_mask(PX, 3, 2, 1),
+            make_reverse_mask(PX, 3, 2, 2),
+        ],
+    ];
+}
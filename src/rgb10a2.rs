@@ -0,0 +1,146 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::{
+    flip_rgba, flop_rgba, rotate180_rgba, transpose_rgba, FlipMode, FlopMode, TransposeError,
+};
+use roxygen::roxygen;
+
+/// Transposes an image of 32-bit packed R10G10B10A2 pixels.
+///
+/// Pixels are moved as whole words, so B10G10R10A2 or any other 32-bit packed layout
+/// is transposed the same way.
+#[roxygen]
+pub fn transpose_rgb10a2(
+    /// Source image data, one 32-bit word per pixel.
+    input: &[u32],
+    /// Number of u32 elements per row in the input.
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u32],
+    /// Number of u32 elements per row in the output.
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// see [FlipMode].
+    flip_mode: FlipMode,
+    /// see [FlopMode].
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_rgba(
+        bytemuck::cast_slice(input),
+        input_stride * 4,
+        bytemuck::cast_slice_mut(output),
+        output_stride * 4,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an image of 32-bit packed R10G10B10A2 or B10G10R10A2 pixels, pixels of every row are reversed.
+#[roxygen]
+pub fn flip_rgb10a2(
+    /// Source image data, one 32-bit word per pixel.
+    input: &[u32],
+    /// Number of u32 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u32],
+    /// Number of u32 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flip_rgba(
+        bytemuck::cast_slice(input),
+        input_stride * 4,
+        bytemuck::cast_slice_mut(output),
+        output_stride * 4,
+        width,
+        height,
+    )
+}
+
+/// Flops an image of 32-bit packed R10G10B10A2 or B10G10R10A2 pixels, order of rows is reversed.
+#[roxygen]
+pub fn flop_rgb10a2(
+    /// Source image data, one 32-bit word per pixel.
+    input: &[u32],
+    /// Number of u32 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u32],
+    /// Number of u32 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    flop_rgba(
+        bytemuck::cast_slice(input),
+        input_stride * 4,
+        bytemuck::cast_slice_mut(output),
+        output_stride * 4,
+        width,
+        height,
+    )
+}
+
+/// Rotates an image of 32-bit packed R10G10B10A2 or B10G10R10A2 pixels by 180 degrees.
+#[roxygen]
+pub fn rotate180_rgb10a2(
+    /// Source image data, one 32-bit word per pixel.
+    input: &[u32],
+    /// Number of u32 elements per row in the input.
+    input_stride: usize,
+    /// Destination image data.
+    output: &mut [u32],
+    /// Number of u32 elements per row in the output.
+    output_stride: usize,
+    /// Width of the image in pixels.
+    width: usize,
+    /// Height of the image in pixels.
+    height: usize,
+) -> Result<(), TransposeError> {
+    rotate180_rgba(
+        bytemuck::cast_slice(input),
+        input_stride * 4,
+        bytemuck::cast_slice_mut(output),
+        output_stride * 4,
+        width,
+        height,
+    )
+}
@@ -26,8 +26,10 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::fused::FusedTranspose;
 #[allow(unused_imports)]
 use crate::rgba8::*;
+use crate::utils::is_valid_image_len;
 use crate::{FlipMode, FlopMode, TransposeError};
 
 #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
@@ -223,6 +225,78 @@ unsafe fn transpose_rgba16_impl_avx2<const FLOP: bool, const FLIP: bool>(
     )
 }
 
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+struct TransposeBlockAvx512_8x8<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u16>
+    for TransposeBlockAvx512_8x8<FLOP, FLIP>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::avx512::avx512_transpose_u16x4_8x8;
+        avx512_transpose_u16x4_8x8::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+#[target_feature(enable = "avx512f")]
+unsafe fn transpose_rgba16_impl_avx512<const FLOP: bool, const FLIP: bool>(
+    input: &[u16],
+    input_stride: usize,
+    output: &mut [u16],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    const CN: usize = 4;
+
+    let mut y = 0usize;
+
+    y = transpose_executor::<u16, 8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockAvx512_8x8::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockAvx2_4x4::<FLOP, FLIP> {},
+    );
+
+    y = transpose_executor::<u16, 2, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockSSSE3_2x2::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u16, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
 pub(crate) fn transpose_rgba16_chunked(
     input: &[u16],
     input_stride: usize,
@@ -233,10 +307,10 @@ pub(crate) fn transpose_rgba16_chunked(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * 4, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * width {
+    if !is_valid_image_len(output.len(), output_stride, height * 4, width) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * 4 {
@@ -248,6 +322,22 @@ pub(crate) fn transpose_rgba16_chunked(
 
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
+        #[cfg(feature = "sve")]
+        if crate::sve::has_wide_sve() {
+            use crate::sve::sve_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u16, 4, false, false>,
+                    FlopMode::Flop => sve_transpose::<u16, 4, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u16, 4, false, true>,
+                    FlopMode::Flop => sve_transpose::<u16, 4, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
         let executor = match flip_mode {
             FlipMode::NoFlip => match flop_mode {
                 FlopMode::NoFlop => transpose_rgba16_impl_neon::<false, false>,
@@ -267,6 +357,24 @@ pub(crate) fn transpose_rgba16_chunked(
         any(feature = "sse", feature = "avx")
     ))]
     {
+        #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+        if std::arch::is_x86_feature_detected!("avx512f") {
+            let executor: unsafe fn(&[u16], usize, &mut [u16], usize, usize, usize) =
+                match flip_mode {
+                    FlipMode::NoFlip => match flop_mode {
+                        FlopMode::NoFlop => transpose_rgba16_impl_avx512::<false, false>,
+                        FlopMode::Flop => transpose_rgba16_impl_avx512::<true, false>,
+                    },
+                    FlipMode::Flip => match flop_mode {
+                        FlopMode::NoFlop => transpose_rgba16_impl_avx512::<false, true>,
+                        FlopMode::Flop => transpose_rgba16_impl_avx512::<true, true>,
+                    },
+                };
+
+            unsafe { executor(input, input_stride, output, output_stride, width, height) }
+            return Ok(());
+        }
+
         #[cfg(all(target_arch = "x86_64", feature = "avx"))]
         if std::arch::is_x86_feature_detected!("avx2") {
             let executor = match flip_mode {
@@ -300,6 +408,24 @@ pub(crate) fn transpose_rgba16_chunked(
             return Ok(());
         }
     }
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        if crate::rvv::has_rvv() {
+            use crate::rvv::rvv_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u16, 4, false, false>,
+                    FlopMode::Flop => rvv_transpose::<u16, 4, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u16, 4, false, true>,
+                    FlopMode::Flop => rvv_transpose::<u16, 4, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
+    }
     #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
     {
         use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
@@ -315,3 +441,227 @@ pub(crate) fn transpose_rgba16_chunked(
         )
     }
 }
+
+/// Fused transpose of RGBA16 going through the block kernels of this module.
+pub(crate) struct Rgba16Fused {}
+
+impl FusedTranspose<u16> for Rgba16Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u16],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u16], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u16, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+        {
+            #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+            if std::arch::is_x86_feature_detected!("avx512f") {
+                unsafe {
+                    crate::fused::transpose_fused_avx512f::<u16, O, 8, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx512_8x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u16, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx2_4x4::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u16, O, 2, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSSSE3_2x2::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<4>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+struct TransposeBlockNeon2x2Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u16>
+    for TransposeBlockNeon2x2Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::neon::neon_transpose_u16x4_2x2_swizzled;
+        neon_transpose_u16x4_2x2_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+struct TransposeBlockSSSE3_2x2Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u16>
+    for TransposeBlockSSSE3_2x2Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::sse::ssse_transpose_u16x4_2x2_swizzled;
+        ssse_transpose_u16x4_2x2_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposeBlockAvx2_4x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u16>
+    for TransposeBlockAvx2_4x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        use crate::avx::avx2_transpose_u16x4_4x4_swizzled;
+        unsafe {
+            avx2_transpose_u16x4_4x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride)
+        }
+    }
+}
+
+/// Fused transpose of 4 channel 16-bit pixels whose channels are reordered by `KEY` from
+/// [crate::ChannelOrder] inside the block kernels, so converter only copies.
+pub(crate) struct Rgba16Swizzled<const KEY: usize> {}
+
+impl<const KEY: usize> FusedTranspose<u16> for Rgba16Swizzled<KEY> {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u16],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u16], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u16, O, 2, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon2x2Swizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+        #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u16, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx2_4x4Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u16, O, 2, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSSSE3_2x2Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::transpose_fused::<u16, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockScalarSwizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+    }
+}
@@ -26,7 +26,23 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
-use crate::{FlipMode, FlopMode, TransposeError};
+use crate::fused::FusedTranspose;
+#[cfg(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "unsafe",
+        feature = "sse"
+    ),
+    all(target_arch = "aarch64", feature = "unsafe", feature = "neon")
+))]
+pub(crate) use crate::non_temporal::transpose_executor_streaming;
+use crate::utils::is_valid_image_len;
+use crate::{FlipMode, FlopMode, StoreHint, TransposeError};
+
+/// Chunked transpose entry point whose destination may be streamed past the cache.
+#[allow(dead_code)]
+pub(crate) type StreamingTransposer<V> =
+    unsafe fn(&[V], usize, &mut [V], usize, usize, usize, StoreHint);
 
 #[allow(dead_code)]
 pub(crate) trait TransposeBlock<V> {
@@ -95,6 +111,21 @@ impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u8> for TransposeBlockNe
     }
 }
 
+/// Approximate footprint of a tile of blocks visited before moving on, small enough that
+/// source and destination rows of a tile stay within L2 and TLB reach.
+const TILE_BYTES: usize = 128 * 1024;
+
+#[allow(dead_code)]
+#[inline(always)]
+pub(crate) const fn tile_size<V, const BLOCK_SIZE: usize, const CN: usize>() -> usize {
+    let pixel_size = CN * std::mem::size_of::<V>();
+    let mut tile = BLOCK_SIZE;
+    while (tile * 2) * (tile * 2) * pixel_size <= TILE_BYTES {
+        tile *= 2;
+    }
+    tile
+}
+
 #[allow(dead_code)]
 #[inline(always)]
 pub(crate) fn transpose_executor<
@@ -113,31 +144,61 @@ pub(crate) fn transpose_executor<
     start_y: usize,
     exec: impl TransposeBlock<V>,
 ) -> usize {
-    let mut y = start_y;
+    let mut end_y = start_y;
+    while end_y + BLOCK_SIZE < height {
+        end_y += BLOCK_SIZE;
+    }
+    let mut end_x = 0usize;
+    while end_x + BLOCK_SIZE < width {
+        end_x += BLOCK_SIZE;
+    }
+
+    // Blocks are visited tile by tile rather than band by band, so very wide or very tall
+    // images do not touch a new destination page with every block.
+    let tile = tile_size::<V, BLOCK_SIZE, CN>();
 
     let mut src_buffer = vec![V::default(); BLOCK_SIZE * BLOCK_SIZE * CN];
     let mut dst_buffer = vec![V::default(); BLOCK_SIZE * BLOCK_SIZE * CN];
 
     unsafe {
-        while y + BLOCK_SIZE < height {
-            let input_y = if FLIP { height - BLOCK_SIZE - y } else { y };
+        let mut tile_y = start_y;
+
+        while tile_y < end_y {
+            let tile_end_y = (tile_y + tile).min(end_y);
+
+            let mut tile_x = 0usize;
+
+            while tile_x < end_x {
+                let tile_end_x = (tile_x + tile).min(end_x);
 
-            let src = input.get_unchecked(input_stride * input_y..);
+                let mut y = tile_y;
 
-            let mut x = 0usize;
+                while y < tile_end_y {
+                    let input_y = if FLIP { height - BLOCK_SIZE - y } else { y };
 
-            while x + BLOCK_SIZE < width {
-                let output_x = if FLOP { x } else { width - BLOCK_SIZE - x };
+                    let src = input.get_unchecked(input_stride * input_y..);
 
-                let src = src.get_unchecked(x * CN..);
-                let dst = output.get_unchecked_mut(y * CN + output_stride * output_x..);
+                    let mut x = tile_x;
 
-                exec.transpose_block(src, input_stride, dst, output_stride);
+                    while x < tile_end_x {
+                        let output_x = if FLOP { x } else { width - BLOCK_SIZE - x };
 
-                x += BLOCK_SIZE;
+                        let src = src.get_unchecked(x * CN..);
+                        let dst = output.get_unchecked_mut(y * CN + output_stride * output_x..);
+
+                        exec.transpose_block(src, input_stride, dst, output_stride);
+
+                        x += BLOCK_SIZE;
+                    }
+
+                    y += BLOCK_SIZE;
+                }
+
+                tile_x = tile_end_x;
             }
 
-            if x < width {
+            if end_x < width {
+                let x = end_x;
                 let rem_x = width - x;
                 assert!(
                     rem_x <= BLOCK_SIZE,
@@ -145,53 +206,64 @@ pub(crate) fn transpose_executor<
                 );
 
                 let output_x = if FLOP { x } else { 0 };
-                let src = src.get_unchecked(x * CN..);
-
-                for j in 0..BLOCK_SIZE {
-                    std::ptr::copy_nonoverlapping(
-                        src.get_unchecked(j * input_stride..).as_ptr(),
-                        src_buffer
-                            .get_unchecked_mut(j * (BLOCK_SIZE * CN)..)
-                            .as_mut_ptr(),
-                        rem_x * CN,
-                    );
-                }
 
-                exec.transpose_block(
-                    src_buffer.as_slice(),
-                    BLOCK_SIZE * CN,
-                    dst_buffer.as_mut_slice(),
-                    BLOCK_SIZE * CN,
-                );
+                let mut y = tile_y;
+
+                while y < tile_end_y {
+                    let input_y = if FLIP { height - BLOCK_SIZE - y } else { y };
 
-                let dst = output.get_unchecked_mut(y * CN + output_stride * output_x..);
+                    let src = input.get_unchecked(input_stride * input_y + x * CN..);
 
-                for j in 0..rem_x {
-                    if FLOP {
+                    for j in 0..BLOCK_SIZE {
                         std::ptr::copy_nonoverlapping(
-                            dst_buffer
+                            src.get_unchecked(j * input_stride..).as_ptr(),
+                            src_buffer
                                 .get_unchecked_mut(j * (BLOCK_SIZE * CN)..)
                                 .as_mut_ptr(),
-                            dst.get_unchecked_mut(j * output_stride..).as_mut_ptr(),
-                            BLOCK_SIZE * CN,
-                        );
-                    } else {
-                        std::ptr::copy_nonoverlapping(
-                            dst_buffer
-                                .get_unchecked_mut((BLOCK_SIZE - j - 1) * (BLOCK_SIZE * CN)..)
-                                .as_mut_ptr(),
-                            dst.get_unchecked_mut(j * output_stride..).as_mut_ptr(),
-                            BLOCK_SIZE * CN,
+                            rem_x * CN,
                         );
                     }
+
+                    exec.transpose_block(
+                        src_buffer.as_slice(),
+                        BLOCK_SIZE * CN,
+                        dst_buffer.as_mut_slice(),
+                        BLOCK_SIZE * CN,
+                    );
+
+                    let dst = output.get_unchecked_mut(y * CN + output_stride * output_x..);
+
+                    for j in 0..rem_x {
+                        if FLOP {
+                            std::ptr::copy_nonoverlapping(
+                                dst_buffer
+                                    .get_unchecked_mut(j * (BLOCK_SIZE * CN)..)
+                                    .as_mut_ptr(),
+                                dst.get_unchecked_mut(j * output_stride..).as_mut_ptr(),
+                                BLOCK_SIZE * CN,
+                            );
+                        } else {
+                            std::ptr::copy_nonoverlapping(
+                                dst_buffer
+                                    .get_unchecked_mut(
+                                        (BLOCK_SIZE - rem_x + j) * (BLOCK_SIZE * CN)..,
+                                    )
+                                    .as_mut_ptr(),
+                                dst.get_unchecked_mut(j * output_stride..).as_mut_ptr(),
+                                BLOCK_SIZE * CN,
+                            );
+                        }
+                    }
+
+                    y += BLOCK_SIZE;
                 }
             }
 
-            y += BLOCK_SIZE;
+            tile_y = tile_end_y;
         }
     }
 
-    y
+    end_y
 }
 
 #[cfg(all(
@@ -246,10 +318,10 @@ impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u8> for TransposeBlockAv
     }
 }
 
-#[cfg(all(target_arch = "x86_64", feature = "nightly_avx512"))]
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
 struct TransposeBlockAvx512_16x16<const FLOP: bool, const FLIP: bool> {}
 
-#[cfg(all(target_arch = "x86_64", feature = "nightly_avx512"))]
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
 impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u8>
     for TransposeBlockAvx512_16x16<FLOP, FLIP>
 {
@@ -268,11 +340,24 @@ fn transpose_rgba8_impl_neon<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 4;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u8, 8, 4, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockNeon8x8::<FLOP, FLIP> {},
+        store_hint,
+    );
+
     y = transpose_executor::<u8, 8, 4, FLOP, FLIP>(
         input,
         input_stride,
@@ -319,11 +404,24 @@ unsafe fn transpose_rgba8_impl_ssse3<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 4;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u8, 8, 4, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockSSSE38x8::<FLOP, FLIP> {},
+        store_hint,
+    );
+
     y = transpose_executor::<u8, 8, 4, FLOP, FLIP>(
         input,
         input_stride,
@@ -366,11 +464,24 @@ unsafe fn transpose_rgba8_impl_avx2<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 4;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u8, 8, 4, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockAvx2_8x8::<FLOP, FLIP> {},
+        store_hint,
+    );
+
     y = transpose_executor::<u8, 8, 4, FLOP, FLIP>(
         input,
         input_stride,
@@ -404,7 +515,7 @@ unsafe fn transpose_rgba8_impl_avx2<const FLOP: bool, const FLIP: bool>(
     )
 }
 
-#[cfg(all(target_arch = "x86_64", feature = "nightly_avx512"))]
+#[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
 #[target_feature(enable = "avx512bw")]
 unsafe fn transpose_rgba8_impl_avx512<const FLOP: bool, const FLIP: bool>(
     input: &[u8],
@@ -413,11 +524,24 @@ unsafe fn transpose_rgba8_impl_avx512<const FLOP: bool, const FLIP: bool>(
     output_stride: usize,
     width: usize,
     height: usize,
+    store_hint: StoreHint,
 ) {
     const CN: usize = 4;
 
     let mut y = 0usize;
 
+    y = transpose_executor_streaming::<u8, 16, 4, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockAvx512_16x16::<FLOP, FLIP> {},
+        store_hint,
+    );
+
     y = transpose_executor::<u8, 16, 4, FLOP, FLIP>(
         input,
         input_stride,
@@ -462,6 +586,55 @@ unsafe fn transpose_rgba8_impl_avx512<const FLOP: bool, const FLIP: bool>(
     )
 }
 
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4<const FLOP: bool, const FLIP: bool> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool> TransposeBlock<u8> for TransposeBlockWasm4x4<FLOP, FLIP> {
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_4x4_u32x1;
+        wasm_transpose_4x4_u32x1::<FLOP, FLIP>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+#[target_feature(enable = "simd128")]
+unsafe fn transpose_rgba8_impl_wasm<const FLOP: bool, const FLIP: bool>(
+    input: &[u8],
+    input_stride: usize,
+    output: &mut [u8],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    const CN: usize = 4;
+
+    let mut y = 0usize;
+
+    y = transpose_executor::<u8, 4, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+        TransposeBlockWasm4x4::<FLOP, FLIP> {},
+    );
+
+    transpose_section::<u8, CN, FLOP, FLIP>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        y,
+    )
+}
+
+#[allow(unused_variables)]
 pub(crate) fn transpose_rgba8_chunked(
     input: &[u8],
     input_stride: usize,
@@ -471,11 +644,12 @@ pub(crate) fn transpose_rgba8_chunked(
     height: usize,
     flip_mode: FlipMode,
     flop_mode: FlopMode,
+    store_hint: StoreHint,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * 4, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * width {
+    if !is_valid_image_len(output.len(), output_stride, height * 4, width) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * 4 {
@@ -487,6 +661,22 @@ pub(crate) fn transpose_rgba8_chunked(
 
     #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     {
+        #[cfg(feature = "sve")]
+        if crate::sve::has_wide_sve() {
+            use crate::sve::sve_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u8, 4, false, false>,
+                    FlopMode::Flop => sve_transpose::<u8, 4, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => sve_transpose::<u8, 4, false, true>,
+                    FlopMode::Flop => sve_transpose::<u8, 4, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
         let executor = match flip_mode {
             FlipMode::NoFlip => match flop_mode {
                 FlopMode::NoFlop => transpose_rgba8_impl_neon::<false, false>,
@@ -497,7 +687,15 @@ pub(crate) fn transpose_rgba8_chunked(
                 FlopMode::Flop => transpose_rgba8_impl_neon::<true, true>,
             },
         };
-        executor(input, input_stride, output, output_stride, width, height);
+        executor(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            store_hint,
+        );
         Ok(())
     }
     #[cfg(all(
@@ -506,7 +704,7 @@ pub(crate) fn transpose_rgba8_chunked(
         any(feature = "sse", feature = "avx")
     ))]
     {
-        #[cfg(all(feature = "nightly_avx512", target_arch = "x86_64"))]
+        #[cfg(all(fast_transpose_avx512, target_arch = "x86_64"))]
         if std::arch::is_x86_feature_detected!("avx512bw") {
             let executor = match flip_mode {
                 FlipMode::NoFlip => match flop_mode {
@@ -519,7 +717,17 @@ pub(crate) fn transpose_rgba8_chunked(
                 },
             };
 
-            unsafe { executor(input, input_stride, output, output_stride, width, height) }
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            }
             return Ok(());
         }
 
@@ -536,13 +744,22 @@ pub(crate) fn transpose_rgba8_chunked(
                 },
             };
 
-            unsafe { executor(input, input_stride, output, output_stride, width, height) }
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            }
             return Ok(());
         }
 
         if std::arch::is_x86_feature_detected!("ssse3") {
-            let executor: unsafe fn(&[u8], usize, &mut [u8], usize, usize, usize) = match flip_mode
-            {
+            let executor: StreamingTransposer<u8> = match flip_mode {
                 FlipMode::NoFlip => match flop_mode {
                     FlopMode::NoFlop => transpose_rgba8_impl_ssse3::<false, false>,
                     FlopMode::Flop => transpose_rgba8_impl_ssse3::<true, false>,
@@ -552,11 +769,57 @@ pub(crate) fn transpose_rgba8_chunked(
                     FlopMode::Flop => transpose_rgba8_impl_ssse3::<true, true>,
                 },
             };
-            unsafe { executor(input, input_stride, output, output_stride, width, height) }
+            unsafe {
+                executor(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    store_hint,
+                )
+            }
             return Ok(());
         }
     }
-    #[cfg(not(all(target_arch = "aarch64", feature = "unsafe", feature = "neon")))]
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    {
+        let executor: unsafe fn(&[u8], usize, &mut [u8], usize, usize, usize) = match flip_mode {
+            FlipMode::NoFlip => match flop_mode {
+                FlopMode::NoFlop => transpose_rgba8_impl_wasm::<false, false>,
+                FlopMode::Flop => transpose_rgba8_impl_wasm::<true, false>,
+            },
+            FlipMode::Flip => match flop_mode {
+                FlopMode::NoFlop => transpose_rgba8_impl_wasm::<false, true>,
+                FlopMode::Flop => transpose_rgba8_impl_wasm::<true, true>,
+            },
+        };
+        unsafe { executor(input, input_stride, output, output_stride, width, height) }
+        Ok(())
+    }
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    {
+        if crate::rvv::has_rvv() {
+            use crate::rvv::rvv_transpose;
+            let executor = match flip_mode {
+                FlipMode::NoFlip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u8, 4, false, false>,
+                    FlopMode::Flop => rvv_transpose::<u8, 4, true, false>,
+                },
+                FlipMode::Flip => match flop_mode {
+                    FlopMode::NoFlop => rvv_transpose::<u8, 4, false, true>,
+                    FlopMode::Flop => rvv_transpose::<u8, 4, true, true>,
+                },
+            };
+            executor(input, input_stride, output, output_stride, width, height);
+            return Ok(());
+        }
+    }
+    #[cfg(not(any(
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+    )))]
     {
         use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
         transpose_arbitrary_grouped::<u8, 4>(
@@ -571,3 +834,425 @@ pub(crate) fn transpose_rgba8_chunked(
         )
     }
 }
+
+/// Fused transpose of RGBA8 going through the block kernels of this module.
+pub(crate) struct Rgba8Fused {}
+
+impl FusedTranspose<u8> for Rgba8Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u8],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u8], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u8, O, 8, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon8x8::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<u8, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4::<FLOP, FLIP> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+            if std::arch::is_x86_feature_detected!("avx512bw") {
+                unsafe {
+                    crate::fused::transpose_fused_avx512::<u8, O, 16, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx512_16x16::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u8, O, 8, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx2_8x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u8, O, 8, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSSSE38x8::<FLOP, FLIP> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<4>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
+
+/// Runs a block kernel of this module on pixels of two 16-bit samples, which are moved as
+/// the same 4 byte pixels.
+#[allow(dead_code)]
+struct TransposeBlockCbCr16<B>(B);
+
+impl<B: TransposeBlock<u8>> TransposeBlock<u16> for TransposeBlockCbCr16<B> {
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u16], src_stride: usize, dst: &mut [u16], dst_stride: usize) {
+        self.0.transpose_block(
+            bytemuck::cast_slice(src),
+            src_stride * std::mem::size_of::<u16>(),
+            bytemuck::cast_slice_mut(dst),
+            dst_stride * std::mem::size_of::<u16>(),
+        );
+    }
+}
+
+/// Fused transpose of two channel 16-bit samples going through the RGBA8 block kernels.
+pub(crate) struct CbCr16Fused {}
+
+impl FusedTranspose<u16> for CbCr16Fused {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u16],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u16], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u16, O, 8, 2, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockCbCr16(TransposeBlockNeon8x8::<FLOP, FLIP> {}),
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<u16, O, 4, 2, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockCbCr16(TransposeBlockWasm4x4::<FLOP, FLIP> {}),
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", fast_transpose_avx512))]
+            if std::arch::is_x86_feature_detected!("avx512bw") {
+                unsafe {
+                    crate::fused::transpose_fused_avx512::<u16, O, 16, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockCbCr16(TransposeBlockAvx512_16x16::<FLOP, FLIP> {}),
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u16, O, 8, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockCbCr16(TransposeBlockAvx2_8x8::<FLOP, FLIP> {}),
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u16, O, 8, 2, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockCbCr16(TransposeBlockSSSE38x8::<FLOP, FLIP> {}),
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::ScalarFused::<2>::transpose::<O, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                converter,
+            );
+        }
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+struct TransposeBlockSSSE34x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u8>
+    for TransposeBlockSSSE34x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::sse::sse_transpose_4x4_u32x1_swizzled;
+        sse_transpose_4x4_u32x1_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+struct TransposeBlockNeon4x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u8>
+    for TransposeBlockNeon4x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::neon::neon_transpose_4x4_u8x4_swizzled;
+        neon_transpose_4x4_u8x4_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+struct TransposeBlockWasm4x4Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u8>
+    for TransposeBlockWasm4x4Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::wasm32::wasm_transpose_4x4_u32x1_swizzled;
+        wasm_transpose_4x4_u32x1_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride);
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+struct TransposeBlockAvx2_8x8Swizzled<const FLOP: bool, const FLIP: bool, const KEY: usize> {}
+
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<u8>
+    for TransposeBlockAvx2_8x8Swizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[u8], src_stride: usize, dst: &mut [u8], dst_stride: usize) {
+        use crate::avx::avx_transpose_8x8_u32_swizzled;
+        unsafe {
+            avx_transpose_8x8_u32_swizzled::<FLOP, FLIP, KEY>(src, src_stride, dst, dst_stride)
+        }
+    }
+}
+
+/// Plain 4x4 block transpose of 4 channel pixels reordering channels by `KEY` from
+/// [crate::ChannelOrder], for targets without a swizzling kernel.
+#[allow(dead_code)]
+pub(crate) struct TransposeBlockScalarSwizzled<const FLOP: bool, const FLIP: bool, const KEY: usize>
+{}
+
+impl<V: Copy, const FLOP: bool, const FLIP: bool, const KEY: usize> TransposeBlock<V>
+    for TransposeBlockScalarSwizzled<FLOP, FLIP, KEY>
+{
+    #[inline(always)]
+    fn transpose_block(&self, src: &[V], src_stride: usize, dst: &mut [V], dst_stride: usize) {
+        const BLOCK_SIZE: usize = 4;
+        for y in 0..BLOCK_SIZE {
+            let src_y = if FLIP { BLOCK_SIZE - 1 - y } else { y };
+            for x in 0..BLOCK_SIZE {
+                let dst_x = if FLOP { x } else { BLOCK_SIZE - 1 - x };
+                let src = &src[src_y * src_stride + x * 4..][..4];
+                let dst = &mut dst[dst_x * dst_stride + y * 4..][..4];
+                for (c, dst) in dst.iter_mut().enumerate() {
+                    *dst = src[(KEY >> (c * 4)) & 0xF];
+                }
+            }
+        }
+    }
+}
+
+/// Fused transpose of 4 channel 8-bit pixels whose channels are reordered by `KEY` from
+/// [crate::ChannelOrder] inside the block kernels, so converter only copies.
+pub(crate) struct Rgba8Swizzled<const KEY: usize> {}
+
+impl<const KEY: usize> FusedTranspose<u8> for Rgba8Swizzled<KEY> {
+    fn transpose<O, const M: usize, const FLOP: bool, const FLIP: bool>(
+        input: &[u8],
+        input_stride: usize,
+        output: &mut [O],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        converter: &impl Fn(&[u8], &mut [O]),
+    ) {
+        #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+        {
+            crate::fused::transpose_fused::<u8, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockNeon4x4Swizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+        unsafe {
+            crate::fused::transpose_fused_wasm::<u8, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockWasm4x4Swizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+        #[cfg(not(any(
+            all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+            all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd")
+        )))]
+        {
+            #[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+            if std::arch::is_x86_feature_detected!("avx2") {
+                unsafe {
+                    crate::fused::transpose_fused_avx2::<u8, O, 8, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockAvx2_8x8Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                feature = "unsafe",
+                feature = "sse"
+            ))]
+            if std::arch::is_x86_feature_detected!("ssse3") {
+                unsafe {
+                    crate::fused::transpose_fused_ssse3::<u8, O, 4, 4, M, FLOP, FLIP>(
+                        input,
+                        input_stride,
+                        output,
+                        output_stride,
+                        width,
+                        height,
+                        TransposeBlockSSSE34x4Swizzled::<FLOP, FLIP, KEY> {},
+                        converter,
+                    );
+                }
+                return;
+            }
+            crate::fused::transpose_fused::<u8, O, 4, 4, M, FLOP, FLIP>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                TransposeBlockScalarSwizzled::<FLOP, FLIP, KEY> {},
+                converter,
+            );
+        }
+    }
+}
@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::utils::is_valid_image_len;
 use crate::TransposeError;
 use bytemuck::{AnyBitPattern, NoUninit, Pod};
 
@@ -43,9 +44,9 @@ trait Rotator<V: Copy> {
 macro_rules! rotate_flatten {
     ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr) => {
         for (dst, src) in $output
-            .chunks_exact_mut($output_stride)
+            .chunks_mut($output_stride)
             .rev()
-            .zip($input.chunks_exact($input_stride))
+            .zip($input.chunks($input_stride))
         {
             let dst = &mut dst[0..$width];
             let src = &src[0..$width];
@@ -59,9 +60,9 @@ macro_rules! rotate_flatten {
 macro_rules! rotate_grouped_copy {
     ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr, $cn: expr) => {
         for (dst, src) in $output
-            .chunks_exact_mut($output_stride)
+            .chunks_mut($output_stride)
             .rev()
-            .zip($input.chunks_exact($input_stride))
+            .zip($input.chunks($input_stride))
         {
             let dst = &mut dst[0..$width * $cn];
             let src = &src[0..$width * $cn];
@@ -97,116 +98,343 @@ where
     }
 }
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[inline(always)]
+#[allow(dead_code)]
+fn rotate_rows<V: Pod>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    reverse: impl Fn(&[u8], &mut [u8]),
+) {
+    if input_stride == width && output_stride == width && input.len() == output.len() {
+        // Packed image rotated by 180 is the whole buffer reversed, kernels then stream
+        // input forward and write output backwards in cache-line sized blocks.
+        reverse(
+            bytemuck::cast_slice(input),
+            bytemuck::cast_slice_mut(output),
+        );
+        return;
+    }
+    for (dst, src) in output
+        .chunks_mut(output_stride)
+        .rev()
+        .zip(input.chunks(input_stride))
+    {
+        reverse(
+            bytemuck::cast_slice(&src[0..width]),
+            bytemuck::cast_slice_mut(&mut dst[0..width]),
+        );
+    }
+}
+
+/// Selects row reversal kernel by pixel size in bytes
+/// and falls back to plain rotation for pixel sizes that have no kernel.
+#[allow(unused_macros)]
+macro_rules! rotate_pixels_dispatch {
+    ($input:expr, $input_stride:expr,$output:expr, $output_stride:expr, $width:expr, $cn: expr, $kernel: ident) => {
+        let row_width = $width * $cn;
+        match std::mem::size_of::<[V; $cn]>() {
+            1 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<1>,
+            ),
+            2 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<2>,
+            ),
+            3 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<3>,
+            ),
+            4 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<4>,
+            ),
+            6 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<6>,
+            ),
+            8 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<8>,
+            ),
+            12 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<12>,
+            ),
+            16 => rotate_rows(
+                $input,
+                $input_stride,
+                $output,
+                $output_stride,
+                row_width,
+                $kernel::<16>,
+            ),
+            _ => CommonGroupedFlipper::<V, $cn> {
+                _phantom: std::marker::PhantomData,
+            }
+            .rotate($input, $input_stride, $output, $output_stride, $width),
+        }
+    };
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
 #[derive(Debug, Copy, Clone, Default)]
 struct SSSE3GroupedRotator<V: Copy, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-macro_rules! define_rotator_grouped_x86 {
-    ($flipper_type:ident, $feature: literal) => {
-        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
-        impl<V: Copy + Pod, const N: usize> $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            #[target_feature(enable = $feature)]
-            unsafe fn rotate_impl(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                rotate_grouped_copy!(input, input_stride, output, output_stride, width, N);
-            }
-        }
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<V: Copy + Pod, const N: usize> SSSE3GroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "ssse3")]
+    unsafe fn rotate_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::sse::sse_reverse_pixels;
+        rotate_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            sse_reverse_pixels
+        );
+    }
+}
 
-        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
-        impl<V: Copy + Pod, const N: usize> Rotator<V> for $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            fn rotate(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                unsafe { self.rotate_impl(input, input_stride, output, output_stride, width) }
-            }
-        }
-    };
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    feature = "unsafe",
+    feature = "sse"
+))]
+impl<V: Copy + Pod, const N: usize> Rotator<V> for SSSE3GroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    fn rotate(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.rotate_impl(input, input_stride, output, output_stride, width) }
+    }
 }
 
-macro_rules! define_rotator_grouped_aarch64 {
-    ($flipper_type: ident, $feature: literal) => {
-        #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
-        impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            #[target_feature(enable = $feature)]
-            unsafe fn rotate_impl(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                rotate_grouped_copy!(input, input_stride, output, output_stride, width, N);
-            }
-        }
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+#[derive(Debug, Copy, Clone, Default)]
+struct Avx2GroupedRotator<V: Copy, const N: usize> {
+    _phantom: std::marker::PhantomData<V>,
+}
 
-        #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
-        impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Rotator<V>
-            for $flipper_type<V, N>
-        where
-            [V; N]: Pod,
-        {
-            fn rotate(
-                &self,
-                input: &[V],
-                input_stride: usize,
-                output: &mut [V],
-                output_stride: usize,
-                width: usize,
-            ) {
-                unsafe { self.rotate_impl(input, input_stride, output, output_stride, width) }
-            }
-        }
-    };
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<V: Copy + Pod, const N: usize> Avx2GroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "avx2")]
+    unsafe fn rotate_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::avx::avx2_reverse_pixels;
+        rotate_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            avx2_reverse_pixels
+        );
+    }
 }
 
-define_rotator_grouped_x86!(SSSE3GroupedRotator, "ssse3");
+#[cfg(all(target_arch = "x86_64", feature = "unsafe", feature = "avx"))]
+impl<V: Copy + Pod, const N: usize> Rotator<V> for Avx2GroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    fn rotate(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.rotate_impl(input, input_stride, output, output_stride, width) }
+    }
+}
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct Sse41GroupedRotator<V: Copy, const N: usize> {
+struct NeonGroupedRotator<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_rotator_grouped_x86!(Sse41GroupedRotator, "sse4.1");
+#[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
+impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Rotator<V>
+    for NeonGroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    fn rotate(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::neon::neon_reverse_pixels;
+        rotate_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            neon_reverse_pixels
+        );
+    }
+}
 
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct Avx2GroupedRotator<V: Copy, const N: usize> {
+struct WasmGroupedRotator<V: Copy, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_rotator_grouped_x86!(Avx2GroupedRotator, "avx2");
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<V: Copy + Pod, const N: usize> WasmGroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    #[target_feature(enable = "simd128")]
+    unsafe fn rotate_impl(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::wasm32::wasm_reverse_pixels;
+        rotate_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            wasm_reverse_pixels
+        );
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+impl<V: Copy + Pod, const N: usize> Rotator<V> for WasmGroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    fn rotate(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        unsafe { self.rotate_impl(input, input_stride, output, output_stride, width) }
+    }
+}
 
-#[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
 #[derive(Debug, Copy, Clone, Default)]
-struct SveGroupedRotator<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
+struct RvvGroupedRotator<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
     _phantom: std::marker::PhantomData<V>,
 }
 
-define_rotator_grouped_aarch64!(SveGroupedRotator, "sve2");
+#[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+impl<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> Rotator<V>
+    for RvvGroupedRotator<V, N>
+where
+    [V; N]: Pod,
+{
+    fn rotate(
+        &self,
+        input: &[V],
+        input_stride: usize,
+        output: &mut [V],
+        output_stride: usize,
+        width: usize,
+    ) {
+        use crate::rvv::rvv_reverse_pixels;
+        rotate_pixels_dispatch!(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            N,
+            rvv_reverse_pixels
+        );
+    }
+}
 
 #[derive(Debug, Copy, Clone, Default)]
 struct RotatorGroupedFactory<V: Copy + Pod + NoUninit + AnyBitPattern, const N: usize> {
@@ -221,29 +449,40 @@ where
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"))]
     fn make_rotator(&self) -> Box<dyn Rotator<V>> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx"))]
         if std::arch::is_x86_feature_detected!("avx2") {
             return Box::new(Avx2GroupedRotator::<V, N>::default());
         }
-        if std::arch::is_x86_feature_detected!("sse4.1") {
-            return Box::new(Sse41GroupedRotator::<V, N>::default());
-        }
+        #[cfg(feature = "sse")]
         if std::arch::is_x86_feature_detected!("ssse3") {
             return Box::new(SSSE3GroupedRotator::<V, N>::default());
         }
         Box::new(CommonGroupedFlipper::<V, N>::default())
     }
 
-    #[cfg(all(target_arch = "aarch64", feature = "unsafe"))]
+    #[cfg(all(target_arch = "aarch64", feature = "unsafe", feature = "neon"))]
     fn make_rotator(&self) -> Box<dyn Rotator<V>> {
-        if std::arch::is_aarch64_feature_detected!("sve2") {
-            return Box::new(SveGroupedRotator::<V, N>::default());
+        Box::new(NeonGroupedRotator::<V, N>::default())
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"))]
+    fn make_rotator(&self) -> Box<dyn Rotator<V>> {
+        Box::new(WasmGroupedRotator::<V, N>::default())
+    }
+
+    #[cfg(all(target_arch = "riscv64", feature = "unsafe", feature = "rvv"))]
+    fn make_rotator(&self) -> Box<dyn Rotator<V>> {
+        if crate::rvv::has_rvv() {
+            return Box::new(RvvGroupedRotator::<V, N>::default());
         }
         Box::new(CommonGroupedFlipper::<V, N>::default())
     }
 
     #[cfg(not(any(
         all(any(target_arch = "x86", target_arch = "x86_64"), feature = "unsafe"),
-        all(target_arch = "aarch64", feature = "unsafe")
+        all(target_arch = "aarch64", feature = "unsafe", feature = "neon"),
+        all(target_arch = "wasm32", feature = "unsafe", feature = "wasm_simd"),
+        all(target_arch = "riscv64", feature = "unsafe", feature = "rvv")
     )))]
     fn make_rotator(&self) -> Box<dyn Rotator<V>> {
         Box::new(CommonGroupedFlipper::<V, N>::default())
@@ -425,13 +664,10 @@ pub fn rotate180_arbitrary<V: Copy + Default + 'static>(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    if input.len() != output.len() {
-        return Err(TransposeError::MismatchDimensions);
-    }
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width {
@@ -472,10 +708,10 @@ fn rotate180_arbitrary_image<V: Copy + Default + 'static + Pod, const N: usize>(
 where
     [V; N]: Pod,
 {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * height {
+    if !is_valid_image_len(output.len(), output_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * N {
@@ -513,7 +749,7 @@ pub fn rotate180_plane(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    rotate180_arbitrary(input, input_stride, output, output_stride, width, height)
+    rotate180_arbitrary_image::<u8, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha rotating by 180
@@ -609,7 +845,7 @@ pub fn rotate180_plane16(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    rotate180_arbitrary(input, input_stride, output, output_stride, width, height)
+    rotate180_arbitrary_image::<u16, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha image rotating by 180
@@ -705,7 +941,7 @@ pub fn rotate180_plane_f32(
     width: usize,
     height: usize,
 ) -> Result<(), TransposeError> {
-    rotate180_arbitrary(input, input_stride, output, output_stride, width, height)
+    rotate180_arbitrary_image::<f32, 1>(input, input_stride, output, output_stride, width, height)
 }
 
 /// Performs plane with alpha image rotating by 180
@@ -0,0 +1,59 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::sync::OnceLock;
+
+const AT_NULL: u64 = 0;
+const AT_HWCAP: u64 = 16;
+const HWCAP_ISA_V: u64 = 1 << (b'V' - b'A');
+
+/// Reads `AT_HWCAP` from the auxiliary vector.
+///
+/// Kernel reports `V` only when the hart has RVV 1.0 and vector state is
+/// enabled for the process, QEMU user-mode emulates this file as well.
+fn read_hwcap() -> Option<u64> {
+    let auxv = std::fs::read("/proc/self/auxv").ok()?;
+    for entry in auxv.chunks_exact(16) {
+        let key = u64::from_ne_bytes(entry[0..8].try_into().ok()?);
+        let value = u64::from_ne_bytes(entry[8..16].try_into().ok()?);
+        if key == AT_NULL {
+            break;
+        }
+        if key == AT_HWCAP {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Checks at runtime if RISC-V Vector extension is available.
+pub(crate) fn has_rvv() -> bool {
+    static HAS_RVV: OnceLock<bool> = OnceLock::new();
+    *HAS_RVV.get_or_init(|| read_hwcap().is_some_and(|hwcap| hwcap & HWCAP_ISA_V != 0))
+}
@@ -0,0 +1,146 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::asm;
+
+/// Defines strip-mined loop that loads `count` pixels contiguously
+/// and stores them with arbitrary, possibly negative, byte stride.
+///
+/// Loop is vector length agnostic, `vsetvli` picks how many pixels
+/// fit into the register group on each iteration.
+macro_rules! define_scatter {
+    ($name: ident, $vtype: literal, $load: literal, $store: literal) => {
+        #[inline(always)]
+        unsafe fn $name(src: *const u8, dst: *mut u8, dst_stride: isize, count: usize, px: usize) {
+            asm!(
+                ".option push",
+                ".option arch, +v",
+                "2:",
+                concat!("vsetvli {vl}, {count}, ", $vtype, ", ta, ma"),
+                concat!($load, " v8, ({src})"),
+                concat!($store, " v8, ({dst}), {stride}"),
+                "sub {count}, {count}, {vl}",
+                "mul {tmp}, {vl}, {px}",
+                "add {src}, {src}, {tmp}",
+                "mul {tmp}, {vl}, {stride}",
+                "add {dst}, {dst}, {tmp}",
+                "bnez {count}, 2b",
+                ".option pop",
+                vl = out(reg) _,
+                tmp = out(reg) _,
+                count = inout(reg) count => _,
+                src = inout(reg) src => _,
+                dst = inout(reg) dst => _,
+                stride = in(reg) dst_stride,
+                px = in(reg) px,
+                out("v8") _,
+                out("v9") _,
+                out("v10") _,
+                out("v11") _,
+                out("v12") _,
+                out("v13") _,
+                out("v14") _,
+                out("v15") _,
+                options(nostack),
+            );
+        }
+    };
+}
+
+define_scatter!(scatter_e8, "e8, m8", "vle8.v", "vsse8.v");
+define_scatter!(scatter_e16, "e16, m8", "vle16.v", "vsse16.v");
+define_scatter!(scatter_e32, "e32, m8", "vle32.v", "vsse32.v");
+define_scatter!(scatter_e64, "e64, m8", "vle64.v", "vsse64.v");
+define_scatter!(scatter_e8x2, "e8, m4", "vlseg2e8.v", "vssseg2e8.v");
+define_scatter!(scatter_e8x3, "e8, m2", "vlseg3e8.v", "vssseg3e8.v");
+define_scatter!(scatter_e8x4, "e8, m2", "vlseg4e8.v", "vssseg4e8.v");
+define_scatter!(scatter_e8x6, "e8, m1", "vlseg6e8.v", "vssseg6e8.v");
+define_scatter!(scatter_e8x8, "e8, m1", "vlseg8e8.v", "vssseg8e8.v");
+define_scatter!(scatter_e16x3, "e16, m2", "vlseg3e16.v", "vssseg3e16.v");
+define_scatter!(scatter_e16x4, "e16, m2", "vlseg4e16.v", "vssseg4e16.v");
+define_scatter!(scatter_e16x6, "e16, m1", "vlseg6e16.v", "vssseg6e16.v");
+define_scatter!(scatter_e16x8, "e16, m1", "vlseg8e16.v", "vssseg8e16.v");
+define_scatter!(scatter_e32x2, "e32, m4", "vlseg2e32.v", "vssseg2e32.v");
+define_scatter!(scatter_e32x3, "e32, m2", "vlseg3e32.v", "vssseg3e32.v");
+define_scatter!(scatter_e32x4, "e32, m2", "vlseg4e32.v", "vssseg4e32.v");
+define_scatter!(scatter_e64x2, "e64, m4", "vlseg2e64.v", "vssseg2e64.v");
+
+/// Copies `count` pixels of `PX` bytes from `src` to `dst`, where each next
+/// pixel in `dst` is placed `dst_stride` bytes after the previous one.
+///
+/// Vector element accesses must be naturally aligned, otherwise they may trap
+/// or be emulated by the kernel. Pixel is therefore moved as the widest elements
+/// that both pointers and the stride are aligned to, falling back to byte
+/// segments, or to a scalar copy when a pixel needs more than eight segments.
+///
+/// # Safety
+///
+/// `src` must be valid for reading `count * PX` bytes and every
+/// `dst + i * dst_stride` for `i < count` must be valid for writing `PX` bytes.
+#[inline(always)]
+pub(crate) unsafe fn rvv_scatter_pixels<const PX: usize>(
+    src: *const u8,
+    dst: *mut u8,
+    dst_stride: isize,
+    count: usize,
+) {
+    if count == 0 {
+        return;
+    }
+    let bits = src as usize | dst as usize | dst_stride as usize;
+    let align = 1usize << (bits | PX).trailing_zeros().min(3);
+    match (PX, align) {
+        (1, _) => scatter_e8(src, dst, dst_stride, count, PX),
+        (2, 1) => scatter_e8x2(src, dst, dst_stride, count, PX),
+        (2, _) => scatter_e16(src, dst, dst_stride, count, PX),
+        (3, _) => scatter_e8x3(src, dst, dst_stride, count, PX),
+        (4, 4) => scatter_e32(src, dst, dst_stride, count, PX),
+        (4, _) => scatter_e8x4(src, dst, dst_stride, count, PX),
+        (6, 1) => scatter_e8x6(src, dst, dst_stride, count, PX),
+        (6, _) => scatter_e16x3(src, dst, dst_stride, count, PX),
+        (8, 8) => scatter_e64(src, dst, dst_stride, count, PX),
+        (8, 4) => scatter_e32x2(src, dst, dst_stride, count, PX),
+        (8, 2) => scatter_e16x4(src, dst, dst_stride, count, PX),
+        (8, _) => scatter_e8x8(src, dst, dst_stride, count, PX),
+        (12, 4) => scatter_e32x3(src, dst, dst_stride, count, PX),
+        (12, 2) => scatter_e16x6(src, dst, dst_stride, count, PX),
+        (16, 8) => scatter_e64x2(src, dst, dst_stride, count, PX),
+        (16, 4) => scatter_e32x4(src, dst, dst_stride, count, PX),
+        (16, 2) => scatter_e16x8(src, dst, dst_stride, count, PX),
+        _ => {
+            for i in 0..count {
+                std::ptr::copy_nonoverlapping(
+                    src.add(i * PX),
+                    dst.offset(i as isize * dst_stride),
+                    PX,
+                );
+            }
+        }
+    }
+}
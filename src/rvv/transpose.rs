@@ -0,0 +1,103 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::rvv::scatter::rvv_scatter_pixels;
+
+/// Number of columns processed per pass, keeps destination rows
+/// touched by strided stores resident in cache while walking down the image.
+const TILE_WIDTH: usize = 64;
+
+#[inline(always)]
+fn rvv_transpose_px<
+    V: Copy,
+    const CN: usize,
+    const PX: usize,
+    const FLOP: bool,
+    const FLIP: bool,
+>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    let row_step = (output_stride * std::mem::size_of::<V>()) as isize;
+    let dst_stride = if FLOP { row_step } else { -row_step };
+
+    let mut x = 0usize;
+    while x < width {
+        let tile = TILE_WIDTH.min(width - x);
+        let dst_row = if FLOP { x } else { width - 1 - x };
+        for (y, src_row) in input.chunks(input_stride).take(height).enumerate() {
+            let dst_y = if FLIP { height - 1 - y } else { y };
+            unsafe {
+                rvv_scatter_pixels::<PX>(
+                    src_row.get_unchecked(x * CN..).as_ptr() as *const u8,
+                    output
+                        .get_unchecked_mut(dst_row * output_stride + dst_y * CN..)
+                        .as_mut_ptr() as *mut u8,
+                    dst_stride,
+                    tile,
+                );
+            }
+        }
+        x += tile;
+    }
+}
+
+/// Transposes image by scattering each source row into destination column
+/// with strided stores, works for any vector register length.
+///
+/// Dimensions must be validated by the caller.
+pub(crate) fn rvv_transpose<V: Copy, const CN: usize, const FLOP: bool, const FLIP: bool>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    macro_rules! dispatch {
+        ($($px: literal),*) => {
+            match std::mem::size_of::<V>() * CN {
+                $($px => rvv_transpose_px::<V, CN, $px, FLOP, FLIP>(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                ),)*
+                _ => unreachable!("Pixel size is not supported by RVV transpose"),
+            }
+        };
+    }
+    dispatch!(1, 2, 3, 4, 6, 8, 12, 16);
+}
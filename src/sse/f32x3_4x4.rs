@@ -0,0 +1,101 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn load_rgb_f32_row(src: &[f32]) -> (__m128i, __m128i, __m128i, __m128i) {
+    let a = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+    let b = _mm_loadu_si128(src.get_unchecked(4..).as_ptr() as *const __m128i);
+    let c = _mm_loadu_si128(src.get_unchecked(8..).as_ptr() as *const __m128i);
+    // Spreads 4 RGB pixels over 4 registers, each pixel in the low 12 bytes
+    (
+        a,
+        _mm_alignr_epi8::<12>(b, a),
+        _mm_alignr_epi8::<8>(c, b),
+        _mm_srli_si128::<4>(c),
+    )
+}
+
+#[inline(always)]
+unsafe fn store_rgb_f32_row(dst: &mut [f32], v: (__m128i, __m128i, __m128i, __m128i)) {
+    let o0 = _mm_alignr_epi8::<4>(v.1, _mm_slli_si128::<4>(v.0));
+    let o1 = _mm_alignr_epi8::<8>(v.2, _mm_slli_si128::<4>(v.1));
+    let o2 = _mm_alignr_epi8::<12>(v.3, _mm_slli_si128::<4>(v.2));
+    _mm_storeu_si128(dst.as_mut_ptr() as *mut __m128i, o0);
+    _mm_storeu_si128(dst.get_unchecked_mut(4..).as_mut_ptr() as *mut __m128i, o1);
+    _mm_storeu_si128(dst.get_unchecked_mut(8..).as_mut_ptr() as *mut __m128i, o2);
+}
+
+#[inline(always)]
+pub(crate) fn ssse_transpose_f32x3_4x4<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = load_rgb_f32_row(src.get_unchecked(0..));
+        let row1 = load_rgb_f32_row(src.get_unchecked(src_stride..));
+        let row2 = load_rgb_f32_row(src.get_unchecked(2 * src_stride..));
+        let row3 = load_rgb_f32_row(src.get_unchecked(3 * src_stride..));
+
+        // Each pixel already sits in its own register, so transposition is only a reordering
+        let (v0, v1, v2, v3) = if FLIP {
+            (
+                (row3.0, row2.0, row1.0, row0.0),
+                (row3.1, row2.1, row1.1, row0.1),
+                (row3.2, row2.2, row1.2, row0.2),
+                (row3.3, row2.3, row1.3, row0.3),
+            )
+        } else {
+            (
+                (row0.0, row1.0, row2.0, row3.0),
+                (row0.1, row1.1, row2.1, row3.1),
+                (row0.2, row1.2, row2.2, row3.2),
+                (row0.3, row1.3, row2.3, row3.3),
+            )
+        };
+
+        if FLOP {
+            store_rgb_f32_row(dst.get_unchecked_mut(0..), v0);
+            store_rgb_f32_row(dst.get_unchecked_mut(dst_stride..), v1);
+            store_rgb_f32_row(dst.get_unchecked_mut(2 * dst_stride..), v2);
+            store_rgb_f32_row(dst.get_unchecked_mut(3 * dst_stride..), v3);
+        } else {
+            store_rgb_f32_row(dst.get_unchecked_mut(3 * dst_stride..), v0);
+            store_rgb_f32_row(dst.get_unchecked_mut(2 * dst_stride..), v1);
+            store_rgb_f32_row(dst.get_unchecked_mut(dst_stride..), v2);
+            store_rgb_f32_row(dst.get_unchecked_mut(0..), v3);
+        }
+    }
+}
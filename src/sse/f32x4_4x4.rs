@@ -0,0 +1,93 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::utils::swizzle_mask_samples;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Pixels of four floats fill a register each, so the block is transposed by where every
+/// row is stored, `shuffle` is applied to every pixel on the way.
+#[inline(always)]
+unsafe fn sse_transpose_f32x4_4x4_impl<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+    shuffle: impl Fn(__m128) -> __m128,
+) {
+    for y in 0..4 {
+        let src_y = if FLIP { 3 - y } else { y };
+        let row = src.get_unchecked(src_y * src_stride..);
+        for x in 0..4 {
+            let v = _mm_loadu_ps(row.get_unchecked(x * 4..).as_ptr());
+            let dst_y = if FLOP { x } else { 3 - x };
+            _mm_storeu_ps(
+                dst.get_unchecked_mut(dst_y * dst_stride + y * 4..)
+                    .as_mut_ptr(),
+                shuffle(v),
+            );
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn sse_transpose_f32x4_4x4<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        sse_transpose_f32x4_4x4_impl::<FLOP, FLIP>(src, src_stride, dst, dst_stride, |v| v);
+    }
+}
+
+/// Same as [sse_transpose_f32x4_4x4] but also reorders channels of every pixel by `KEY`
+/// from [crate::ChannelOrder] with one byte shuffle per pixel.
+#[inline(always)]
+pub(crate) fn sse_transpose_f32x4_4x4_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        let mask = const { swizzle_mask_samples(KEY, 4) };
+        let mask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        sse_transpose_f32x4_4x4_impl::<FLOP, FLIP>(src, src_stride, dst, dst_stride, |v| {
+            _mm_castsi128_ps(_mm_shuffle_epi8(_mm_castps_si128(v), mask))
+        });
+    }
+}
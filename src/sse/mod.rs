@@ -27,12 +27,18 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 mod f32x2_2x2;
+mod f32x3_4x4;
+mod f32x4_4x4;
+mod premultiply;
+mod reverse;
 mod u16_4x4;
 mod u16_8x8;
+mod u16x3_2x2;
 mod u16x4_2x2;
 mod u8_8x8;
 mod u8x2_4x4;
 mod u8x2_8x8;
+mod u8x3_4x4;
 mod utils;
 mod x4_f32;
 mod x4_u32;
@@ -40,14 +46,22 @@ mod x8_f32;
 mod x8_u32;
 
 pub(crate) use f32x2_2x2::ssse_transpose_f32x2_2x2;
+pub(crate) use f32x3_4x4::ssse_transpose_f32x3_4x4;
+pub(crate) use f32x4_4x4::{sse_transpose_f32x4_4x4, sse_transpose_f32x4_4x4_swizzled};
+pub(crate) use premultiply::{sse_premultiply_u8, sse_unpremultiply_u8};
+pub(crate) use reverse::sse_reverse_pixels;
 pub(crate) use u16_4x4::sse_transpose_4x4_u16;
 pub(crate) use u16_8x8::sse_transpose_8x8_u16;
-pub(crate) use u16x4_2x2::ssse_transpose_u16x4_2x2;
+pub(crate) use u16x3_2x2::ssse_transpose_u16x3_2x2;
+pub(crate) use u16x4_2x2::{ssse_transpose_u16x4_2x2, ssse_transpose_u16x4_2x2_swizzled};
 pub(crate) use u8_8x8::sse_transpose_u8_8x8;
 pub(crate) use u8x2_4x4::sse_transpose_u8x2_4x4;
 pub(crate) use u8x2_8x8::sse_transpose_u8x2_8x8;
+pub(crate) use u8x3_4x4::sse_transpose_u8x3_4x4;
 pub(crate) use utils::_mm_shuffle;
+#[cfg(all(target_arch = "x86_64", feature = "avx"))]
+pub(crate) use utils::{_mm_loadu_si192_x2, _mm_storeu_si192_x2};
 pub(crate) use x4_f32::sse_transpose_4x4_f32;
-pub(crate) use x4_u32::sse_transpose_4x4_u32x1;
+pub(crate) use x4_u32::{sse_transpose_4x4_u32x1, sse_transpose_4x4_u32x1_swizzled};
 pub(crate) use x8_f32::sse_transpose_8x8_f32;
 pub(crate) use x8_u32::sse_transpose_8x8_u32x1;
@@ -0,0 +1,123 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Premultiplies color of RGBA8 pixels in runs of 4, rounded division by 255 is the
+/// multiply-high of `c * a + 128` by 257. Returns how many pixels were written, pixels
+/// land in reverse order when `REVERSE` is set.
+#[target_feature(enable = "sse4.1")]
+pub(crate) fn sse_premultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 4;
+    unsafe {
+        let alpha_shuffle = _mm_setr_epi8(3, 3, 3, -1, 7, 7, 7, -1, 11, 11, 11, -1, 15, 15, 15, -1);
+        let alpha_lane = _mm_set1_epi32(0xFF00_0000u32 as i32);
+        let rounding = _mm_set1_epi16(128);
+        let div_255 = _mm_set1_epi16(257);
+        let zeros = _mm_setzero_si128();
+        for i in 0..chunks {
+            let v = _mm_loadu_si128(src.get_unchecked(i * 16..).as_ptr() as *const __m128i);
+            // Alpha lane is scaled by 255 so it survives the division unchanged.
+            let a = _mm_or_si128(_mm_shuffle_epi8(v, alpha_shuffle), alpha_lane);
+            let lo = _mm_mullo_epi16(_mm_unpacklo_epi8(v, zeros), _mm_unpacklo_epi8(a, zeros));
+            let hi = _mm_mullo_epi16(_mm_unpackhi_epi8(v, zeros), _mm_unpackhi_epi8(a, zeros));
+            let lo = _mm_mulhi_epu16(_mm_add_epi16(lo, rounding), div_255);
+            let hi = _mm_mulhi_epu16(_mm_add_epi16(hi, rounding), div_255);
+            let mut v = _mm_packus_epi16(lo, hi);
+            let dst_px = if REVERSE {
+                v = _mm_shuffle_epi32::<0x1B>(v);
+                pixels - 4 * (i + 1)
+            } else {
+                4 * i
+            };
+            _mm_storeu_si128(
+                dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr() as *mut __m128i,
+                v,
+            );
+        }
+    }
+    chunks * 4
+}
+
+/// Divides color of a pixel widened to `[r, g, b, a]` by its alpha, rounded and saturated.
+///
+/// Quotient of `c * 255 + a / 2` stays below 2^16, so the refined float reciprocal lands
+/// within one of it and the remainder corrects that.
+#[inline(always)]
+unsafe fn sse_unpremultiply_px(p: __m128i) -> __m128i {
+    let a = _mm_shuffle_epi32::<0xFF>(p);
+    let n = _mm_add_epi32(
+        _mm_sub_epi32(_mm_slli_epi32::<8>(p), p),
+        _mm_srli_epi32::<1>(a),
+    );
+    let af = _mm_cvtepi32_ps(a);
+    let recip = _mm_rcp_ps(af);
+    let recip = _mm_mul_ps(recip, _mm_sub_ps(_mm_set1_ps(2.), _mm_mul_ps(af, recip)));
+    let q = _mm_cvttps_epi32(_mm_mul_ps(_mm_cvtepi32_ps(n), recip));
+    let r = _mm_sub_epi32(n, _mm_mullo_epi32(q, a));
+    // Masks are -1 where set, so adding one steps down and subtracting one steps up.
+    let q = _mm_add_epi32(q, _mm_cmplt_epi32(r, _mm_setzero_si128()));
+    let q = _mm_sub_epi32(q, _mm_cmpgt_epi32(r, _mm_sub_epi32(a, _mm_set1_epi32(1))));
+    let q = _mm_min_epi32(q, _mm_set1_epi32(255));
+    let q = _mm_andnot_si128(_mm_cmpeq_epi32(a, _mm_setzero_si128()), q);
+    _mm_blend_epi16::<0xC0>(q, p)
+}
+
+/// Unpremultiplies color of RGBA8 pixels in runs of 4, see [sse_unpremultiply_px].
+/// Returns how many pixels were written, pixels land in reverse order when `REVERSE` is set.
+#[target_feature(enable = "sse4.1")]
+pub(crate) fn sse_unpremultiply_u8<const REVERSE: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+    let pixels = src.len().min(dst.len()) / 4;
+    let chunks = pixels / 4;
+    unsafe {
+        for i in 0..chunks {
+            let v = _mm_loadu_si128(src.get_unchecked(i * 16..).as_ptr() as *const __m128i);
+            let p0 = sse_unpremultiply_px(_mm_cvtepu8_epi32(v));
+            let p1 = sse_unpremultiply_px(_mm_cvtepu8_epi32(_mm_srli_si128::<4>(v)));
+            let p2 = sse_unpremultiply_px(_mm_cvtepu8_epi32(_mm_srli_si128::<8>(v)));
+            let p3 = sse_unpremultiply_px(_mm_cvtepu8_epi32(_mm_srli_si128::<12>(v)));
+            let mut v = _mm_packus_epi16(_mm_packus_epi32(p0, p1), _mm_packus_epi32(p2, p3));
+            let dst_px = if REVERSE {
+                v = _mm_shuffle_epi32::<0x1B>(v);
+                pixels - 4 * (i + 1)
+            } else {
+                4 * i
+            };
+            _mm_storeu_si128(
+                dst.get_unchecked_mut(dst_px * 4..).as_mut_ptr() as *mut __m128i,
+                v,
+            );
+        }
+    }
+    chunks * 4
+}
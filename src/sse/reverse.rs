@@ -0,0 +1,137 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::reverse_masks::ReverseMasks;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn sse_reverse_triple(
+    v: (__m128i, __m128i, __m128i),
+    masks: &[[[i8; 16]; 3]; 3],
+) -> (__m128i, __m128i, __m128i) {
+    let mut r = [_mm_setzero_si128(); 3];
+    for (dst, mask) in r.iter_mut().zip(masks.iter()) {
+        let m0 = _mm_loadu_si128(mask[0].as_ptr() as *const __m128i);
+        let m1 = _mm_loadu_si128(mask[1].as_ptr() as *const __m128i);
+        let m2 = _mm_loadu_si128(mask[2].as_ptr() as *const __m128i);
+        *dst = _mm_or_si128(
+            _mm_or_si128(_mm_shuffle_epi8(v.0, m0), _mm_shuffle_epi8(v.1, m1)),
+            _mm_shuffle_epi8(v.2, m2),
+        );
+    }
+    (r[0], r[1], r[2])
+}
+
+/// Reverses order of `PX` bytes wide pixels from `src` into `dst`.
+///
+/// Source is read forward and destination is written backward,
+/// `src` and `dst` are expected to have the same length.
+#[inline(always)]
+pub(crate) fn sse_reverse_pixels<const PX: usize>(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len(), dst.len());
+    let width = src.len() / PX;
+    let mut x = 0usize;
+
+    unsafe {
+        if 16 % PX == 0 {
+            let pixels_per_reg = 16 / PX;
+            let mask = _mm_loadu_si128(ReverseMasks::<PX>::SINGLE.as_ptr() as *const __m128i);
+
+            while x + 4 * pixels_per_reg <= width {
+                let src_ptr = src.get_unchecked(x * PX..).as_ptr();
+                let row0 = _mm_loadu_si128(src_ptr as *const __m128i);
+                let row1 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
+                let row2 = _mm_loadu_si128(src_ptr.add(32) as *const __m128i);
+                let row3 = _mm_loadu_si128(src_ptr.add(48) as *const __m128i);
+
+                let dst_ptr = dst
+                    .get_unchecked_mut((width - x - 4 * pixels_per_reg) * PX..)
+                    .as_mut_ptr();
+                _mm_storeu_si128(dst_ptr as *mut __m128i, _mm_shuffle_epi8(row3, mask));
+                _mm_storeu_si128(
+                    dst_ptr.add(16) as *mut __m128i,
+                    _mm_shuffle_epi8(row2, mask),
+                );
+                _mm_storeu_si128(
+                    dst_ptr.add(32) as *mut __m128i,
+                    _mm_shuffle_epi8(row1, mask),
+                );
+                _mm_storeu_si128(
+                    dst_ptr.add(48) as *mut __m128i,
+                    _mm_shuffle_epi8(row0, mask),
+                );
+
+                x += 4 * pixels_per_reg;
+            }
+
+            while x + pixels_per_reg <= width {
+                let row0 = _mm_loadu_si128(src.get_unchecked(x * PX..).as_ptr() as *const __m128i);
+                _mm_storeu_si128(
+                    dst.get_unchecked_mut((width - x - pixels_per_reg) * PX..)
+                        .as_mut_ptr() as *mut __m128i,
+                    _mm_shuffle_epi8(row0, mask),
+                );
+                x += pixels_per_reg;
+            }
+        } else if 48 % PX == 0 {
+            let pixels_per_block = 48 / PX;
+            let masks = &ReverseMasks::<PX>::TRIPLE;
+
+            while x + pixels_per_block <= width {
+                let src_ptr = src.get_unchecked(x * PX..).as_ptr();
+                let row0 = _mm_loadu_si128(src_ptr as *const __m128i);
+                let row1 = _mm_loadu_si128(src_ptr.add(16) as *const __m128i);
+                let row2 = _mm_loadu_si128(src_ptr.add(32) as *const __m128i);
+
+                let v0 = sse_reverse_triple((row0, row1, row2), masks);
+
+                let dst_ptr = dst
+                    .get_unchecked_mut((width - x - pixels_per_block) * PX..)
+                    .as_mut_ptr();
+                _mm_storeu_si128(dst_ptr as *mut __m128i, v0.0);
+                _mm_storeu_si128(dst_ptr.add(16) as *mut __m128i, v0.1);
+                _mm_storeu_si128(dst_ptr.add(32) as *mut __m128i, v0.2);
+
+                x += pixels_per_block;
+            }
+        }
+
+        while x < width {
+            std::ptr::copy_nonoverlapping(
+                src.get_unchecked(x * PX..).as_ptr(),
+                dst.get_unchecked_mut((width - x - 1) * PX..).as_mut_ptr(),
+                PX,
+            );
+            x += 1;
+        }
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::sse::u16x4_2x2::sse_transpose_u64_2x2_impl;
+use crate::sse::utils::{_mm_loadu_si96, _mm_storeu_si96};
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[inline(always)]
+unsafe fn load_rgb16_row(src: &[u16]) -> __m128i {
+    // Widens 2 RGB16 pixels to RGBX16, so each pixel occupies 64 bits
+    let expand = _mm_setr_epi8(0, 1, 2, 3, 4, 5, -1, -1, 6, 7, 8, 9, 10, 11, -1, -1);
+    _mm_shuffle_epi8(_mm_loadu_si96(src.as_ptr() as *const u8), expand)
+}
+
+#[inline(always)]
+unsafe fn store_rgb16_row(dst: &mut [u16], v: __m128i) {
+    let compress = _mm_setr_epi8(0, 1, 2, 3, 4, 5, 8, 9, 10, 11, 12, 13, -1, -1, -1, -1);
+    _mm_storeu_si96(dst.as_mut_ptr() as *mut u8, _mm_shuffle_epi8(v, compress));
+}
+
+#[inline(always)]
+pub(crate) fn ssse_transpose_u16x3_2x2<const FLOP: bool, const FLIP: bool>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = load_rgb16_row(src.get_unchecked(0..));
+        let row1 = load_rgb16_row(src.get_unchecked(src_stride..));
+
+        let v0 = sse_transpose_u64_2x2_impl::<FLIP>((row0, row1));
+
+        if FLOP {
+            store_rgb16_row(dst.get_unchecked_mut(0..), v0.0);
+            store_rgb16_row(dst.get_unchecked_mut(dst_stride..), v0.1);
+        } else {
+            store_rgb16_row(dst.get_unchecked_mut(dst_stride..), v0.0);
+            store_rgb16_row(dst.get_unchecked_mut(0..), v0.1);
+        }
+    }
+}
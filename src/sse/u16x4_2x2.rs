@@ -28,6 +28,7 @@
  */
 
 use crate::sse::_mm_shuffle;
+use crate::utils::swizzle_mask_samples;
 #[cfg(target_arch = "x86")]
 use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
@@ -48,6 +49,27 @@ pub(crate) unsafe fn sse_transpose_u64_2x2_impl<const FLIP: bool>(
     }
 }
 
+#[inline(always)]
+unsafe fn sse_store_u16x4_2x2<const FLOP: bool>(
+    dst: &mut [u16],
+    dst_stride: usize,
+    v0: (__m128i, __m128i),
+) {
+    if FLOP {
+        _mm_storeu_si128(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.0);
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
+            v0.1,
+        );
+    } else {
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
+            v0.0,
+        );
+        _mm_storeu_si128(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.1);
+    }
+}
+
 #[inline]
 pub(crate) fn ssse_transpose_u16x4_2x2<const FLOP: bool, const FLIP: bool>(
     src: &[u16],
@@ -60,19 +82,34 @@ pub(crate) fn ssse_transpose_u16x4_2x2<const FLOP: bool, const FLIP: bool>(
         let row1 = _mm_loadu_si128(src.get_unchecked(src_stride..).as_ptr() as *const _);
 
         let v0 = sse_transpose_u64_2x2_impl::<FLIP>((row0, row1));
+        sse_store_u16x4_2x2::<FLOP>(dst, dst_stride, v0);
+    }
+}
 
-        if FLOP {
-            _mm_storeu_si128(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.0);
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v0.1,
-            );
-        } else {
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut _,
-                v0.0,
-            );
-            _mm_storeu_si128(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut _, v0.1);
-        }
+/// Same as [ssse_transpose_u16x4_2x2] but also reorders channels of every 4 channel 16-bit
+/// pixel by `KEY` from [crate::ChannelOrder] with one byte shuffle per row.
+#[inline(always)]
+pub(crate) fn ssse_transpose_u16x4_2x2_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = _mm_loadu_si128(src.get_unchecked(0..).as_ptr() as *const _);
+        let row1 = _mm_loadu_si128(src.get_unchecked(src_stride..).as_ptr() as *const _);
+
+        let v0 = sse_transpose_u64_2x2_impl::<FLIP>((row0, row1));
+        let mask = const { swizzle_mask_samples(KEY, 2) };
+        let mask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        sse_store_u16x4_2x2::<FLOP>(
+            dst,
+            dst_stride,
+            (_mm_shuffle_epi8(v0.0, mask), _mm_shuffle_epi8(v0.1, mask)),
+        );
     }
 }
@@ -27,6 +27,42 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
 pub(crate) const fn _mm_shuffle(z: u32, y: u32, x: u32, w: u32) -> i32 {
     ((z << 6) | (y << 4) | (x << 2) | w) as i32
 }
+
+#[inline(always)]
+pub(crate) unsafe fn _mm_loadu_si96(ptr: *const u8) -> __m128i {
+    _mm_unpacklo_epi64(_mm_loadu_si64(ptr), _mm_loadu_si32(ptr.add(8)))
+}
+
+#[inline(always)]
+pub(crate) unsafe fn _mm_storeu_si96(ptr: *mut u8, v: __m128i) {
+    _mm_storeu_si64(ptr, v);
+    _mm_storeu_si32(ptr.add(8), _mm_unpackhi_epi64(v, v));
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx"))]
+/// Loads 24 bytes as two 12 bytes halves, each placed in the low part of a register.
+#[inline(always)]
+pub(crate) unsafe fn _mm_loadu_si192_x2(ptr: *const u8) -> (__m128i, __m128i) {
+    let lo = _mm_loadu_si128(ptr as *const __m128i);
+    let hi = _mm_loadu_si64(ptr.add(16));
+    (lo, _mm_alignr_epi8::<12>(hi, lo))
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx"))]
+/// Stores two 12 bytes halves held in the low part of registers as contiguous 24 bytes.
+#[inline(always)]
+pub(crate) unsafe fn _mm_storeu_si192_x2(ptr: *mut u8, v: (__m128i, __m128i)) {
+    _mm_storeu_si128(
+        ptr as *mut __m128i,
+        _mm_alignr_epi8::<4>(v.1, _mm_slli_si128::<4>(v.0)),
+    );
+    _mm_storeu_si64(ptr.add(16), _mm_srli_si128::<4>(v.1));
+}
@@ -27,6 +27,7 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+use crate::utils::swizzle_mask_u8x4;
 #[cfg(target_arch = "x86")]
 use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
@@ -75,6 +76,62 @@ pub(crate) unsafe fn sse_transpose_4x4_impl<const FLIP: bool>(
     }
 }
 
+#[inline(always)]
+unsafe fn sse_store_4x4_u32x1<const FLOP: bool>(
+    dst: &mut [u8],
+    dst_stride: usize,
+    v0: (__m128i, __m128i, __m128i, __m128i),
+) {
+    if FLOP {
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(0..).as_mut_ptr() as *mut __m128i,
+            v0.0,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.1,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.2,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.3,
+        );
+    } else {
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.0,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.1,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut __m128i,
+            v0.2,
+        );
+        _mm_storeu_si128(
+            dst.get_unchecked_mut(0..).as_mut_ptr() as *mut __m128i,
+            v0.3,
+        );
+    }
+}
+
+#[inline(always)]
+unsafe fn sse_load_4x4_u32x1(
+    src: &[u8],
+    src_stride: usize,
+) -> (__m128i, __m128i, __m128i, __m128i) {
+    (
+        _mm_loadu_si128(src.get_unchecked(0..).as_ptr() as *const __m128i),
+        _mm_loadu_si128(src.get_unchecked(src_stride..).as_ptr() as *const __m128i),
+        _mm_loadu_si128(src.get_unchecked(2 * src_stride..).as_ptr() as *const __m128i),
+        _mm_loadu_si128(src.get_unchecked(3 * src_stride..).as_ptr() as *const __m128i),
+    )
+}
+
 #[inline(always)]
 pub(crate) fn sse_transpose_4x4_u32x1<const FLOP: bool, const FLIP: bool>(
     src: &[u8],
@@ -83,47 +140,37 @@ pub(crate) fn sse_transpose_4x4_u32x1<const FLOP: bool, const FLIP: bool>(
     dst_stride: usize,
 ) {
     unsafe {
-        let row0 = _mm_loadu_si128(src.get_unchecked(0..).as_ptr() as *const __m128i);
-        let row1 = _mm_loadu_si128(src.get_unchecked(src_stride..).as_ptr() as *const __m128i);
-        let row2 = _mm_loadu_si128(src.get_unchecked(2 * src_stride..).as_ptr() as *const __m128i);
-        let row3 = _mm_loadu_si128(src.get_unchecked(3 * src_stride..).as_ptr() as *const __m128i);
-
-        let v0 = sse_transpose_4x4_impl::<FLIP>((row0, row1, row2, row3));
+        let v0 = sse_transpose_4x4_impl::<FLIP>(sse_load_4x4_u32x1(src, src_stride));
+        sse_store_4x4_u32x1::<FLOP>(dst, dst_stride, v0);
+    }
+}
 
-        if FLOP {
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(0..).as_mut_ptr() as *mut __m128i,
-                v0.0,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.1,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.2,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.3,
-            );
-        } else {
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.0,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.1,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut __m128i,
-                v0.2,
-            );
-            _mm_storeu_si128(
-                dst.get_unchecked_mut(0..).as_mut_ptr() as *mut __m128i,
-                v0.3,
-            );
-        }
+/// Same as [sse_transpose_4x4_u32x1] but also reorders channels of every 4 channel 8-bit
+/// pixel by `KEY` from [crate::ChannelOrder], the reorder shares the flip shuffle.
+#[inline(always)]
+pub(crate) fn sse_transpose_4x4_u32x1_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let v0 = sse_transpose_4x4_impl::<false>(sse_load_4x4_u32x1(src, src_stride));
+        let mask = const { swizzle_mask_u8x4(KEY, FLIP) };
+        let mask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        sse_store_4x4_u32x1::<FLOP>(
+            dst,
+            dst_stride,
+            (
+                _mm_shuffle_epi8(v0.0, mask),
+                _mm_shuffle_epi8(v0.1, mask),
+                _mm_shuffle_epi8(v0.2, mask),
+                _mm_shuffle_epi8(v0.3, mask),
+            ),
+        );
     }
 }
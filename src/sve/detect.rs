@@ -0,0 +1,58 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::asm;
+use std::sync::OnceLock;
+
+/// Returns SVE vector length in bytes.
+///
+/// # Safety
+///
+/// SVE must be available on the current CPU.
+unsafe fn sve_vector_length() -> usize {
+    let vl: usize;
+    asm!(
+        ".arch_extension sve",
+        "rdvl {vl}, #1",
+        vl = out(reg) vl,
+        options(nomem, nostack, pure),
+    );
+    vl
+}
+
+/// Checks at runtime if SVE is available and its vectors are wider than NEON ones.
+///
+/// On 128-bit implementations NEON kernels are at least as fast, so SVE paths
+/// are selected only when they have more lanes to offer.
+pub(crate) fn has_wide_sve() -> bool {
+    static HAS_WIDE_SVE: OnceLock<bool> = OnceLock::new();
+    *HAS_WIDE_SVE.get_or_init(|| {
+        std::arch::is_aarch64_feature_detected!("sve") && unsafe { sve_vector_length() } > 16
+    })
+}
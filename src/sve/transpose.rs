@@ -0,0 +1,123 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::asm;
+
+/// Defines predicated loop that gathers `count` elements placed `src_stride`
+/// bytes apart and stores them contiguously.
+///
+/// Offsets are kept in 64-bit lanes so any image stride is representable,
+/// loop is vector length agnostic and tail is handled by `whilelo` predicate.
+macro_rules! define_gather {
+    ($name: ident, $load: literal, $store: literal, $index: literal) => {
+        #[inline(always)]
+        unsafe fn $name(src: *const u8, src_stride: isize, dst: *mut u8, count: usize) {
+            asm!(
+                ".arch_extension sve",
+                "mov {i}, #0",
+                "index z1.d, #0, {stride}",
+                "cntd {step}",
+                "mul {step}, {step}, {stride}",
+                "whilelo p0.d, {i}, {count}",
+                "2:",
+                concat!($load, " {{ z0.d }}, p0/z, [{src}, z1.d]"),
+                concat!($store, " {{ z0.d }}, p0, [{dst}, {i}", $index, "]"),
+                "add {src}, {src}, {step}",
+                "incd {i}",
+                "whilelo p0.d, {i}, {count}",
+                "b.first 2b",
+                i = out(reg) _,
+                step = out(reg) _,
+                count = in(reg) count,
+                stride = in(reg) src_stride,
+                src = inout(reg) src => _,
+                dst = in(reg) dst,
+                out("v0") _,
+                out("v1") _,
+                out("p0") _,
+                options(nostack),
+            );
+        }
+    };
+}
+
+define_gather!(gather_b, "ld1b", "st1b", "");
+define_gather!(gather_h, "ld1h", "st1h", ", lsl #1");
+define_gather!(gather_w, "ld1w", "st1w", ", lsl #2");
+define_gather!(gather_d, "ld1d", "st1d", ", lsl #3");
+
+/// Number of source rows gathered per pass, keeps touched source cache lines
+/// resident while destination rows are written one after another.
+const TILE_HEIGHT: usize = 64;
+
+/// Transposes image by gathering each source column into destination row.
+///
+/// Each gather moves only one pixel per 64-bit offset lane and ignores store hints
+/// and L2 tiling, so this path is opt-in behind `sve` feature rather than default.
+///
+/// Supports pixels of 1, 2, 4 and 8 bytes, dimensions must be validated by the caller.
+pub(crate) fn sve_transpose<V: Copy, const CN: usize, const FLOP: bool, const FLIP: bool>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) {
+    let gather: unsafe fn(*const u8, isize, *mut u8, usize) = match std::mem::size_of::<V>() * CN {
+        1 => gather_b,
+        2 => gather_h,
+        4 => gather_w,
+        8 => gather_d,
+        _ => unreachable!("Pixel size is not supported by SVE transpose"),
+    };
+
+    let row_step = (input_stride * std::mem::size_of::<V>()) as isize;
+    let src_stride = if FLIP { -row_step } else { row_step };
+
+    let mut y = 0usize;
+    while y < height {
+        let tile = TILE_HEIGHT.min(height - y);
+        let src_y = if FLIP { height - 1 - y } else { y };
+        for (dst_x, dst_row) in output.chunks_mut(output_stride).take(width).enumerate() {
+            let x = if FLOP { dst_x } else { width - 1 - dst_x };
+            unsafe {
+                gather(
+                    input
+                        .get_unchecked(src_y * input_stride + x * CN..)
+                        .as_ptr() as *const u8,
+                    src_stride,
+                    dst_row.get_unchecked_mut(y * CN..).as_mut_ptr() as *mut u8,
+                    tile,
+                );
+            }
+        }
+        y += tile;
+    }
+}
@@ -0,0 +1,707 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::convert::{
+    mirror_converted, transpose_converted, transpose_converted_with, TileTransposer,
+};
+use crate::float32_rgba_invoker::RgbaF32Swizzled;
+use crate::fused::FusedSample;
+use crate::rgba16::Rgba16Swizzled;
+use crate::rgba8::Rgba8Swizzled;
+use crate::{ChannelOrder, FlipMode, FlopMode, TransposeError};
+use roxygen::roxygen;
+
+pub(crate) trait SwizzleSample: FusedSample + 'static {
+    /// Value written into alpha when source has none
+    const OPAQUE: Self;
+
+    /// Transposes 4 channel pixels reordering channels by `KEY`, see [ChannelOrder::swizzle_from]
+    fn transpose_reordered<const KEY: usize>(
+        input: &[Self],
+        input_stride: usize,
+        output: &mut [Self],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        flip_mode: FlipMode,
+        flop_mode: FlopMode,
+    ) -> Result<(), TransposeError>;
+}
+
+macro_rules! define_swizzle_sample {
+    ($sample: ty, $opaque: expr, $swizzled: ident) => {
+        impl SwizzleSample for $sample {
+            const OPAQUE: Self = $opaque;
+
+            fn transpose_reordered<const KEY: usize>(
+                input: &[Self],
+                input_stride: usize,
+                output: &mut [Self],
+                output_stride: usize,
+                width: usize,
+                height: usize,
+                flip_mode: FlipMode,
+                flop_mode: FlopMode,
+            ) -> Result<(), TransposeError> {
+                // Channels are reordered by the shuffles of the block kernels.
+                transpose_converted_with::<$sample, $swizzled<KEY>, $sample, 4, 4>(
+                    input,
+                    input_stride,
+                    output,
+                    output_stride,
+                    width,
+                    height,
+                    flip_mode,
+                    flop_mode,
+                    |src, dst| dst.copy_from_slice(src),
+                )
+            }
+        }
+    };
+}
+
+define_swizzle_sample!(u8, u8::MAX, Rgba8Swizzled);
+define_swizzle_sample!(u16, u16::MAX, Rgba16Swizzled);
+define_swizzle_sample!(f32, 1., RgbaF32Swizzled);
+
+type RowSwizzler<V> = fn(&[V], &mut [V]);
+
+/// Reorders pixels of a row by channel map packed into `KEY`, see [ChannelOrder::swizzle_from],
+/// map is known at compile time so every pixel becomes a fixed shuffle.
+fn swizzle_row<
+    V: SwizzleSample,
+    const N: usize,
+    const M: usize,
+    const REVERSE: bool,
+    const KEY: usize,
+>(
+    src: &[V],
+    dst: &mut [V],
+) {
+    let src = src.chunks_exact(N);
+    let dst = dst.chunks_exact_mut(M);
+    let swizzle = |dst: &mut [V], src: &[V]| {
+        for (c, dst) in dst.iter_mut().enumerate() {
+            let position = (KEY >> (c * 4)) & 0xF;
+            *dst = if position < N {
+                src[position]
+            } else {
+                V::OPAQUE
+            };
+        }
+    };
+    if REVERSE {
+        dst.rev().zip(src).for_each(|(dst, src)| swizzle(dst, src));
+    } else {
+        dst.zip(src).for_each(|(dst, src)| swizzle(dst, src));
+    }
+}
+
+macro_rules! select_swizzler {
+    ($src:expr, $dst:expr, [$($s:ident),*], $d:tt) => {
+        $(select_swizzler!(@row $src, $dst, $s, $d);)*
+    };
+    (@row $src:expr, $dst:expr, $s:ident, [$($d:ident),*]) => {
+        $(
+            if $src == ChannelOrder::$s && $dst == ChannelOrder::$d {
+                return swizzle_row::<
+                    V,
+                    { ChannelOrder::$s.channels() },
+                    { ChannelOrder::$d.channels() },
+                    REVERSE,
+                    { ChannelOrder::$d.swizzle_from(ChannelOrder::$s) },
+                >;
+            }
+        )*
+    };
+}
+
+fn swizzler<V: SwizzleSample, const REVERSE: bool>(
+    src_order: ChannelOrder,
+    dst_order: ChannelOrder,
+) -> RowSwizzler<V> {
+    select_swizzler!(
+        src_order,
+        dst_order,
+        [Rgba, Bgra, Argb, Abgr, Rgb, Bgr],
+        [Rgba, Bgra, Argb, Abgr, Rgb, Bgr]
+    );
+    unreachable!("Every channel order pair is covered")
+}
+
+macro_rules! select_reordered {
+    ($src:expr, $dst:expr, [$($s:ident),*], $d:tt) => {
+        $(select_reordered!(@row $src, $dst, $s, $d);)*
+    };
+    (@row $src:expr, $dst:expr, $s:ident, [$($d:ident),*]) => {
+        $(
+            if $src == ChannelOrder::$s && $dst == ChannelOrder::$d {
+                return V::transpose_reordered::<{ ChannelOrder::$d.swizzle_from(ChannelOrder::$s) }>;
+            }
+        )*
+    };
+}
+
+/// Transposer of 4 channel pixels that reorders channels in the block kernels when the
+/// sample type has such kernels.
+fn reordered_transposer<V: SwizzleSample>(
+    src_order: ChannelOrder,
+    dst_order: ChannelOrder,
+) -> TileTransposer<V> {
+    select_reordered!(
+        src_order,
+        dst_order,
+        [Rgba, Bgra, Argb, Abgr],
+        [Rgba, Bgra, Argb, Abgr]
+    );
+    unreachable!("Every 4 channel order pair is covered")
+}
+
+fn transpose_swizzled<V: SwizzleSample>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    src_order: ChannelOrder,
+    dst_order: ChannelOrder,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    if src_order.channels() == 4 && dst_order.channels() == 4 {
+        return reordered_transposer::<V>(src_order, dst_order)(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            flip_mode,
+            flop_mode,
+        );
+    }
+    let swizzle = swizzler::<V, false>(src_order, dst_order);
+    macro_rules! dispatch {
+        ($n:expr, $m:expr) => {
+            transpose_converted::<V, V, $n, $m>(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                flip_mode,
+                flop_mode,
+                swizzle,
+            )
+        };
+    }
+    match (src_order.channels(), dst_order.channels()) {
+        (3, 3) => dispatch!(3, 3),
+        (3, _) => dispatch!(3, 4),
+        _ => dispatch!(4, 3),
+    }
+}
+
+fn mirror_swizzled<V: SwizzleSample, const REVERSE_PIXELS: bool, const REVERSE_ROWS: bool>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    src_order: ChannelOrder,
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_converted::<V, V, REVERSE_ROWS>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order.channels(),
+        dst_order.channels(),
+        swizzler::<V, REVERSE_PIXELS>(src_order, dst_order),
+    )
+}
+
+/// Transposes an 8-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled as opaque when source has none.
+///
+/// # Performance
+///
+/// Between 4 channel orders the block kernels reorder channels in their shuffles, converting
+/// from or to 3 channels reorders transposed blocks in a scalar row pass while still in cache.
+#[roxygen]
+pub fn transpose_rgba_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_swizzled(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Transposes a 16-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `u16::MAX` when source has none.
+///
+/// # Performance
+///
+/// Between 4 channel orders the block kernels reorder channels in their shuffles, converting
+/// from or to 3 channels reorders transposed blocks in a scalar row pass while still in cache.
+#[roxygen]
+pub fn transpose_rgba16_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (height * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_swizzled(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Transposes a 32-bit float color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `1.0` when source has none.
+///
+/// # Performance
+///
+/// Between 4 channel orders the block kernels reorder channels in their shuffles, converting
+/// from or to 3 channels reorders transposed blocks in a scalar row pass while still in cache.
+#[roxygen]
+pub fn transpose_rgba_f32_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (height * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+) -> Result<(), TransposeError> {
+    transpose_swizzled(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+        flip_mode,
+        flop_mode,
+    )
+}
+
+/// Flips an 8-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled as opaque when source has none.
+#[roxygen]
+pub fn flip_rgba_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u8, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Flips a 16-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `u16::MAX` when source has none.
+#[roxygen]
+pub fn flip_rgba16_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u16, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Flips a 32-bit float color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `1.0` when source has none.
+#[roxygen]
+pub fn flip_rgba_f32_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<f32, true, false>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Flops an 8-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled as opaque when source has none.
+#[roxygen]
+pub fn flop_rgba_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u8, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Flops a 16-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `u16::MAX` when source has none.
+#[roxygen]
+pub fn flop_rgba16_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u16, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Flops a 32-bit float color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `1.0` when source has none.
+#[roxygen]
+pub fn flop_rgba_f32_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<f32, false, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Rotates by 180 degrees an 8-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled as opaque when source has none.
+#[roxygen]
+pub fn rotate180_rgba_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u8],
+    /// Number of bytes per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u8, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Rotates by 180 degrees a 16-bit color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `u16::MAX` when source has none.
+#[roxygen]
+pub fn rotate180_rgba16_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<u16, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
+
+/// Rotates by 180 degrees a 32-bit float color image while reordering its channels.
+///
+/// Converts between RGBA, BGRA, ARGB, ABGR, RGB and BGR in the same pass, alpha is dropped
+/// when destination has none and filled with `1.0` when source has none.
+#[roxygen]
+pub fn rotate180_rgba_f32_swizzled(
+    /// Source image data as a flat array of pixels in `src_order`.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * channels for packed data).
+    input_stride: usize,
+    /// Destination buffer for pixels in `dst_order`.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (width * channels for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Channel order of the source.
+    src_order: ChannelOrder,
+    /// Channel order of the destination.
+    dst_order: ChannelOrder,
+) -> Result<(), TransposeError> {
+    mirror_swizzled::<f32, true, true>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        src_order,
+        dst_order,
+    )
+}
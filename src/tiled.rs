@@ -0,0 +1,553 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::convert::{f32_transposer, u16_transposer, u8_transposer, TileTransposer};
+use crate::utils::is_valid_image_len;
+use crate::{Orientation, TileLayout, TransposeError};
+use roxygen::roxygen;
+
+/// Minimum number of rows and columns of an image block reoriented at once, small tiles are batched
+/// so transpose kernels read and write long runs while the block stays in cache
+const BLOCK_ROWS: usize = 64;
+const BLOCK_COLUMNS: usize = 256;
+
+/// Rows and columns of a block, rounded up to whole tiles
+const fn block_size(size: usize) -> (usize, usize) {
+    (
+        BLOCK_ROWS.div_ceil(size) * size,
+        BLOCK_COLUMNS.div_ceil(size) * size,
+    )
+}
+
+/// Copies `height` rows of `width` pixels, reversing pixels of each row and order of rows on request
+fn copy_rows<V: Copy, const N: usize>(
+    src: &[V],
+    src_stride: usize,
+    dst: &mut [V],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    reverse_x: bool,
+    reverse_y: bool,
+) {
+    for (y, src) in src.chunks(src_stride).take(height).enumerate() {
+        let dy = if reverse_y { height - 1 - y } else { y };
+        let src = &src[..width * N];
+        let dst = &mut dst[dy * dst_stride..][..width * N];
+        if reverse_x {
+            for (dst, src) in dst.chunks_exact_mut(N).zip(src.chunks_exact(N).rev()) {
+                dst.copy_from_slice(src);
+            }
+        } else {
+            dst.copy_from_slice(src);
+        }
+    }
+}
+
+/// Morton offsets of tile coordinates, bits of a coordinate spread to even positions
+fn morton_spread(layout: TileLayout) -> Vec<usize> {
+    match layout {
+        TileLayout::Tiled { .. } => Vec::new(),
+        TileLayout::Morton { size } => (0..size)
+            .map(|v| {
+                let mut spread = 0;
+                let mut bit = 0;
+                while v >> bit != 0 {
+                    spread |= ((v >> bit) & 1) << (bit * 2);
+                    bit += 1;
+                }
+                spread
+            })
+            .collect(),
+    }
+}
+
+/// Rows of an image block staged in row order, and tiles they are split into
+struct TiledBlock<'a> {
+    layout: TileLayout,
+    spread: &'a [usize],
+    width: usize,
+    rows: usize,
+}
+
+impl TiledBlock<'_> {
+    fn scatter<V: Copy, const N: usize>(&self, block: &[V], tiles: &mut [V]) {
+        let size = self.layout.size();
+        let block_stride = self.width * N;
+        for (tx, tile) in tiles.chunks_exact_mut(size * size * N).enumerate() {
+            let x = tx * size;
+            let tile_width = size.min(self.width - x);
+            for y in 0..self.rows {
+                let src = &block[y * block_stride + x * N..][..tile_width * N];
+                match self.layout {
+                    TileLayout::Tiled { .. } => {
+                        tile[y * size * N..][..tile_width * N].copy_from_slice(src);
+                    }
+                    TileLayout::Morton { .. } => {
+                        let row = self.spread[y] << 1;
+                        for (px, &spread) in src.chunks_exact(N).zip(self.spread) {
+                            let offset = (row | spread) * N;
+                            tile[offset..offset + N].copy_from_slice(px);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn gather<V: Copy, const N: usize>(&self, tiles: &[V], block: &mut [V]) {
+        let size = self.layout.size();
+        let block_stride = self.width * N;
+        for (tx, tile) in tiles.chunks_exact(size * size * N).enumerate() {
+            let x = tx * size;
+            let tile_width = size.min(self.width - x);
+            for y in 0..self.rows {
+                let dst = &mut block[y * block_stride + x * N..][..tile_width * N];
+                match self.layout {
+                    TileLayout::Tiled { .. } => {
+                        dst.copy_from_slice(&tile[y * size * N..][..tile_width * N]);
+                    }
+                    TileLayout::Morton { .. } => {
+                        let row = self.spread[y] << 1;
+                        for (px, &spread) in dst.chunks_exact_mut(N).zip(self.spread) {
+                            let offset = (row | spread) * N;
+                            px.copy_from_slice(&tile[offset..offset + N]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Source rectangle `(x, y, width, height)` of a destination rectangle of the reoriented image
+fn source_rect(
+    orientation: Orientation,
+    width: usize,
+    height: usize,
+    (x, y, rect_width, rect_height): (usize, usize, usize, usize),
+) -> (usize, usize, usize, usize) {
+    let (reverse_x, reverse_y) = orientation.reversed_axes();
+    let (x, y, rect_width, rect_height) = if orientation.is_transposed() {
+        (y, x, rect_height, rect_width)
+    } else {
+        (x, y, rect_width, rect_height)
+    };
+    let x = if reverse_x { width - x - rect_width } else { x };
+    let y = if reverse_y {
+        height - y - rect_height
+    } else {
+        y
+    };
+    (x, y, rect_width, rect_height)
+}
+
+/// Destination rectangle `(x, y, width, height)` of a source rectangle of the image
+fn destination_rect(
+    orientation: Orientation,
+    width: usize,
+    height: usize,
+    (x, y, rect_width, rect_height): (usize, usize, usize, usize),
+) -> (usize, usize, usize, usize) {
+    let (reverse_x, reverse_y) = orientation.reversed_axes();
+    let x = if reverse_x { width - x - rect_width } else { x };
+    let y = if reverse_y {
+        height - y - rect_height
+    } else {
+        y
+    };
+    if orientation.is_transposed() {
+        (y, x, rect_height, rect_width)
+    } else {
+        (x, y, rect_width, rect_height)
+    }
+}
+
+/// Reorients a `width` x `height` block starting at the beginning of `src` into `dst`
+fn orient_block<V: Copy, const N: usize>(
+    src: &[V],
+    src_stride: usize,
+    dst: &mut [V],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    transposer: TileTransposer<V>,
+) -> Result<(), TransposeError> {
+    if let Some((flip_mode, flop_mode)) = orientation.transpose_modes() {
+        transposer(
+            &src[..(height - 1) * src_stride + width * N],
+            src_stride,
+            &mut dst[..(width - 1) * dst_stride + height * N],
+            dst_stride,
+            width,
+            height,
+            flip_mode,
+            flop_mode,
+        )
+    } else {
+        let (reverse_x, reverse_y) = orientation.reversed_axes();
+        copy_rows::<V, N>(
+            src, src_stride, dst, dst_stride, width, height, reverse_x, reverse_y,
+        );
+        Ok(())
+    }
+}
+
+/// Reorients a linear image in blocks of whole destination tiles, every block is produced with
+/// the transpose kernels into a scratch buffer and split into tiles while still in cache
+fn rotate_to_tiled_impl<V: Copy + Default, const N: usize>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    width: usize,
+    height: usize,
+    layout: TileLayout,
+    orientation: Orientation,
+    transposer: TileTransposer<V>,
+) -> Result<(), TransposeError> {
+    let (dst_width, dst_height) = if orientation.is_transposed() {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    if !layout.is_valid() || output.len() != layout.buffer_len(dst_width, dst_height, N) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(input.len(), input_stride, width * N, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < width * N {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+
+    let size = layout.size();
+    let spread = morton_spread(layout);
+    let (block_rows, block_columns) = block_size(size);
+    let mut block =
+        vec![V::default(); block_rows.min(dst_height) * block_columns.min(dst_width) * N];
+    let tile_len = size * size * N;
+    let tiles_row = dst_width.div_ceil(size) * tile_len;
+
+    for (by, band) in output
+        .chunks_mut(tiles_row * (block_rows / size))
+        .enumerate()
+    {
+        let y = by * block_rows;
+        let rows = block_rows.min(dst_height - y);
+        for x in (0..dst_width).step_by(block_columns) {
+            let columns = block_columns.min(dst_width - x);
+            let stride = columns * N;
+            let block = &mut block[..stride * rows];
+            let (src_x, src_y, src_width, src_height) =
+                source_rect(orientation, width, height, (x, y, columns, rows));
+            orient_block::<V, N>(
+                &input[src_y * input_stride + src_x * N..],
+                input_stride,
+                block,
+                stride,
+                src_width,
+                src_height,
+                orientation,
+                transposer,
+            )?;
+            let tiles = x / size * tile_len..(x + columns).div_ceil(size) * tile_len;
+            for (k, band) in band.chunks_exact_mut(tiles_row).enumerate() {
+                TiledBlock {
+                    layout,
+                    spread: &spread,
+                    width: columns,
+                    rows: size.min(rows - k * size),
+                }
+                .scatter::<V, N>(&block[k * size * stride..], &mut band[tiles.clone()]);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reorients a tiled image in blocks of whole source tiles, every block is gathered
+/// into a scratch buffer and reoriented into the destination with the transpose kernels
+fn rotate_from_tiled_impl<V: Copy + Default, const N: usize>(
+    input: &[V],
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    layout: TileLayout,
+    orientation: Orientation,
+    transposer: TileTransposer<V>,
+) -> Result<(), TransposeError> {
+    let (dst_width, dst_height) = if orientation.is_transposed() {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    if !layout.is_valid() || input.len() != layout.buffer_len(width, height, N) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, dst_width * N, dst_height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if output_stride < dst_width * N {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+
+    let size = layout.size();
+    let spread = morton_spread(layout);
+    let (block_rows, block_columns) = block_size(size);
+    let mut block = vec![V::default(); block_rows.min(height) * block_columns.min(width) * N];
+    let tile_len = size * size * N;
+    let tiles_row = width.div_ceil(size) * tile_len;
+
+    for (by, band) in input.chunks(tiles_row * (block_rows / size)).enumerate() {
+        let y = by * block_rows;
+        let rows = block_rows.min(height - y);
+        for x in (0..width).step_by(block_columns) {
+            let columns = block_columns.min(width - x);
+            let stride = columns * N;
+            let block = &mut block[..stride * rows];
+            let tiles = x / size * tile_len..(x + columns).div_ceil(size) * tile_len;
+            for (k, band) in band.chunks_exact(tiles_row).enumerate() {
+                TiledBlock {
+                    layout,
+                    spread: &spread,
+                    width: columns,
+                    rows: size.min(rows - k * size),
+                }
+                .gather::<V, N>(&band[tiles.clone()], &mut block[k * size * stride..]);
+            }
+            let (dst_x, dst_y, _, _) =
+                destination_rect(orientation, width, height, (x, y, columns, rows));
+            orient_block::<V, N>(
+                block,
+                stride,
+                &mut output[dst_y * output_stride + dst_x * N..],
+                output_stride,
+                columns,
+                rows,
+                orientation,
+                transposer,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Reorients a linear 8-bit image of `N` channel pixels straight into a tiled destination.
+///
+/// Destination tiles are laid out over the reoriented image, padding pixels of edge tiles are left untouched.
+#[roxygen]
+pub fn rotate_to_tiled<const N: usize>(
+    /// Source image data.
+    input: &[u8],
+    /// Number of u8 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination tiled image of [TileLayout::buffer_len] samples.
+    output: &mut [u8],
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the destination.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_to_tiled_impl::<u8, N>(
+        input,
+        input_stride,
+        output,
+        width,
+        height,
+        layout,
+        orientation,
+        u8_transposer::<N>(),
+    )
+}
+
+/// Reorients a tiled 8-bit image of `N` channel pixels straight into a linear destination.
+#[roxygen]
+pub fn rotate_from_tiled<const N: usize>(
+    /// Source tiled image of [TileLayout::buffer_len] samples.
+    input: &[u8],
+    /// Destination image data.
+    output: &mut [u8],
+    /// Number of u8 elements per row in the output (reoriented width * N for packed data).
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the source.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_from_tiled_impl::<u8, N>(
+        input,
+        output,
+        output_stride,
+        width,
+        height,
+        layout,
+        orientation,
+        u8_transposer::<N>(),
+    )
+}
+
+/// Reorients a linear 16-bit image of `N` channel pixels straight into a tiled destination.
+///
+/// Destination tiles are laid out over the reoriented image, padding pixels of edge tiles are left untouched.
+#[roxygen]
+pub fn rotate_to_tiled16<const N: usize>(
+    /// Source image data.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination tiled image of [TileLayout::buffer_len] samples.
+    output: &mut [u16],
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the destination.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_to_tiled_impl::<u16, N>(
+        input,
+        input_stride,
+        output,
+        width,
+        height,
+        layout,
+        orientation,
+        u16_transposer::<N>(),
+    )
+}
+
+/// Reorients a tiled 16-bit image of `N` channel pixels straight into a linear destination.
+#[roxygen]
+pub fn rotate_from_tiled16<const N: usize>(
+    /// Source tiled image of [TileLayout::buffer_len] samples.
+    input: &[u16],
+    /// Destination image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (reoriented width * N for packed data).
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the source.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_from_tiled_impl::<u16, N>(
+        input,
+        output,
+        output_stride,
+        width,
+        height,
+        layout,
+        orientation,
+        u16_transposer::<N>(),
+    )
+}
+
+/// Reorients a linear 32-bit float image of `N` channel pixels straight into a tiled destination.
+///
+/// Destination tiles are laid out over the reoriented image, padding pixels of edge tiles are left untouched.
+#[roxygen]
+pub fn rotate_to_tiled_f32<const N: usize>(
+    /// Source image data.
+    input: &[f32],
+    /// Number of f32 elements per row in the input (width * N for packed data).
+    input_stride: usize,
+    /// Destination tiled image of [TileLayout::buffer_len] samples.
+    output: &mut [f32],
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the destination.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_to_tiled_impl::<f32, N>(
+        input,
+        input_stride,
+        output,
+        width,
+        height,
+        layout,
+        orientation,
+        f32_transposer::<N>(),
+    )
+}
+
+/// Reorients a tiled 32-bit float image of `N` channel pixels straight into a linear destination.
+#[roxygen]
+pub fn rotate_from_tiled_f32<const N: usize>(
+    /// Source tiled image of [TileLayout::buffer_len] samples.
+    input: &[f32],
+    /// Destination image data.
+    output: &mut [f32],
+    /// Number of f32 elements per row in the output (reoriented width * N for packed data).
+    output_stride: usize,
+    /// Width of the source image in pixels.
+    width: usize,
+    /// Height of the source image in pixels.
+    height: usize,
+    /// Tile layout of the source.
+    layout: TileLayout,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_from_tiled_impl::<f32, N>(
+        input,
+        output,
+        output_stride,
+        width,
+        height,
+        layout,
+        orientation,
+        f32_transposer::<N>(),
+    )
+}
@@ -26,6 +26,7 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::utils::is_valid_image_len;
 use crate::{FlipMode, FlopMode, TransposeError};
 
 #[allow(clippy::too_many_arguments)]
@@ -334,10 +335,10 @@ pub fn transpose_arbitrary_grouped<V: Copy, const N: usize>(
     flip_mode: FlipMode,
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    if input.len() != input_stride * height {
+    if !is_valid_image_len(input.len(), input_stride, width * N, height) {
         return Err(TransposeError::MismatchDimensions);
     }
-    if output.len() != output_stride * width {
+    if !is_valid_image_len(output.len(), output_stride, height * N, width) {
         return Err(TransposeError::MismatchDimensions);
     }
     if input_stride < width * N {
@@ -29,9 +29,10 @@
 #![forbid(unsafe_code)]
 
 use crate::plane16::transpose_plane16_chunked;
+use crate::rgb16::transpose_rgb16_chunked;
 use crate::rgba16::transpose_rgba16_chunked;
 use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
-use crate::{FlipMode, FlopMode, TransposeError};
+use crate::{FlipMode, FlopMode, StoreHint, TransposeError};
 use roxygen::roxygen;
 
 /// Transposes a single-channel 16-bit image.
@@ -71,6 +72,46 @@ pub fn transpose_plane16(
         height,
         flip_mode,
         flop_mode,
+        StoreHint::Temporal,
+    )
+}
+
+/// Transposes a single-channel 16-bit image, choosing how destination rows are written.
+///
+/// Same as [transpose_plane16], with `store_hint` deciding whether destination rows bypass the cache.
+/// Streaming stores pay off only when the destination is far larger than the cache
+/// and is not read back soon after, measure before enabling.
+#[roxygen]
+pub fn transpose_plane16_hinted(
+    /// Source image data as a flat array of 16-bit pixels.
+    input: &[u16],
+    /// Number of u16 elements per row in the input (width for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u16],
+    /// Number of u16 elements per row in the output (height for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+    /// Whether destination rows go through the cache or are streamed past it.
+    store_hint: StoreHint,
+) -> Result<(), TransposeError> {
+    transpose_plane16_chunked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        store_hint,
     )
 }
 
@@ -141,7 +182,7 @@ pub fn transpose_rgb16(
     /// Vertical mirroring mode for rotation control.
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    transpose_arbitrary_grouped::<u16, 3>(
+    transpose_rgb16_chunked(
         input,
         input_stride,
         output,
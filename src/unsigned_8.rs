@@ -29,10 +29,10 @@
 #![forbid(unsafe_code)]
 use crate::cbcr8::transpose_cbcr8_chunked;
 use crate::plane8::transpose_plane8_chunked;
+use crate::rgb8::transpose_rgb8_chunked;
 use crate::rgba8::transpose_rgba8_chunked;
-use crate::transpose_arbitrary_group::transpose_arbitrary_grouped;
 use crate::utils::FlopMode;
-use crate::{FlipMode, TransposeError};
+use crate::{FlipMode, StoreHint, TransposeError};
 use roxygen::roxygen;
 
 /// Transposes a single-channel (grayscale) image.
@@ -72,6 +72,46 @@ pub fn transpose_plane(
         height,
         flip_mode,
         flop_mode,
+        StoreHint::Temporal,
+    )
+}
+
+/// Transposes a single-channel (grayscale) image, choosing how destination rows are written.
+///
+/// Same as [transpose_plane], with `store_hint` deciding whether destination rows bypass the cache.
+/// Streaming stores pay off only when the destination is far larger than the cache
+/// and is not read back soon after, measure before enabling.
+#[roxygen]
+pub fn transpose_plane_hinted(
+    /// Source image data as a flat array of pixels.
+    input: &[u8],
+    /// Number of bytes per row in the input (width for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed image data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+    /// Whether destination rows go through the cache or are streamed past it.
+    store_hint: StoreHint,
+) -> Result<(), TransposeError> {
+    transpose_plane8_chunked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        store_hint,
     )
 }
 
@@ -142,7 +182,7 @@ pub fn transpose_rgb(
     /// Vertical mirroring mode for rotation control.
     flop_mode: FlopMode,
 ) -> Result<(), TransposeError> {
-    transpose_arbitrary_grouped::<u8, 3>(
+    transpose_rgb8_chunked(
         input,
         input_stride,
         output,
@@ -191,5 +231,45 @@ pub fn transpose_rgba(
         height,
         flip_mode,
         flop_mode,
+        StoreHint::Temporal,
+    )
+}
+
+/// Transposes a RGBA image, choosing how destination rows are written.
+///
+/// Same as [transpose_rgba], with `store_hint` deciding whether destination rows bypass the cache.
+/// Streaming stores pay off only when the destination is far larger than the cache
+/// and is not read back soon after, measure before enabling.
+#[roxygen]
+pub fn transpose_rgba_hinted(
+    /// Source RGBA image data as a flat array (R0,G0,B0,A0,R1,G1,B1,A1,...).
+    input: &[u8],
+    /// Number of bytes per row in the input (width * 4 for packed data).
+    input_stride: usize,
+    /// Destination buffer for transposed RGBA data.
+    output: &mut [u8],
+    /// Number of bytes per row in the output (height * 4 for packed data).
+    output_stride: usize,
+    /// Width of the input image in pixels.
+    width: usize,
+    /// Height of the input image in pixels.
+    height: usize,
+    /// Horizontal mirroring mode for rotation control.
+    flip_mode: FlipMode,
+    /// Vertical mirroring mode for rotation control.
+    flop_mode: FlopMode,
+    /// Whether destination rows go through the cache or are streamed past it.
+    store_hint: StoreHint,
+) -> Result<(), TransposeError> {
+    transpose_rgba8_chunked(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        flip_mode,
+        flop_mode,
+        store_hint,
     )
 }
@@ -34,6 +34,7 @@ use std::fmt::{Display, Formatter};
 pub enum TransposeError {
     MismatchDimensions,
     InvalidArraySize,
+    InvalidBitDepth,
 }
 
 impl Display for TransposeError {
@@ -45,6 +46,7 @@ impl Display for TransposeError {
             TransposeError::InvalidArraySize => {
                 f.write_str("Vector size must match provided width and height")
             }
+            TransposeError::InvalidBitDepth => f.write_str("Bit depth must be in range 8..=16"),
         }
     }
 }
@@ -64,3 +66,402 @@ pub enum FlopMode {
     NoFlop,
     Flop,
 }
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+/// Declares one of eight image orientations, rotations are clockwise
+pub enum Orientation {
+    /// Image is copied as is
+    #[default]
+    Identity,
+    /// Pixels in every row are mirrored
+    MirrorHorizontal,
+    /// Row order is mirrored
+    MirrorVertical,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrors image along its main diagonal
+    Transpose,
+    /// Mirrors image along its anti-diagonal
+    Transverse,
+}
+
+impl Orientation {
+    /// Whether width and height of the image swap
+    pub const fn is_transposed(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90
+                | Orientation::Rotate270
+                | Orientation::Transpose
+                | Orientation::Transverse
+        )
+    }
+
+    /// Whether the orientation reverses the source x and y axes
+    pub(crate) const fn reversed_axes(self) -> (bool, bool) {
+        match self {
+            Orientation::Identity | Orientation::Transpose => (false, false),
+            Orientation::MirrorHorizontal | Orientation::Rotate270 => (true, false),
+            Orientation::MirrorVertical | Orientation::Rotate90 => (false, true),
+            Orientation::Rotate180 | Orientation::Transverse => (true, true),
+        }
+    }
+
+    /// Flip and flop modes of the transpose producing this orientation
+    pub(crate) const fn transpose_modes(self) -> Option<(FlipMode, FlopMode)> {
+        match self {
+            Orientation::Rotate90 => Some((FlipMode::Flip, FlopMode::Flop)),
+            Orientation::Rotate270 => Some((FlipMode::NoFlip, FlopMode::NoFlop)),
+            Orientation::Transpose => Some((FlipMode::NoFlip, FlopMode::Flop)),
+            Orientation::Transverse => Some((FlipMode::Flip, FlopMode::NoFlop)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Declares how color channels are scaled by alpha
+pub enum AlphaMode {
+    /// Multiplies color channels by alpha
+    Premultiply,
+    /// Divides color channels by alpha, fully transparent pixels become zero
+    Unpremultiply,
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Declares which bits of a byte hold the leftmost pixel of a row packed with 1, 2 or 4 bits per pixel
+pub enum BitOrder {
+    /// Leftmost pixel is in the most significant bits, as in PBM, PNG, BMP and fax images
+    MsbFirst,
+    /// Leftmost pixel is in the least significant bits, as in XBM images
+    LsbFirst,
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Declares color filter array of a Bayer raw image by colors of its top-left 2x2 cell in row order
+pub enum CfaPattern {
+    Rggb,
+    Bggr,
+    Grbg,
+    Gbrg,
+}
+
+impl CfaPattern {
+    /// Colors of the top-left cell in row order, 0 is red, 1 is green and 2 is blue
+    const fn cell(self) -> [u8; 4] {
+        match self {
+            CfaPattern::Rggb => [0, 1, 1, 2],
+            CfaPattern::Bggr => [2, 1, 1, 0],
+            CfaPattern::Grbg => [1, 0, 2, 1],
+            CfaPattern::Gbrg => [1, 2, 0, 1],
+        }
+    }
+
+    const fn from_cell(cell: [u8; 4]) -> CfaPattern {
+        match cell {
+            [0, 1, 1, 2] => CfaPattern::Rggb,
+            [2, 1, 1, 0] => CfaPattern::Bggr,
+            [1, 0, 2, 1] => CfaPattern::Grbg,
+            _ => CfaPattern::Gbrg,
+        }
+    }
+
+    /// Pattern of a `width` x `height` mosaic after it is put in `orientation`.
+    ///
+    /// Mirroring an axis of odd length keeps its phase, so the result depends on dimensions parity.
+    pub const fn oriented(
+        self,
+        width: usize,
+        height: usize,
+        orientation: Orientation,
+    ) -> CfaPattern {
+        let cell = self.cell();
+        let (w, h) = (width & 1, height & 1);
+        let mut oriented = [0u8; 4];
+        let mut i = 0;
+        while i < 4 {
+            let (ox, oy) = (i & 1, i >> 1);
+            // Parity of the source sample landing at (ox, oy), w - 1 - v has parity of w + 1 + v
+            let (x, y) = match orientation {
+                Orientation::Identity => (ox, oy),
+                Orientation::MirrorHorizontal => (w + 1 + ox, oy),
+                Orientation::MirrorVertical => (ox, h + 1 + oy),
+                Orientation::Rotate180 => (w + 1 + ox, h + 1 + oy),
+                Orientation::Rotate90 => (oy, h + 1 + ox),
+                Orientation::Rotate270 => (w + 1 + oy, ox),
+                Orientation::Transpose => (oy, ox),
+                Orientation::Transverse => (w + 1 + oy, h + 1 + ox),
+            };
+            oriented[i] = cell[(y & 1) * 2 + (x & 1)];
+            i += 1;
+        }
+        CfaPattern::from_cell(oriented)
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Declares tiled memory layout of an image.
+///
+/// Image is split into square tiles of `size` x `size` pixels, right and bottom tiles are padded
+/// to full size, and tiles are stored one after another in row order. A single Morton tile covering
+/// the whole image gives plain Z-order layout.
+pub enum TileLayout {
+    /// Pixels inside a tile are stored in row order
+    Tiled { size: usize },
+    /// Pixels inside a tile are stored in Morton (Z) order, `size` must be a power of two
+    Morton { size: usize },
+}
+
+impl TileLayout {
+    /// Width and height of a tile in pixels
+    pub const fn size(self) -> usize {
+        match self {
+            TileLayout::Tiled { size } | TileLayout::Morton { size } => size,
+        }
+    }
+
+    pub(crate) const fn is_valid(self) -> bool {
+        match self {
+            TileLayout::Tiled { size } => size > 0,
+            TileLayout::Morton { size } => size.is_power_of_two(),
+        }
+    }
+
+    /// Number of samples in a tiled image of `width` x `height` pixels with `channels` samples each,
+    /// padding included
+    pub const fn buffer_len(self, width: usize, height: usize, channels: usize) -> usize {
+        let size = self.size();
+        if size == 0 {
+            return 0;
+        }
+        width.div_ceil(size) * height.div_ceil(size) * size * size * channels
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Declares order of color channels in a pixel
+pub enum ChannelOrder {
+    Rgba,
+    Bgra,
+    Argb,
+    Abgr,
+    Rgb,
+    Bgr,
+}
+
+impl ChannelOrder {
+    /// Number of channels in a pixel
+    pub const fn channels(self) -> usize {
+        match self {
+            ChannelOrder::Rgba | ChannelOrder::Bgra | ChannelOrder::Argb | ChannelOrder::Abgr => 4,
+            ChannelOrder::Rgb | ChannelOrder::Bgr => 3,
+        }
+    }
+
+    /// Components stored in each channel, 0 is red, 1 green, 2 blue and 3 alpha
+    const fn components(self) -> &'static [usize] {
+        match self {
+            ChannelOrder::Rgba => &[0, 1, 2, 3],
+            ChannelOrder::Bgra => &[2, 1, 0, 3],
+            ChannelOrder::Argb => &[3, 0, 1, 2],
+            ChannelOrder::Abgr => &[3, 2, 1, 0],
+            ChannelOrder::Rgb => &[0, 1, 2],
+            ChannelOrder::Bgr => &[2, 1, 0],
+        }
+    }
+
+    /// For every channel of `self` returns channel of `src` holding the same component,
+    /// packed by 4 bits per channel, `0xF` marks alpha that `src` does not have.
+    pub(crate) const fn swizzle_from(self, src: ChannelOrder) -> usize {
+        let dst = self.components();
+        let src = src.components();
+        let mut key = 0usize;
+        let mut c = 0usize;
+        while c < dst.len() {
+            let mut position = 0xF;
+            let mut i = 0usize;
+            while i < src.len() {
+                if src[i] == dst[c] {
+                    position = i;
+                }
+                i += 1;
+            }
+            key |= position << (c * 4);
+            c += 1;
+        }
+        key
+    }
+}
+
+/// Byte shuffle of four 4 channel 8-bit pixels that reorders channels by `key` from
+/// [ChannelOrder::swizzle_from], and reverses pixels as well when `reverse` is set.
+#[allow(dead_code)]
+pub(crate) const fn swizzle_mask_u8x4(key: usize, reverse: bool) -> [u8; 16] {
+    let mut mask = [0u8; 16];
+    let mut i = 0usize;
+    while i < 16 {
+        let pixel = if reverse { 3 - i / 4 } else { i / 4 };
+        let channel = (key >> ((i % 4) * 4)) & 0xF;
+        mask[i] = (pixel * 4 + channel) as u8;
+        i += 1;
+    }
+    mask
+}
+
+/// Byte shuffle of 4 channel pixels made of `sample_size` byte samples that reorders
+/// channels of every pixel within 16 bytes by `key` from [ChannelOrder::swizzle_from].
+#[allow(dead_code)]
+pub(crate) const fn swizzle_mask_samples(key: usize, sample_size: usize) -> [u8; 16] {
+    let pixel_size = sample_size * 4;
+    let mut mask = [0u8; 16];
+    let mut i = 0usize;
+    while i < 16 {
+        let pixel = i / pixel_size;
+        let channel = (key >> ((i % pixel_size) / sample_size * 4)) & 0xF;
+        mask[i] = (pixel * pixel_size + channel * sample_size + i % sample_size) as u8;
+        i += 1;
+    }
+    mask
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+/// Source region of interest in pixels
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns view of the region inside the source image.
+    ///
+    /// The view is passed to any transpose, flip, flop or rotate180 function together with
+    /// the original `input_stride` and the region's `width` and `height`, so only the region
+    /// is transformed, without an intermediate copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `input`: Source image data
+    /// * `input_stride`: Source image stride in elements
+    /// * `width`: Source image width in pixels
+    /// * `height`: Source image height in pixels
+    /// * `channels`: Elements per pixel
+    ///
+    /// returns: Result<&[V], TransposeError>
+    ///
+    pub fn crop<'a, V>(
+        &self,
+        input: &'a [V],
+        input_stride: usize,
+        width: usize,
+        height: usize,
+        channels: usize,
+    ) -> Result<&'a [V], TransposeError> {
+        let range = self.range(input.len(), input_stride, width, height, channels)?;
+        Ok(&input[range])
+    }
+
+    /// Returns mutable view of the region inside the destination image.
+    ///
+    /// The view is passed to any transpose, flip, flop or rotate180 function as `output`
+    /// together with the original `output_stride`, so the result is composited at `(x, y)`
+    /// of a larger canvas and pixels outside the region are left untouched.
+    /// For transposes the region's `width` must be the source height and `height` the source width.
+    ///
+    /// # Arguments
+    ///
+    /// * `output`: Destination image data
+    /// * `output_stride`: Destination image stride in elements
+    /// * `width`: Destination image width in pixels
+    /// * `height`: Destination image height in pixels
+    /// * `channels`: Elements per pixel
+    ///
+    /// returns: Result<&mut [V], TransposeError>
+    ///
+    pub fn crop_mut<'a, V>(
+        &self,
+        output: &'a mut [V],
+        output_stride: usize,
+        width: usize,
+        height: usize,
+        channels: usize,
+    ) -> Result<&'a mut [V], TransposeError> {
+        let range = self.range(output.len(), output_stride, width, height, channels)?;
+        Ok(&mut output[range])
+    }
+
+    fn range(
+        &self,
+        len: usize,
+        stride: usize,
+        width: usize,
+        height: usize,
+        channels: usize,
+    ) -> Result<std::ops::Range<usize>, TransposeError> {
+        if !is_valid_image_len(len, stride, width * channels, height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if stride < width * channels {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        let right = self.x.checked_add(self.width);
+        let bottom = self.y.checked_add(self.height);
+        if right.is_none_or(|right| right > width) || bottom.is_none_or(|bottom| bottom > height) {
+            return Err(TransposeError::MismatchDimensions);
+        }
+        if self.height == 0 {
+            return Ok(0..0);
+        }
+        let start = self.y * stride + self.x * channels;
+        let end = start + (self.height - 1) * stride + self.width * channels;
+        Ok(start..end)
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Default)]
+/// Declares how transposed pixels are written to the destination
+pub enum StoreHint {
+    /// Always writes through the cache
+    #[default]
+    Temporal,
+    /// Bypasses the cache only when destination is larger than 16 MiB, a size the last level
+    /// cache is not expected to hold
+    Auto,
+    /// Always bypasses the cache where the platform supports non-temporal stores
+    NonTemporal,
+}
+
+/// Destinations above this size are not expected to stay resident in the last level cache
+const NON_TEMPORAL_THRESHOLD: usize = 16 * 1024 * 1024;
+
+#[allow(dead_code)]
+pub(crate) fn use_non_temporal_stores(store_hint: StoreHint, output_bytes: usize) -> bool {
+    match store_hint {
+        StoreHint::Auto => output_bytes > NON_TEMPORAL_THRESHOLD,
+        StoreHint::Temporal => false,
+        StoreHint::NonTemporal => true,
+    }
+}
+
+/// Checks that buffer holds `height` rows spaced `stride` apart, accepting buffers that end
+/// right after the last row's pixels, as returned by [Rect::crop].
+#[inline]
+pub(crate) fn is_valid_image_len(
+    len: usize,
+    stride: usize,
+    row_length: usize,
+    height: usize,
+) -> bool {
+    len == stride * height || (height > 0 && len == (height - 1) * stride + row_length)
+}
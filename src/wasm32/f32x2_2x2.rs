@@ -0,0 +1,69 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::wasm32::*;
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_f32x2_2x2<const FLOP: bool, const FLIP: bool>(
+    src: &[f32],
+    src_stride: usize,
+    dst: &mut [f32],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = v128_load(src.get_unchecked(0..).as_ptr() as *const v128);
+        let row1 = v128_load(src.get_unchecked(src_stride..).as_ptr() as *const v128);
+
+        let (v0, v1) = if FLIP {
+            (
+                i64x2_shuffle::<2, 0>(row0, row1),
+                i64x2_shuffle::<3, 1>(row0, row1),
+            )
+        } else {
+            (
+                i64x2_shuffle::<0, 2>(row0, row1),
+                i64x2_shuffle::<1, 3>(row0, row1),
+            )
+        };
+
+        if FLOP {
+            v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v0);
+            v128_store(
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+                v1,
+            );
+        } else {
+            v128_store(
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+                v0,
+            );
+            v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v1);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod f32x2_2x2;
+mod f32x3_4x4;
+mod f32x4_4x4;
+mod reverse;
+mod u16_4x4;
+mod u16_8x8;
+mod u8_8x8;
+mod x4_f32;
+mod x4_u32;
+
+pub(crate) use f32x2_2x2::wasm_transpose_f32x2_2x2;
+pub(crate) use f32x3_4x4::wasm_transpose_f32x3_4x4;
+pub(crate) use f32x4_4x4::{wasm_transpose_f32x4_4x4, wasm_transpose_f32x4_4x4_swizzled};
+pub(crate) use reverse::wasm_reverse_pixels;
+pub(crate) use u16_4x4::wasm_transpose_4x4_u16;
+pub(crate) use u16_8x8::wasm_transpose_8x8_u16;
+pub(crate) use u8_8x8::wasm_transpose_u8_8x8;
+pub(crate) use x4_f32::wasm_transpose_4x4_f32;
+pub(crate) use x4_u32::{wasm_transpose_4x4_u32x1, wasm_transpose_4x4_u32x1_swizzled};
@@ -0,0 +1,106 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::wasm32::*;
+
+#[inline(always)]
+fn wasm_transpose_u16_4x4_impl<const FLIP: bool>(v0: (v128, v128, v128, v128)) -> (v128, v128) {
+    // Interleave 16 bit elements. Goes from:
+    // in[0]: 00 01 02 03
+    // in[1]: 10 11 12 13
+    // in[2]: 20 21 22 23
+    // in[3]: 30 31 32 33
+    // to:
+    // a0:    00 10 01 11 02 12 03 13
+    // a1:    20 30 21 31 22 32 23 33
+    let a0 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v0.0, v0.1);
+    let a1 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v0.2, v0.3);
+
+    // Interleave 32 bit elements resulting in two output rows per register:
+    // b0: 00 10 20 30  01 11 21 31
+    // b1: 02 12 22 32  03 13 23 33
+    let b0 = i32x4_shuffle::<0, 4, 1, 5>(a0, a1);
+    let b1 = i32x4_shuffle::<2, 6, 3, 7>(a0, a1);
+
+    if FLIP {
+        (
+            i16x8_shuffle::<3, 2, 1, 0, 7, 6, 5, 4>(b0, b0),
+            i16x8_shuffle::<3, 2, 1, 0, 7, 6, 5, 4>(b1, b1),
+        )
+    } else {
+        (b0, b1)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_4x4_u16<const FLOP: bool, const FLIP: bool>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = v128_load64_zero(src.get_unchecked(0..).as_ptr() as *const u64);
+        let row1 = v128_load64_zero(src.get_unchecked(src_stride..).as_ptr() as *const u64);
+        let row2 = v128_load64_zero(src.get_unchecked(2 * src_stride..).as_ptr() as *const u64);
+        let row3 = v128_load64_zero(src.get_unchecked(3 * src_stride..).as_ptr() as *const u64);
+
+        let v0 = wasm_transpose_u16_4x4_impl::<FLIP>((row0, row1, row2, row3));
+
+        if FLOP {
+            v128_store64_lane::<0>(v0.0, dst.get_unchecked_mut(0..).as_mut_ptr() as *mut u64);
+            v128_store64_lane::<1>(
+                v0.0,
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.1,
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.1,
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+        } else {
+            v128_store64_lane::<0>(
+                v0.0,
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.0,
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.1,
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(v0.1, dst.get_unchecked_mut(0..).as_mut_ptr() as *mut u64);
+        }
+    }
+}
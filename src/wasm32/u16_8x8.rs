@@ -0,0 +1,187 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::wasm32::*;
+
+#[inline(always)]
+#[allow(clippy::type_complexity)]
+fn wasm_transpose_u16_8x8_impl<const FLIP: bool>(
+    v0: (v128, v128, v128, v128),
+    v1: (v128, v128, v128, v128),
+) -> ((v128, v128, v128, v128), (v128, v128, v128, v128)) {
+    // Interleave 16 bit elements. Goes from:
+    // in[0]: 00 01 02 03 04 05 06 07
+    // ...
+    // in[7]: 70 71 72 73 74 75 76 77
+    // to:
+    // a0: 00 10 01 11 02 12 03 13
+    // a1: 04 14 05 15 06 16 07 17
+    // a2: 20 30 21 31 22 32 23 33
+    // a3: 24 34 25 35 26 36 27 37
+    // and the same for rows 4..7 in a4..a7.
+    let a0 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v0.0, v0.1);
+    let a1 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(v0.0, v0.1);
+    let a2 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v0.2, v0.3);
+    let a3 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(v0.2, v0.3);
+    let a4 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v1.0, v1.1);
+    let a5 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(v1.0, v1.1);
+    let a6 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(v1.2, v1.3);
+    let a7 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(v1.2, v1.3);
+
+    // Interleave 32 bit elements resulting in:
+    // b0: 00 10 20 30 01 11 21 31
+    // b1: 02 12 22 32 03 13 23 33
+    // b2: 04 14 24 34 05 15 25 35
+    // b3: 06 16 26 36 07 17 27 37
+    // and the same for rows 4..7 in b4..b7.
+    let b0 = i32x4_shuffle::<0, 4, 1, 5>(a0, a2);
+    let b1 = i32x4_shuffle::<2, 6, 3, 7>(a0, a2);
+    let b2 = i32x4_shuffle::<0, 4, 1, 5>(a1, a3);
+    let b3 = i32x4_shuffle::<2, 6, 3, 7>(a1, a3);
+    let b4 = i32x4_shuffle::<0, 4, 1, 5>(a4, a6);
+    let b5 = i32x4_shuffle::<2, 6, 3, 7>(a4, a6);
+    let b6 = i32x4_shuffle::<0, 4, 1, 5>(a5, a7);
+    let b7 = i32x4_shuffle::<2, 6, 3, 7>(a5, a7);
+
+    // Interleave 64 bit elements resulting in:
+    // out[0]: 00 10 20 30 40 50 60 70
+    // ...
+    // out[7]: 07 17 27 37 47 57 67 77
+    let r0 = i64x2_shuffle::<0, 2>(b0, b4);
+    let r1 = i64x2_shuffle::<1, 3>(b0, b4);
+    let r2 = i64x2_shuffle::<0, 2>(b1, b5);
+    let r3 = i64x2_shuffle::<1, 3>(b1, b5);
+    let r4 = i64x2_shuffle::<0, 2>(b2, b6);
+    let r5 = i64x2_shuffle::<1, 3>(b2, b6);
+    let r6 = i64x2_shuffle::<0, 2>(b3, b7);
+    let r7 = i64x2_shuffle::<1, 3>(b3, b7);
+
+    if FLIP {
+        (
+            (
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r0, r0),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r1, r1),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r2, r2),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r3, r3),
+            ),
+            (
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r4, r4),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r5, r5),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r6, r6),
+                i16x8_shuffle::<7, 6, 5, 4, 3, 2, 1, 0>(r7, r7),
+            ),
+        )
+    } else {
+        ((r0, r1, r2, r3), (r4, r5, r6, r7))
+    }
+}
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_8x8_u16<const FLOP: bool, const FLIP: bool>(
+    src: &[u16],
+    src_stride: usize,
+    dst: &mut [u16],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = v128_load(src.get_unchecked(0..).as_ptr() as *const v128);
+        let row1 = v128_load(src.get_unchecked(src_stride..).as_ptr() as *const v128);
+        let row2 = v128_load(src.get_unchecked(2 * src_stride..).as_ptr() as *const v128);
+        let row3 = v128_load(src.get_unchecked(3 * src_stride..).as_ptr() as *const v128);
+        let row4 = v128_load(src.get_unchecked(4 * src_stride..).as_ptr() as *const v128);
+        let row5 = v128_load(src.get_unchecked(5 * src_stride..).as_ptr() as *const v128);
+        let row6 = v128_load(src.get_unchecked(6 * src_stride..).as_ptr() as *const v128);
+        let row7 = v128_load(src.get_unchecked(7 * src_stride..).as_ptr() as *const v128);
+
+        let (v0, v1) =
+            wasm_transpose_u16_8x8_impl::<FLIP>((row0, row1, row2, row3), (row4, row5, row6, row7));
+
+        if FLOP {
+            v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v0.0);
+            v128_store(
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+                v0.1,
+            );
+            v128_store(
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.2,
+            );
+            v128_store(
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.3,
+            );
+            v128_store(
+                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.0,
+            );
+            v128_store(
+                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.1,
+            );
+            v128_store(
+                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.2,
+            );
+            v128_store(
+                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.3,
+            );
+        } else {
+            v128_store(
+                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.0,
+            );
+            v128_store(
+                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.1,
+            );
+            v128_store(
+                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.2,
+            );
+            v128_store(
+                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut v128,
+                v0.3,
+            );
+            v128_store(
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.0,
+            );
+            v128_store(
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut v128,
+                v1.1,
+            );
+            v128_store(
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+                v1.2,
+            );
+            v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v1.3);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::arch::wasm32::*;
+
+#[inline(always)]
+fn wasm_transpose_8x8_impl<const FLIP: bool>(
+    v0: (v128, v128, v128, v128),
+    v1: (v128, v128, v128, v128),
+) -> (v128, v128, v128, v128) {
+    // Interleave 8 bit elements. Goes from:
+    // in[0]: 00 01 02 03 04 05 06 07
+    // in[1]: 10 11 12 13 14 15 16 17
+    // ...
+    // in[7]: 70 71 72 73 74 75 76 77
+    // to:
+    // a0:    00 10 01 11 02 12 03 13  04 14 05 15 06 16 07 17
+    // a1:    20 30 21 31 22 32 23 33  24 34 25 35 26 36 27 37
+    // a2:    40 50 41 51 42 52 43 53  44 54 45 55 46 56 47 57
+    // a3:    60 70 61 71 62 72 63 73  64 74 65 75 66 76 67 77
+    let a0 = i8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(v0.0, v0.1);
+    let a1 = i8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(v0.2, v0.3);
+    let a2 = i8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(v1.0, v1.1);
+    let a3 = i8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(v1.2, v1.3);
+
+    // Interleave 16 bit elements resulting in:
+    // b0: 00 10 20 30 01 11 21 31  02 12 22 32 03 13 23 33
+    // b1: 04 14 24 34 05 15 25 35  06 16 26 36 07 17 27 37
+    // b2: 40 50 60 70 41 51 61 71  42 52 62 72 43 53 63 73
+    // b3: 44 54 64 74 45 55 65 75  46 56 66 76 47 57 67 77
+    let b0 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(a0, a1);
+    let b1 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(a0, a1);
+    let b2 = i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(a2, a3);
+    let b3 = i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(a2, a3);
+
+    // Interleave 32 bit elements resulting in two output rows per register:
+    // c0: 00 10 20 30 40 50 60 70  01 11 21 31 41 51 61 71
+    // c1: 02 12 22 32 42 52 62 72  03 13 23 33 43 53 63 73
+    // c2: 04 14 24 34 44 54 64 74  05 15 25 35 45 55 65 75
+    // c3: 06 16 26 36 46 56 66 76  07 17 27 37 47 57 67 77
+    let c0 = i32x4_shuffle::<0, 4, 1, 5>(b0, b2);
+    let c1 = i32x4_shuffle::<2, 6, 3, 7>(b0, b2);
+    let c2 = i32x4_shuffle::<0, 4, 1, 5>(b1, b3);
+    let c3 = i32x4_shuffle::<2, 6, 3, 7>(b1, b3);
+
+    if FLIP {
+        (
+            i8x16_shuffle::<7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8>(c0, c0),
+            i8x16_shuffle::<7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8>(c1, c1),
+            i8x16_shuffle::<7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8>(c2, c2),
+            i8x16_shuffle::<7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8>(c3, c3),
+        )
+    } else {
+        (c0, c1, c2, c3)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_u8_8x8<const FLOP: bool, const FLIP: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let row0 = v128_load64_zero(src.get_unchecked(0..).as_ptr() as *const u64);
+        let row1 = v128_load64_zero(src.get_unchecked(src_stride..).as_ptr() as *const u64);
+        let row2 = v128_load64_zero(src.get_unchecked(2 * src_stride..).as_ptr() as *const u64);
+        let row3 = v128_load64_zero(src.get_unchecked(3 * src_stride..).as_ptr() as *const u64);
+        let row4 = v128_load64_zero(src.get_unchecked(4 * src_stride..).as_ptr() as *const u64);
+        let row5 = v128_load64_zero(src.get_unchecked(5 * src_stride..).as_ptr() as *const u64);
+        let row6 = v128_load64_zero(src.get_unchecked(6 * src_stride..).as_ptr() as *const u64);
+        let row7 = v128_load64_zero(src.get_unchecked(7 * src_stride..).as_ptr() as *const u64);
+
+        let v0 =
+            wasm_transpose_8x8_impl::<FLIP>((row0, row1, row2, row3), (row4, row5, row6, row7));
+
+        if FLOP {
+            v128_store64_lane::<0>(v0.0, dst.get_unchecked_mut(0..).as_mut_ptr() as *mut u64);
+            v128_store64_lane::<1>(
+                v0.0,
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.1,
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.1,
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.2,
+                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.2,
+                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.3,
+                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.3,
+                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+        } else {
+            v128_store64_lane::<0>(
+                v0.0,
+                dst.get_unchecked_mut(7 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.0,
+                dst.get_unchecked_mut(6 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.1,
+                dst.get_unchecked_mut(5 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.1,
+                dst.get_unchecked_mut(4 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.2,
+                dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(
+                v0.2,
+                dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<0>(
+                v0.3,
+                dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut u64,
+            );
+            v128_store64_lane::<1>(v0.3, dst.get_unchecked_mut(0..).as_mut_ptr() as *mut u64);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::utils::swizzle_mask_u8x4;
+use std::arch::wasm32::*;
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_4x4_impl<const FLIP: bool>(
+    v0: (v128, v128, v128, v128),
+) -> (v128, v128, v128, v128) {
+    // Interleave 32 bit elements. Goes from:
+    // in[0]: 00 01 02 03
+    // in[1]: 10 11 12 13
+    // in[2]: 20 21 22 23
+    // in[3]: 30 31 32 33
+    // to:
+    // a0:    00 10 01 11
+    // a1:    20 30 21 31
+    // a2:    02 12 03 13
+    // a3:    22 32 23 33
+    let a0 = i32x4_shuffle::<0, 4, 1, 5>(v0.0, v0.1);
+    let a1 = i32x4_shuffle::<0, 4, 1, 5>(v0.2, v0.3);
+    let a2 = i32x4_shuffle::<2, 6, 3, 7>(v0.0, v0.1);
+    let a3 = i32x4_shuffle::<2, 6, 3, 7>(v0.2, v0.3);
+
+    // Interleave 64 bit elements resulting in:
+    // out[0]: 00 10 20 30
+    // out[1]: 01 11 21 31
+    // out[2]: 02 12 22 32
+    // out[3]: 03 13 23 33
+    let r0 = i64x2_shuffle::<0, 2>(a0, a1);
+    let r1 = i64x2_shuffle::<1, 3>(a0, a1);
+    let r2 = i64x2_shuffle::<0, 2>(a2, a3);
+    let r3 = i64x2_shuffle::<1, 3>(a2, a3);
+
+    if FLIP {
+        (
+            i32x4_shuffle::<3, 2, 1, 0>(r0, r0),
+            i32x4_shuffle::<3, 2, 1, 0>(r1, r1),
+            i32x4_shuffle::<3, 2, 1, 0>(r2, r2),
+            i32x4_shuffle::<3, 2, 1, 0>(r3, r3),
+        )
+    } else {
+        (r0, r1, r2, r3)
+    }
+}
+
+#[inline(always)]
+unsafe fn wasm_load_4x4_u32x1(src: &[u8], src_stride: usize) -> (v128, v128, v128, v128) {
+    (
+        v128_load(src.get_unchecked(0..).as_ptr() as *const v128),
+        v128_load(src.get_unchecked(src_stride..).as_ptr() as *const v128),
+        v128_load(src.get_unchecked(2 * src_stride..).as_ptr() as *const v128),
+        v128_load(src.get_unchecked(3 * src_stride..).as_ptr() as *const v128),
+    )
+}
+
+#[inline(always)]
+unsafe fn wasm_store_4x4_u32x1<const FLOP: bool>(
+    dst: &mut [u8],
+    dst_stride: usize,
+    v0: (v128, v128, v128, v128),
+) {
+    if FLOP {
+        v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v0.0);
+        v128_store(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+            v0.1,
+        );
+        v128_store(
+            dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut v128,
+            v0.2,
+        );
+        v128_store(
+            dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut v128,
+            v0.3,
+        );
+    } else {
+        v128_store(
+            dst.get_unchecked_mut(3 * dst_stride..).as_mut_ptr() as *mut v128,
+            v0.0,
+        );
+        v128_store(
+            dst.get_unchecked_mut(2 * dst_stride..).as_mut_ptr() as *mut v128,
+            v0.1,
+        );
+        v128_store(
+            dst.get_unchecked_mut(dst_stride..).as_mut_ptr() as *mut v128,
+            v0.2,
+        );
+        v128_store(dst.get_unchecked_mut(0..).as_mut_ptr() as *mut v128, v0.3);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn wasm_transpose_4x4_u32x1<const FLOP: bool, const FLIP: bool>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let v0 = wasm_transpose_4x4_impl::<FLIP>(wasm_load_4x4_u32x1(src, src_stride));
+        wasm_store_4x4_u32x1::<FLOP>(dst, dst_stride, v0);
+    }
+}
+
+/// Same as [wasm_transpose_4x4_u32x1] but also reorders channels of every 4 channel 8-bit
+/// pixel by `KEY` from [crate::ChannelOrder], the reorder shares the flip swizzle.
+#[inline(always)]
+pub(crate) fn wasm_transpose_4x4_u32x1_swizzled<
+    const FLOP: bool,
+    const FLIP: bool,
+    const KEY: usize,
+>(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+) {
+    unsafe {
+        let v0 = wasm_transpose_4x4_impl::<false>(wasm_load_4x4_u32x1(src, src_stride));
+        let mask = const { swizzle_mask_u8x4(KEY, FLIP) };
+        let mask = v128_load(mask.as_ptr() as *const v128);
+        wasm_store_4x4_u32x1::<FLOP>(
+            dst,
+            dst_stride,
+            (
+                i8x16_swizzle(v0.0, mask),
+                i8x16_swizzle(v0.1, mask),
+                i8x16_swizzle(v0.2, mask),
+                i8x16_swizzle(v0.3, mask),
+            ),
+        );
+    }
+}
@@ -0,0 +1,1025 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![forbid(unsafe_code)]
+use crate::convert::{u16_transposer, u8_transposer, TileTransposer};
+use crate::utils::is_valid_image_len;
+use crate::{
+    flip_plane, flip_plane16, flip_plane16_with_alpha, flip_plane_with_alpha, flop_plane,
+    flop_plane16, flop_plane16_with_alpha, flop_plane_with_alpha, rotate180_plane,
+    rotate180_plane16, rotate180_plane16_with_alpha, rotate180_plane_with_alpha, Orientation,
+    TransposeError,
+};
+use roxygen::roxygen;
+
+type PlaneMirror<V> = fn(&[V], usize, &mut [V], usize, usize, usize) -> Result<(), TransposeError>;
+
+/// Sample of a YUV plane with plane kernels for one and two channel (interleaved chroma) layouts
+pub(crate) trait YuvSample: Copy + Default + 'static {
+    fn transposer<const CN: usize>() -> TileTransposer<Self>;
+    fn flipper<const CN: usize>() -> PlaneMirror<Self>;
+    fn flopper<const CN: usize>() -> PlaneMirror<Self>;
+    fn rotator<const CN: usize>() -> PlaneMirror<Self>;
+    /// Rounded mean of two samples
+    fn average(a: Self, b: Self) -> Self;
+}
+
+impl YuvSample for u8 {
+    fn transposer<const CN: usize>() -> TileTransposer<Self> {
+        u8_transposer::<CN>()
+    }
+
+    fn flipper<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            flip_plane
+        } else {
+            flip_plane_with_alpha
+        }
+    }
+
+    fn flopper<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            flop_plane
+        } else {
+            flop_plane_with_alpha
+        }
+    }
+
+    fn rotator<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            rotate180_plane
+        } else {
+            rotate180_plane_with_alpha
+        }
+    }
+
+    #[inline(always)]
+    fn average(a: Self, b: Self) -> Self {
+        ((a as u16 + b as u16 + 1) >> 1) as u8
+    }
+}
+
+impl YuvSample for u16 {
+    fn transposer<const CN: usize>() -> TileTransposer<Self> {
+        u16_transposer::<CN>()
+    }
+
+    fn flipper<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            flip_plane16
+        } else {
+            flip_plane16_with_alpha
+        }
+    }
+
+    fn flopper<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            flop_plane16
+        } else {
+            flop_plane16_with_alpha
+        }
+    }
+
+    fn rotator<const CN: usize>() -> PlaneMirror<Self> {
+        if CN == 1 {
+            rotate180_plane16
+        } else {
+            rotate180_plane16_with_alpha
+        }
+    }
+
+    #[inline(always)]
+    fn average(a: Self, b: Self) -> Self {
+        ((a as u32 + b as u32 + 1) >> 1) as u16
+    }
+}
+
+pub(crate) fn copy_plane<V: Copy>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), TransposeError> {
+    if !is_valid_image_len(input.len(), input_stride, width, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if !is_valid_image_len(output.len(), output_stride, width, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if input_stride < width || output_stride < width {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    for (dst, src) in output
+        .chunks_mut(output_stride)
+        .zip(input.chunks(input_stride))
+    {
+        dst[..width].copy_from_slice(&src[..width]);
+    }
+    Ok(())
+}
+
+/// Size of a `width` x `height` plane after `orientation`
+pub(crate) const fn oriented_size(
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> (usize, usize) {
+    if orientation.is_transposed() {
+        (height, width)
+    } else {
+        (width, height)
+    }
+}
+
+/// Checks length and stride of a plane holding `height` rows of `row_length` samples
+pub(crate) fn check_plane<V>(
+    plane: &[V],
+    stride: usize,
+    row_length: usize,
+    height: usize,
+) -> Result<(), TransposeError> {
+    if !is_valid_image_len(plane.len(), stride, row_length, height) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if stride < row_length {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    Ok(())
+}
+
+/// Checks lengths and strides of a source plane of `width` x `height` pixels and of its
+/// destination plane of `dst_width` x `dst_height` pixels, with `CN` samples per pixel
+pub(crate) fn check_planes<V, const CN: usize>(
+    input: &[V],
+    input_stride: usize,
+    output: &[V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    dst_width: usize,
+    dst_height: usize,
+) -> Result<(), TransposeError> {
+    check_plane(input, input_stride, width * CN, height)?;
+    check_plane(output, output_stride, dst_width * CN, dst_height)
+}
+
+/// Checks a plane of `width` x `height` pixels and its destination for [orient_plane],
+/// so frames are rejected before any of their planes is written
+pub(crate) fn check_oriented_plane<V, const CN: usize>(
+    input: &[V],
+    input_stride: usize,
+    output: &[V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    check_planes::<V, CN>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width,
+        height,
+        dst_width,
+        dst_height,
+    )
+}
+
+/// Reorients a plane of `width` x `height` pixels with `CN` samples each
+pub(crate) fn orient_plane<V: YuvSample, const CN: usize>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    if let Some((flip_mode, flop_mode)) = orientation.transpose_modes() {
+        return V::transposer::<CN>()(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            width,
+            height,
+            flip_mode,
+            flop_mode,
+        );
+    }
+    let mirror = match orientation {
+        Orientation::MirrorHorizontal => V::flipper::<CN>(),
+        Orientation::MirrorVertical => V::flopper::<CN>(),
+        Orientation::Rotate180 => V::rotator::<CN>(),
+        _ => {
+            return copy_plane(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width * CN,
+                height,
+            )
+        }
+    };
+    mirror(input, input_stride, output, output_stride, width, height)
+}
+
+/// Reorients a 4:2:2 chroma plane keeping it 4:2:2.
+///
+/// Transposing swaps subsampled axis, so the transposed plane is halved horizontally
+/// by averaging sample pairs and doubled vertically by repeating rows.
+pub(crate) fn orient_chroma_422<V: YuvSample>(
+    input: &[V],
+    input_stride: usize,
+    output: &mut [V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    let chroma_width = width.div_ceil(2);
+    if !orientation.is_transposed() {
+        return orient_plane::<V, 1>(
+            input,
+            input_stride,
+            output,
+            output_stride,
+            chroma_width,
+            height,
+            orientation,
+        );
+    }
+
+    let dst_width = height.div_ceil(2);
+    if !is_valid_image_len(output.len(), output_stride, dst_width, width) {
+        return Err(TransposeError::MismatchDimensions);
+    }
+    if output_stride < dst_width {
+        return Err(TransposeError::MismatchDimensions);
+    }
+
+    // Chroma transposed as is is `height` wide and `chroma_width` tall, i.e. 4:4:0
+    let mut rotated = vec![V::default(); height * chroma_width];
+    orient_plane::<V, 1>(
+        input,
+        input_stride,
+        &mut rotated,
+        height,
+        chroma_width,
+        height,
+        orientation,
+    )?;
+
+    resample_440_to_422(
+        &rotated,
+        height,
+        output,
+        output_stride,
+        dst_width,
+        chroma_row_phase(width, orientation),
+    );
+    Ok(())
+}
+
+/// Checks a 4:2:2 chroma plane of a `width` x `height` frame and its destination for
+/// [orient_chroma_422]
+pub(crate) fn check_chroma_422<V>(
+    input: &[V],
+    input_stride: usize,
+    output: &[V],
+    output_stride: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    let (dst_width, dst_height) = if orientation.is_transposed() {
+        (height.div_ceil(2), width)
+    } else {
+        (width.div_ceil(2), height)
+    };
+    check_planes::<V, 1>(
+        input,
+        input_stride,
+        output,
+        output_stride,
+        width.div_ceil(2),
+        height,
+        dst_width,
+        dst_height,
+    )
+}
+
+/// Row phase of transposed 4:4:0 chroma of a `width` pixels wide frame for [resample_440_to_422].
+///
+/// Odd widths have a lone last column in the final chroma pair, which becomes the first
+/// destination row when x is reversed, so pairs of destination rows start one row later.
+pub(crate) const fn chroma_row_phase(width: usize, orientation: Orientation) -> usize {
+    if orientation.reversed_axes().0 {
+        width & 1
+    } else {
+        0
+    }
+}
+
+/// Resamples tightly packed 4:4:0 chroma of `src_width` samples per row to 4:2:2 of
+/// `dst_width` samples per row by averaging horizontal pairs and repeating each row twice,
+/// `row_phase` of 1 repeats the first row once, for rows paired from the bottom.
+pub(crate) fn resample_440_to_422<V: YuvSample>(
+    src: &[V],
+    src_width: usize,
+    output: &mut [V],
+    output_stride: usize,
+    dst_width: usize,
+    row_phase: usize,
+) {
+    for (y, dst) in output.chunks_mut(output_stride).enumerate() {
+        let src_y = (y + row_phase) / 2;
+        let src = &src[src_y * src_width..(src_y + 1) * src_width];
+        for (dst, pair) in dst[..dst_width].iter_mut().zip(src.chunks(2)) {
+            *dst = V::average(pair[0], *pair.last().unwrap());
+        }
+    }
+}
+
+/// Reorients three plane frame with chroma planes of `chroma_width` x `chroma_height`
+fn rotate_planar<V: YuvSample>(
+    src_y: &[V],
+    src_stride_y: usize,
+    src_u: &[V],
+    src_stride_u: usize,
+    src_v: &[V],
+    src_stride_v: usize,
+    dst_y: &mut [V],
+    dst_stride_y: usize,
+    dst_u: &mut [V],
+    dst_stride_u: usize,
+    dst_v: &mut [V],
+    dst_stride_v: usize,
+    width: usize,
+    height: usize,
+    chroma_width: usize,
+    chroma_height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_oriented_plane::<V, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    check_oriented_plane::<V, 1>(
+        src_u,
+        src_stride_u,
+        dst_u,
+        dst_stride_u,
+        chroma_width,
+        chroma_height,
+        orientation,
+    )?;
+    check_oriented_plane::<V, 1>(
+        src_v,
+        src_stride_v,
+        dst_v,
+        dst_stride_v,
+        chroma_width,
+        chroma_height,
+        orientation,
+    )?;
+    orient_plane::<V, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_plane::<V, 1>(
+        src_u,
+        src_stride_u,
+        dst_u,
+        dst_stride_u,
+        chroma_width,
+        chroma_height,
+        orientation,
+    )?;
+    orient_plane::<V, 1>(
+        src_v,
+        src_stride_v,
+        dst_v,
+        dst_stride_v,
+        chroma_width,
+        chroma_height,
+        orientation,
+    )
+}
+
+/// Reorients luma and interleaved 4:2:0 chroma planes
+fn rotate_biplanar<V: YuvSample>(
+    src_y: &[V],
+    src_stride_y: usize,
+    src_uv: &[V],
+    src_stride_uv: usize,
+    dst_y: &mut [V],
+    dst_stride_y: usize,
+    dst_uv: &mut [V],
+    dst_stride_uv: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_oriented_plane::<V, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    check_oriented_plane::<V, 2>(
+        src_uv,
+        src_stride_uv,
+        dst_uv,
+        dst_stride_uv,
+        width.div_ceil(2),
+        height.div_ceil(2),
+        orientation,
+    )?;
+    orient_plane::<V, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_plane::<V, 2>(
+        src_uv,
+        src_stride_uv,
+        dst_uv,
+        dst_stride_uv,
+        width.div_ceil(2),
+        height.div_ceil(2),
+        orientation,
+    )
+}
+
+/// Reorients an I420 (YUV 4:2:0 planar) frame.
+///
+/// Chroma planes are `width.div_ceil(2)` x `height.div_ceil(2)` in the source,
+/// and `height.div_ceil(2)` x `width.div_ceil(2)` in the destination when `orientation` is transposed.
+#[roxygen]
+pub fn rotate_i420(
+    /// Source luma plane.
+    src_y: &[u8],
+    /// Number of bytes per row in `src_y`.
+    src_stride_y: usize,
+    /// Source U plane.
+    src_u: &[u8],
+    /// Number of bytes per row in `src_u`.
+    src_stride_u: usize,
+    /// Source V plane.
+    src_v: &[u8],
+    /// Number of bytes per row in `src_v`.
+    src_stride_v: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_planar::<u8>(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        width.div_ceil(2),
+        height.div_ceil(2),
+        orientation,
+    )
+}
+
+/// Reorients an NV12 or NV21 (YUV 4:2:0 with interleaved chroma) frame.
+///
+/// Chroma plane holds `width.div_ceil(2)` x `height.div_ceil(2)` sample pairs in the source,
+/// pairs are moved together so UV and VU order are both preserved.
+#[roxygen]
+pub fn rotate_nv12(
+    /// Source luma plane.
+    src_y: &[u8],
+    /// Number of bytes per row in `src_y`.
+    src_stride_y: usize,
+    /// Source interleaved chroma plane.
+    src_uv: &[u8],
+    /// Number of bytes per row in `src_uv`.
+    src_stride_uv: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination interleaved chroma plane.
+    dst_uv: &mut [u8],
+    /// Number of bytes per row in `dst_uv`.
+    dst_stride_uv: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_biplanar::<u8>(
+        src_y,
+        src_stride_y,
+        src_uv,
+        src_stride_uv,
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients an I422 (YUV 4:2:2 planar) frame keeping it 4:2:2.
+///
+/// Chroma planes are `width.div_ceil(2)` x `height` in the source. Transposing orientations
+/// would turn chroma into 4:4:0, so it is resampled to `height.div_ceil(2)` x `width`
+/// by averaging horizontal pairs and repeating rows.
+#[roxygen]
+pub fn rotate_i422(
+    /// Source luma plane.
+    src_y: &[u8],
+    /// Number of bytes per row in `src_y`.
+    src_stride_y: usize,
+    /// Source U plane.
+    src_u: &[u8],
+    /// Number of bytes per row in `src_u`.
+    src_stride_u: usize,
+    /// Source V plane.
+    src_v: &[u8],
+    /// Number of bytes per row in `src_v`.
+    src_stride_v: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    check_oriented_plane::<u8, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    check_chroma_422(
+        src_u,
+        src_stride_u,
+        dst_u,
+        dst_stride_u,
+        width,
+        height,
+        orientation,
+    )?;
+    check_chroma_422(
+        src_v,
+        src_stride_v,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_plane::<u8, 1>(
+        src_y,
+        src_stride_y,
+        dst_y,
+        dst_stride_y,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_chroma_422(
+        src_u,
+        src_stride_u,
+        dst_u,
+        dst_stride_u,
+        width,
+        height,
+        orientation,
+    )?;
+    orient_chroma_422(
+        src_v,
+        src_stride_v,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients an I444 (YUV 4:4:4 planar) frame.
+#[roxygen]
+pub fn rotate_i444(
+    /// Source luma plane.
+    src_y: &[u8],
+    /// Number of bytes per row in `src_y`.
+    src_stride_y: usize,
+    /// Source U plane.
+    src_u: &[u8],
+    /// Number of bytes per row in `src_u`.
+    src_stride_u: usize,
+    /// Source V plane.
+    src_v: &[u8],
+    /// Number of bytes per row in `src_v`.
+    src_stride_v: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u8],
+    /// Number of bytes per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u8],
+    /// Number of bytes per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u8],
+    /// Number of bytes per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_planar::<u8>(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a 4:2:0 frame of 16-bit samples with interleaved chroma, for P010 and P016
+/// alike since samples are moved whatever bits they use
+fn rotate_p01x(
+    src_y: &[u16],
+    src_stride_y: usize,
+    src_uv: &[u16],
+    src_stride_uv: usize,
+    dst_y: &mut [u16],
+    dst_stride_y: usize,
+    dst_uv: &mut [u16],
+    dst_stride_uv: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_biplanar::<u16>(
+        src_y,
+        src_stride_y,
+        src_uv,
+        src_stride_uv,
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a 4:2:0 planar frame of 16-bit samples, for I010 and I012 alike since
+/// samples are moved whatever bits they use
+fn rotate_i01x(
+    src_y: &[u16],
+    src_stride_y: usize,
+    src_u: &[u16],
+    src_stride_u: usize,
+    src_v: &[u16],
+    src_stride_v: usize,
+    dst_y: &mut [u16],
+    dst_stride_y: usize,
+    dst_u: &mut [u16],
+    dst_stride_u: usize,
+    dst_v: &mut [u16],
+    dst_stride_v: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_planar::<u16>(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        width.div_ceil(2),
+        height.div_ceil(2),
+        orientation,
+    )
+}
+
+/// Reorients a P010 (10-bit YUV 4:2:0 with interleaved chroma, MSB aligned) frame.
+///
+/// Samples are moved intact, so MSB alignment is preserved. Chroma plane holds
+/// `width.div_ceil(2)` x `height.div_ceil(2)` sample pairs in the source.
+#[roxygen]
+pub fn rotate_p010(
+    /// Source luma plane.
+    src_y: &[u16],
+    /// Number of u16 elements per row in `src_y`.
+    src_stride_y: usize,
+    /// Source interleaved chroma plane.
+    src_uv: &[u16],
+    /// Number of u16 elements per row in `src_uv`.
+    src_stride_uv: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u16],
+    /// Number of u16 elements per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination interleaved chroma plane.
+    dst_uv: &mut [u16],
+    /// Number of u16 elements per row in `dst_uv`.
+    dst_stride_uv: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_p01x(
+        src_y,
+        src_stride_y,
+        src_uv,
+        src_stride_uv,
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients a P016 (16-bit YUV 4:2:0 with interleaved chroma) frame.
+///
+/// Chroma plane holds `width.div_ceil(2)` x `height.div_ceil(2)` sample pairs in the source.
+#[roxygen]
+pub fn rotate_p016(
+    /// Source luma plane.
+    src_y: &[u16],
+    /// Number of u16 elements per row in `src_y`.
+    src_stride_y: usize,
+    /// Source interleaved chroma plane.
+    src_uv: &[u16],
+    /// Number of u16 elements per row in `src_uv`.
+    src_stride_uv: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u16],
+    /// Number of u16 elements per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination interleaved chroma plane.
+    dst_uv: &mut [u16],
+    /// Number of u16 elements per row in `dst_uv`.
+    dst_stride_uv: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_p01x(
+        src_y,
+        src_stride_y,
+        src_uv,
+        src_stride_uv,
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients an I010 (10-bit YUV 4:2:0 planar, LSB aligned) frame.
+///
+/// Samples are moved intact, so LSB alignment is preserved. Chroma planes are
+/// `width.div_ceil(2)` x `height.div_ceil(2)` in the source, and `height.div_ceil(2)` x
+/// `width.div_ceil(2)` in the destination when `orientation` is transposed.
+#[roxygen]
+pub fn rotate_i010(
+    /// Source luma plane.
+    src_y: &[u16],
+    /// Number of u16 elements per row in `src_y`.
+    src_stride_y: usize,
+    /// Source U plane.
+    src_u: &[u16],
+    /// Number of u16 elements per row in `src_u`.
+    src_stride_u: usize,
+    /// Source V plane.
+    src_v: &[u16],
+    /// Number of u16 elements per row in `src_v`.
+    src_stride_v: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u16],
+    /// Number of u16 elements per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u16],
+    /// Number of u16 elements per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u16],
+    /// Number of u16 elements per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_i01x(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
+
+/// Reorients an I012 (12-bit YUV 4:2:0 planar, LSB aligned) frame.
+///
+/// Samples are moved intact, so LSB alignment is preserved. Chroma planes are
+/// `width.div_ceil(2)` x `height.div_ceil(2)` in the source, and `height.div_ceil(2)` x
+/// `width.div_ceil(2)` in the destination when `orientation` is transposed.
+#[roxygen]
+pub fn rotate_i012(
+    /// Source luma plane.
+    src_y: &[u16],
+    /// Number of u16 elements per row in `src_y`.
+    src_stride_y: usize,
+    /// Source U plane.
+    src_u: &[u16],
+    /// Number of u16 elements per row in `src_u`.
+    src_stride_u: usize,
+    /// Source V plane.
+    src_v: &[u16],
+    /// Number of u16 elements per row in `src_v`.
+    src_stride_v: usize,
+    /// Destination luma plane.
+    dst_y: &mut [u16],
+    /// Number of u16 elements per row in `dst_y`.
+    dst_stride_y: usize,
+    /// Destination U plane.
+    dst_u: &mut [u16],
+    /// Number of u16 elements per row in `dst_u`.
+    dst_stride_u: usize,
+    /// Destination V plane.
+    dst_v: &mut [u16],
+    /// Number of u16 elements per row in `dst_v`.
+    dst_stride_v: usize,
+    /// Width of the source luma plane in pixels.
+    width: usize,
+    /// Height of the source luma plane in pixels.
+    height: usize,
+    /// Target orientation.
+    orientation: Orientation,
+) -> Result<(), TransposeError> {
+    rotate_i01x(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        orientation,
+    )
+}
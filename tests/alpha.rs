@@ -0,0 +1,363 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    random_samples, reference_mirror, reference_transpose, FLIP_MODES, FLOP_MODES, ROW_WIDTHS,
+    SIZES,
+};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+type AlphaTransposer<'a, V> = &'a dyn Fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    FlipMode,
+    FlopMode,
+) -> Result<(), TransposeError>;
+type AlphaMirror<'a, V> =
+    &'a dyn Fn(&[V], usize, &mut [V], usize, usize, usize) -> Result<(), TransposeError>;
+
+fn map_pixels<V: Copy>(samples: Vec<V>, pixel: &impl Fn([V; 4]) -> [V; 4]) -> Vec<V> {
+    samples
+        .chunks_exact(4)
+        .flat_map(|px| pixel([px[0], px[1], px[2], px[3]]))
+        .collect()
+}
+
+/// Compares transpose and every mirror with alpha applied against geometry of
+/// [reference_transpose] and [reference_mirror] followed by `pixel`.
+fn check_alpha<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    pixel: impl Fn([V; 4]) -> [V; 4],
+    transposer: AlphaTransposer<V>,
+    mirrors: [(AlphaMirror<V>, bool, bool); 3],
+) {
+    for width in SIZES {
+        for height in SIZES {
+            let input_stride = (width + 1) * 4;
+            let input = random_samples(
+                input_stride * height,
+                (width * 131 + height) as u32,
+                &sample,
+            );
+            for flip_mode in FLIP_MODES {
+                for flop_mode in FLOP_MODES {
+                    let output_stride = height * 4;
+                    let mut output = vec![V::default(); output_stride * width];
+                    transposer(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                    )
+                    .unwrap();
+                    let reference = map_pixels(
+                        reference_transpose(
+                            &input,
+                            input_stride,
+                            width,
+                            height,
+                            4,
+                            flip_mode,
+                            flop_mode,
+                        ),
+                        &pixel,
+                    );
+                    assert!(
+                        output == reference,
+                        "{width}x{height}, {flip_mode:?}, {flop_mode:?}"
+                    );
+                }
+            }
+        }
+    }
+    for (mirror, reverse_x, reverse_y) in mirrors {
+        for width in ROW_WIDTHS {
+            for height in [1, 2, 3, 8] {
+                let input_stride = (width + 1) * 4;
+                let input =
+                    random_samples(input_stride * height, (width * 7 + height) as u32, &sample);
+                let output_stride = width * 4;
+                let mut output = vec![V::default(); output_stride * height];
+                mirror(
+                    &input,
+                    input_stride,
+                    &mut output,
+                    output_stride,
+                    width,
+                    height,
+                )
+                .unwrap();
+                let reference = map_pixels(
+                    reference_mirror(&input, input_stride, width, height, 4, reverse_x, reverse_y),
+                    &pixel,
+                );
+                assert!(
+                    output == reference,
+                    "{width}x{height}, reverse x {reverse_x}, reverse y {reverse_y}"
+                );
+            }
+        }
+    }
+}
+
+/// Samples within `max` biased toward zero and `max`, so transparent, opaque and
+/// colors above alpha are all frequent.
+fn biased_sample(v: u32, max: u32) -> u32 {
+    match v % 8 {
+        0 => 0,
+        1 => max,
+        _ => (v >> 3) % (max + 1),
+    }
+}
+
+fn premultiplied(c: u64, a: u64, max: u64) -> u64 {
+    (2 * c * a + max) / (2 * max)
+}
+
+fn unpremultiplied(c: u64, a: u64, max: u64) -> u64 {
+    if a == 0 {
+        0
+    } else {
+        ((2 * c * max + a) / (2 * a)).min(max)
+    }
+}
+
+#[test]
+fn u8_alpha_matches_reference() {
+    let sample = |v| biased_sample(v, 255) as u8;
+    for (alpha_mode, scale) in [
+        (
+            AlphaMode::Premultiply,
+            premultiplied as fn(u64, u64, u64) -> u64,
+        ),
+        (AlphaMode::Unpremultiply, unpremultiplied),
+    ] {
+        let pixel = |px: [u8; 4]| {
+            let a = px[3] as u64;
+            let c = |c: u8| scale(c as u64, a, 255) as u8;
+            [c(px[0]), c(px[1]), c(px[2]), px[3]]
+        };
+        check_alpha(
+            sample,
+            pixel,
+            &|i, is, o, os, w, h, fp, fl| {
+                transpose_rgba_alpha(i, is, o, os, w, h, alpha_mode, fp, fl)
+            },
+            [
+                (
+                    &|i, is, o, os, w, h| flip_rgba_alpha(i, is, o, os, w, h, alpha_mode),
+                    true,
+                    false,
+                ),
+                (
+                    &|i, is, o, os, w, h| flop_rgba_alpha(i, is, o, os, w, h, alpha_mode),
+                    false,
+                    true,
+                ),
+                (
+                    &|i, is, o, os, w, h| rotate180_rgba_alpha(i, is, o, os, w, h, alpha_mode),
+                    true,
+                    true,
+                ),
+            ],
+        );
+    }
+}
+
+#[test]
+fn u16_alpha_matches_reference() {
+    for bit_depth in [8, 10, 12, 16] {
+        let max = (1u32 << bit_depth) - 1;
+        let sample = |v| biased_sample(v, max) as u16;
+        for (alpha_mode, scale) in [
+            (
+                AlphaMode::Premultiply,
+                premultiplied as fn(u64, u64, u64) -> u64,
+            ),
+            (AlphaMode::Unpremultiply, unpremultiplied),
+        ] {
+            let pixel = |px: [u16; 4]| {
+                let a = px[3] as u64;
+                let c = |c: u16| scale(c as u64, a, max as u64) as u16;
+                [c(px[0]), c(px[1]), c(px[2]), px[3]]
+            };
+            check_alpha(
+                sample,
+                pixel,
+                &|i, is, o, os, w, h, fp, fl| {
+                    transpose_rgba16_alpha(i, is, o, os, w, h, alpha_mode, bit_depth, fp, fl)
+                },
+                [
+                    (
+                        &|i, is, o, os, w, h| {
+                            flip_rgba16_alpha(i, is, o, os, w, h, alpha_mode, bit_depth)
+                        },
+                        true,
+                        false,
+                    ),
+                    (
+                        &|i, is, o, os, w, h| {
+                            flop_rgba16_alpha(i, is, o, os, w, h, alpha_mode, bit_depth)
+                        },
+                        false,
+                        true,
+                    ),
+                    (
+                        &|i, is, o, os, w, h| {
+                            rotate180_rgba16_alpha(i, is, o, os, w, h, alpha_mode, bit_depth)
+                        },
+                        true,
+                        true,
+                    ),
+                ],
+            );
+        }
+    }
+}
+
+#[test]
+fn f32_alpha_matches_reference() {
+    let sample = |v| biased_sample(v, 1000) as f32 / 1000.;
+    for alpha_mode in [AlphaMode::Premultiply, AlphaMode::Unpremultiply] {
+        let pixel = |px: [f32; 4]| {
+            let a = px[3];
+            let c = |c: f32| match alpha_mode {
+                AlphaMode::Premultiply => c * a,
+                AlphaMode::Unpremultiply if a == 0. => 0.,
+                AlphaMode::Unpremultiply => c / a,
+            };
+            [c(px[0]), c(px[1]), c(px[2]), a]
+        };
+        check_alpha(
+            sample,
+            pixel,
+            &|i, is, o, os, w, h, fp, fl| {
+                transpose_rgba_f32_alpha(i, is, o, os, w, h, alpha_mode, fp, fl)
+            },
+            [
+                (
+                    &|i, is, o, os, w, h| flip_rgba_f32_alpha(i, is, o, os, w, h, alpha_mode),
+                    true,
+                    false,
+                ),
+                (
+                    &|i, is, o, os, w, h| flop_rgba_f32_alpha(i, is, o, os, w, h, alpha_mode),
+                    false,
+                    true,
+                ),
+                (
+                    &|i, is, o, os, w, h| rotate180_rgba_f32_alpha(i, is, o, os, w, h, alpha_mode),
+                    true,
+                    true,
+                ),
+            ],
+        );
+    }
+}
+
+#[test]
+fn u16_unpremultiply_is_exact_for_every_alpha() {
+    // Every alpha against colors around it, where the float reciprocal is corrected.
+    let max = u16::MAX as u64;
+    let input: Vec<u16> = (1..=u16::MAX)
+        .flat_map(|a| {
+            let c = |d: u16| a.saturating_sub(d);
+            [c(1), c(a / 2), a / 3, a]
+        })
+        .collect();
+    let mut output = vec![0u16; input.len()];
+    let width = input.len() / 4;
+    flip_rgba16_alpha(
+        &input,
+        width * 4,
+        &mut output,
+        width * 4,
+        width,
+        1,
+        AlphaMode::Unpremultiply,
+        16,
+    )
+    .unwrap();
+    for (src, dst) in input.chunks_exact(4).rev().zip(output.chunks_exact(4)) {
+        let a = src[3] as u64;
+        let expected = [0, 1, 2].map(|i| unpremultiplied(src[i] as u64, a, max) as u16);
+        assert_eq!(&dst[..3], &expected, "alpha {a}");
+        assert_eq!(dst[3], src[3]);
+    }
+}
+
+#[test]
+fn u8_alpha_is_exact_for_every_color_and_alpha() {
+    // Row per alpha holding every color, mirrored both with and without reversed pixels.
+    let input: Vec<u8> = (0..=255u8)
+        .flat_map(|a| (0..=255u8).flat_map(move |c| [c, !c, c / 3, a]))
+        .collect();
+    let stride = 256 * 4;
+    for (alpha_mode, scale) in [
+        (
+            AlphaMode::Premultiply,
+            premultiplied as fn(u64, u64, u64) -> u64,
+        ),
+        (AlphaMode::Unpremultiply, unpremultiplied),
+    ] {
+        let pixel = |px: [u8; 4]| {
+            let c = |c: u8| scale(c as u64, px[3] as u64, 255) as u8;
+            [c(px[0]), c(px[1]), c(px[2]), px[3]]
+        };
+        let mirrors: [(AlphaMirror<u8>, bool); 2] = [
+            (
+                &|i, is, o, os, w, h| flop_rgba_alpha(i, is, o, os, w, h, alpha_mode),
+                false,
+            ),
+            (
+                &|i, is, o, os, w, h| rotate180_rgba_alpha(i, is, o, os, w, h, alpha_mode),
+                true,
+            ),
+        ];
+        for (mirror, reverse_x) in mirrors {
+            let mut output = vec![0u8; input.len()];
+            mirror(&input, stride, &mut output, stride, 256, 256).unwrap();
+            let reference = map_pixels(
+                reference_mirror(&input, stride, 256, 256, 4, reverse_x, true),
+                &pixel,
+            );
+            assert!(output == reference, "{alpha_mode:?}, reverse x {reverse_x}");
+        }
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{oriented_size, packed_rows, random_samples, reference_orient, ORIENTATIONS, SIZES};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+type MosaicRotate<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    CfaPattern,
+    Orientation,
+) -> Result<CfaPattern, TransposeError>;
+
+type MosaicMirror<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    CfaPattern,
+) -> Result<CfaPattern, TransposeError>;
+
+const PATTERNS: [CfaPattern; 4] = [
+    CfaPattern::Rggb,
+    CfaPattern::Bggr,
+    CfaPattern::Grbg,
+    CfaPattern::Gbrg,
+];
+
+/// Color of every sample of a `width` x `height` mosaic, 0 is red, 1 is green and 2 is blue.
+fn colors(pattern: CfaPattern, width: usize, height: usize) -> Vec<u8> {
+    let cell = match pattern {
+        CfaPattern::Rggb => [0, 1, 1, 2],
+        CfaPattern::Bggr => [2, 1, 1, 0],
+        CfaPattern::Grbg => [1, 0, 2, 1],
+        CfaPattern::Gbrg => [1, 2, 0, 1],
+    };
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| cell[(y & 1) * 2 + (x & 1)]))
+        .collect()
+}
+
+/// Compares samples against [reference_orient] and the returned pattern against colors of
+/// the reoriented source samples for every orientation.
+fn check_mosaic<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    rotate: MosaicRotate<V>,
+) {
+    for width in SIZES {
+        for height in SIZES {
+            let input_stride = width + 1;
+            let input = random_samples(
+                input_stride * height,
+                (width * 131 + height) as u32,
+                &sample,
+            );
+            for pattern in PATTERNS {
+                let source_colors = colors(pattern, width, height);
+                for orientation in ORIENTATIONS {
+                    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                    let output_stride = dst_width + 2;
+                    let mut output = vec![V::default(); output_stride * dst_height];
+                    let oriented = rotate(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        pattern,
+                        orientation,
+                    )
+                    .unwrap();
+                    let context = format!("{width}x{height} {pattern:?} {orientation:?}");
+                    assert!(
+                        packed_rows(&output, output_stride, dst_width, dst_height)
+                            == reference_orient(
+                                &input,
+                                input_stride,
+                                width,
+                                height,
+                                1,
+                                orientation
+                            ),
+                        "{context}"
+                    );
+                    assert_eq!(
+                        colors(oriented, dst_width, dst_height),
+                        reference_orient(&source_colors, width, width, height, 1, orientation),
+                        "{context}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Compares a mirror against the rotation with the same orientation.
+fn check_mosaic_mirror<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    mirror: MosaicMirror<V>,
+    rotate: MosaicRotate<V>,
+    orientation: Orientation,
+) {
+    for (width, height) in [(3usize, 5usize), (8, 8), (17, 4)] {
+        let input = random_samples(width * height, (width + height) as u32, &sample);
+        let mut mirrored = vec![V::default(); width * height];
+        let mut rotated = vec![V::default(); width * height];
+        for pattern in PATTERNS {
+            let mirrored_pattern =
+                mirror(&input, width, &mut mirrored, width, width, height, pattern).unwrap();
+            let rotated_pattern = rotate(
+                &input,
+                width,
+                &mut rotated,
+                width,
+                width,
+                height,
+                pattern,
+                orientation,
+            )
+            .unwrap();
+            assert_eq!(mirrored_pattern, rotated_pattern);
+            assert!(mirrored == rotated, "{width}x{height} {orientation:?}");
+        }
+    }
+}
+
+#[test]
+fn bayer8_matches_reference() {
+    check_mosaic(|v| v as u8, rotate_bayer8);
+    check_mosaic_mirror(
+        |v| v as u8,
+        flip_bayer8,
+        rotate_bayer8,
+        Orientation::MirrorHorizontal,
+    );
+    check_mosaic_mirror(
+        |v| v as u8,
+        flop_bayer8,
+        rotate_bayer8,
+        Orientation::MirrorVertical,
+    );
+}
+
+#[test]
+fn bayer16_matches_reference() {
+    check_mosaic(|v| v as u16, rotate_bayer16);
+    check_mosaic_mirror(
+        |v| v as u16,
+        flip_bayer16,
+        rotate_bayer16,
+        Orientation::MirrorHorizontal,
+    );
+    check_mosaic_mirror(
+        |v| v as u16,
+        flop_bayer16,
+        rotate_bayer16,
+        Orientation::MirrorVertical,
+    );
+}
@@ -0,0 +1,198 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{oriented_size, random_samples, reference_orient, ORIENTATIONS};
+use fast_transpose::*;
+
+type BlockRotate =
+    fn(&[u8], usize, &mut [u8], usize, usize, usize, Orientation) -> Result<(), TransposeError>;
+
+type BlockMirror = fn(&[u8], usize, &mut [u8], usize, usize, usize) -> Result<(), TransposeError>;
+
+/// Byte offset of an 8-byte part in a block, offset of its index table and bits per index.
+type Part = (usize, usize, usize);
+
+const COLOR: usize = 4;
+const CHANNEL: usize = 2;
+const BC1: [Part; 1] = [(0, COLOR, 2)];
+const BC3: [Part; 2] = [(0, CHANNEL, 3), (8, COLOR, 2)];
+const BC4: [Part; 1] = [(0, CHANNEL, 3)];
+const BC5: [Part; 2] = [(0, CHANNEL, 3), (8, CHANNEL, 3)];
+
+/// Texture sizes with whole blocks, partial trailing blocks and textures smaller than a block.
+const TEXTURE_SIZES: [usize; 10] = [1, 2, 3, 4, 5, 6, 8, 12, 17, 20];
+
+fn texel_channels(parts: &[Part]) -> usize {
+    parts.iter().map(|&(_, index, _)| index + 1).sum()
+}
+
+/// Expands every texel to the endpoints of its block parts followed by its index in each part,
+/// so a lossless reorientation of a texture is the same reorientation of the expanded image.
+fn expand(
+    texture: &[u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    block: usize,
+    parts: &[Part],
+) -> Vec<u8> {
+    let mut texels = Vec::with_capacity(width * height * texel_channels(parts));
+    for y in 0..height {
+        for x in 0..width {
+            let block = &texture[(y / 4) * stride + (x / 4) * block..][..block];
+            let texel = (y % 4) * 4 + x % 4;
+            for &(offset, index, bits) in parts {
+                let part = &block[offset..offset + 8];
+                texels.extend_from_slice(&part[..index]);
+                let indices = u64::from_le_bytes(part.try_into().unwrap()) >> (index * 8);
+                texels.push(((indices >> (texel * bits)) & ((1 << bits) - 1)) as u8);
+            }
+        }
+    }
+    texels
+}
+
+fn check_blocks(block: usize, parts: &[Part], rotate: BlockRotate) {
+    let channels = texel_channels(parts);
+    for width in TEXTURE_SIZES {
+        for height in TEXTURE_SIZES {
+            let (blocks_x, blocks_y) = (width.div_ceil(4), height.div_ceil(4));
+            let input_stride = blocks_x * block + 3;
+            let input = random_samples(
+                input_stride * blocks_y,
+                (width * 131 + height) as u32,
+                |v| v as u8,
+            );
+            let texels = expand(&input, input_stride, width, height, block, parts);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let output_stride = dst_width.div_ceil(4) * block + 5;
+                let mut output = vec![0u8; output_stride * dst_height.div_ceil(4)];
+                let result = rotate(
+                    &input,
+                    input_stride,
+                    &mut output,
+                    output_stride,
+                    width,
+                    height,
+                    orientation,
+                );
+                let context = format!("{width}x{height} {orientation:?}");
+                let partial = |size: usize| size > 4 && size % 4 != 0;
+                let reverses_x = matches!(
+                    orientation,
+                    Orientation::MirrorHorizontal
+                        | Orientation::Rotate180
+                        | Orientation::Rotate270
+                        | Orientation::Transverse
+                );
+                let reverses_y = matches!(
+                    orientation,
+                    Orientation::MirrorVertical
+                        | Orientation::Rotate180
+                        | Orientation::Rotate90
+                        | Orientation::Transverse
+                );
+                if (reverses_x && partial(width)) || (reverses_y && partial(height)) {
+                    assert_eq!(result, Err(TransposeError::MismatchDimensions), "{context}");
+                    continue;
+                }
+                result.unwrap();
+                assert!(
+                    expand(&output, output_stride, dst_width, dst_height, block, parts)
+                        == reference_orient(
+                            &texels,
+                            width * channels,
+                            width,
+                            height,
+                            channels,
+                            orientation
+                        ),
+                    "{context}"
+                );
+            }
+        }
+    }
+}
+
+/// Compares a mirror against the rotation with the same orientation.
+fn check_mirror_blocks(
+    block: usize,
+    mirror: BlockMirror,
+    rotate: BlockRotate,
+    orientation: Orientation,
+) {
+    for (width, height) in [(3usize, 2usize), (8, 12), (16, 4)] {
+        let stride = width.div_ceil(4) * block;
+        let len = stride * height.div_ceil(4);
+        let input = random_samples(len, (width + height) as u32, |v| v as u8);
+        let (mut mirrored, mut rotated) = (vec![0u8; len], vec![0u8; len]);
+        mirror(&input, stride, &mut mirrored, stride, width, height).unwrap();
+        rotate(
+            &input,
+            stride,
+            &mut rotated,
+            stride,
+            width,
+            height,
+            orientation,
+        )
+        .unwrap();
+        assert_eq!(mirrored, rotated, "{width}x{height} {orientation:?}");
+    }
+}
+
+#[test]
+fn bc1_matches_reference() {
+    check_blocks(8, &BC1, rotate_bc1);
+    check_mirror_blocks(8, flip_bc1, rotate_bc1, Orientation::MirrorHorizontal);
+    check_mirror_blocks(8, flop_bc1, rotate_bc1, Orientation::MirrorVertical);
+}
+
+#[test]
+fn bc3_matches_reference() {
+    check_blocks(16, &BC3, rotate_bc3);
+    check_mirror_blocks(16, flip_bc3, rotate_bc3, Orientation::MirrorHorizontal);
+    check_mirror_blocks(16, flop_bc3, rotate_bc3, Orientation::MirrorVertical);
+}
+
+#[test]
+fn bc4_matches_reference() {
+    check_blocks(8, &BC4, rotate_bc4);
+    check_mirror_blocks(8, flip_bc4, rotate_bc4, Orientation::MirrorHorizontal);
+    check_mirror_blocks(8, flop_bc4, rotate_bc4, Orientation::MirrorVertical);
+}
+
+#[test]
+fn bc5_matches_reference() {
+    check_blocks(16, &BC5, rotate_bc5);
+    check_mirror_blocks(16, flip_bc5, rotate_bc5, Orientation::MirrorHorizontal);
+    check_mirror_blocks(16, flop_bc5, rotate_bc5, Orientation::MirrorVertical);
+}
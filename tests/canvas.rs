@@ -0,0 +1,144 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    random_samples, reference_mirror, reference_transpose, Transposer, FLIP_MODES, FLOP_MODES,
+};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+const CANVAS_WIDTH: usize = 71;
+const CANVAS_HEIGHT: usize = 59;
+const BACKGROUND: u8 = 0xA5;
+
+/// Checks pixels inside `rect` against `expected` and that the rest of canvas is untouched.
+fn assert_composited<V: Copy + PartialEq + Debug>(
+    canvas: &[V],
+    stride: usize,
+    rect: Rect,
+    channels: usize,
+    expected: &[V],
+    background: V,
+) {
+    for (y, row) in canvas.chunks(stride).enumerate() {
+        for (x, pixel) in row[..CANVAS_WIDTH * channels].chunks(channels).enumerate() {
+            let inside = (rect.x..rect.x + rect.width).contains(&x)
+                && (rect.y..rect.y + rect.height).contains(&y);
+            if inside {
+                let offset = ((y - rect.y) * rect.width + x - rect.x) * channels;
+                assert_eq!(
+                    pixel,
+                    &expected[offset..offset + channels],
+                    "{rect:?} {x}x{y}"
+                );
+            } else {
+                assert!(pixel.iter().all(|&v| v == background), "{rect:?} {x}x{y}");
+            }
+        }
+        assert!(row[CANVAS_WIDTH * channels..]
+            .iter()
+            .all(|&v| v == background));
+    }
+}
+
+/// Transposes images of several sizes through `transposer` into a region of a padded canvas.
+fn check_transpose_into_canvas<V: Copy + Default + PartialEq + Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    background: V,
+    transposer: Transposer<V>,
+) {
+    let stride = (CANVAS_WIDTH + 2) * channels;
+    for (width, height, x, y) in [(13, 21, 0, 0), (33, 17, 5, 9), (9, 40, 31, 50)] {
+        let input = random_samples(width * height * channels, (width + height) as u32, &sample);
+        // Transposed image is `height` pixels wide and `width` pixels tall.
+        let rect = Rect::new(x, y, height, width);
+        for flip_mode in FLIP_MODES {
+            for flop_mode in FLOP_MODES {
+                let mut canvas = vec![background; stride * CANVAS_HEIGHT];
+                let view = rect
+                    .crop_mut(&mut canvas, stride, CANVAS_WIDTH, CANVAS_HEIGHT, channels)
+                    .unwrap();
+                transposer(
+                    &input,
+                    width * channels,
+                    view,
+                    stride,
+                    width,
+                    height,
+                    flip_mode,
+                    flop_mode,
+                )
+                .unwrap();
+                let reference = reference_transpose(
+                    &input,
+                    width * channels,
+                    width,
+                    height,
+                    channels,
+                    flip_mode,
+                    flop_mode,
+                );
+                assert_composited(&canvas, stride, rect, channels, &reference, background);
+            }
+        }
+    }
+}
+
+#[test]
+fn transpose_into_canvas() {
+    check_transpose_into_canvas(4, |v| v as u8, BACKGROUND, transpose_rgba);
+    check_transpose_into_canvas(1, |v| v as u8, BACKGROUND, transpose_plane);
+    check_transpose_into_canvas(1, |v| v as u16, 0xA5A5, transpose_plane16);
+    check_transpose_into_canvas(1, |v| v as f32, -1.5, transpose_plane_f32);
+    check_transpose_into_canvas(4, |v| v as f32, -1.5, transpose_rgba_f32);
+}
+
+#[test]
+fn mirror_into_canvas() {
+    let stride = CANVAS_WIDTH * 3 + 1;
+    let (width, height) = (37, 23);
+    let input = random_samples(width * height * 3, 17, |v| v as u8);
+    let rect = Rect::new(CANVAS_WIDTH - width, 30, width, height);
+    let mirrors: [(common::Mirror<u8>, bool, bool); 3] = [
+        (flip_rgb, true, false),
+        (flop_rgb, false, true),
+        (rotate180_rgb, true, true),
+    ];
+    for (mirror, reverse_x, reverse_y) in mirrors {
+        let mut canvas = vec![BACKGROUND; stride * CANVAS_HEIGHT];
+        let view = rect
+            .crop_mut(&mut canvas, stride, CANVAS_WIDTH, CANVAS_HEIGHT, 3)
+            .unwrap();
+        mirror(&input, width * 3, view, stride, width, height).unwrap();
+        let reference = reference_mirror(&input, width * 3, width, height, 3, reverse_x, reverse_y);
+        assert_composited(&canvas, stride, rect, 3, &reference, BACKGROUND);
+    }
+}
@@ -0,0 +1,558 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+#![allow(dead_code)]
+use fast_transpose::{BitOrder, FlipMode, FlopMode, Orientation, TransposeError};
+use std::fmt::Debug;
+
+pub const FLIP_MODES: [FlipMode; 2] = [FlipMode::NoFlip, FlipMode::Flip];
+pub const FLOP_MODES: [FlopMode; 2] = [FlopMode::NoFlop, FlopMode::Flop];
+
+/// Sizes around every block size used by the kernels, so full blocks, remainders
+/// and scalar edges are all visited.
+pub const SIZES: [usize; 12] = [1, 2, 3, 5, 7, 8, 9, 15, 16, 17, 33, 65];
+
+/// Fills a buffer with a deterministic xorshift sequence.
+pub fn random_samples<V>(len: usize, seed: u32, map: impl Fn(u32) -> V) -> Vec<V> {
+    let mut state = seed.wrapping_mul(0x9e37_79b9) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            map(state)
+        })
+        .collect()
+}
+
+/// Transposes a packed `channels` image element by element.
+pub fn reference_transpose<V: Copy + Default>(
+    input: &[V],
+    input_stride: usize,
+    width: usize,
+    height: usize,
+    channels: usize,
+    flip_mode: FlipMode,
+    flop_mode: FlopMode,
+) -> Vec<V> {
+    let output_stride = height * channels;
+    let mut output = vec![V::default(); output_stride * width];
+    for y in 0..height {
+        let input_y = match flip_mode {
+            FlipMode::NoFlip => y,
+            FlipMode::Flip => height - 1 - y,
+        };
+        for x in 0..width {
+            let output_x = match flop_mode {
+                FlopMode::NoFlop => width - 1 - x,
+                FlopMode::Flop => x,
+            };
+            for c in 0..channels {
+                output[output_x * output_stride + y * channels + c] =
+                    input[input_y * input_stride + x * channels + c];
+            }
+        }
+    }
+    output
+}
+
+pub type Transposer<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    FlipMode,
+    FlopMode,
+) -> Result<(), TransposeError>;
+
+/// Compares `transposer` against [`reference_transpose`] for every flip and flop mode
+/// over all combinations of `widths` and `heights` with strided input.
+pub fn check_transpose<V: Copy + Default + PartialEq + Debug>(
+    widths: &[usize],
+    heights: &[usize],
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    transposer: Transposer<V>,
+) {
+    for &width in widths {
+        for &height in heights {
+            // A spare pixel per row keeps strided reads honest.
+            let input_stride = (width + 1) * channels;
+            let input = random_samples(
+                input_stride * height,
+                (width * 131 + height) as u32,
+                &sample,
+            );
+            for flip_mode in FLIP_MODES {
+                for flop_mode in FLOP_MODES {
+                    let output_stride = height * channels;
+                    let mut output = vec![V::default(); output_stride * width];
+                    transposer(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                    )
+                    .unwrap();
+                    let reference = reference_transpose(
+                        &input,
+                        input_stride,
+                        width,
+                        height,
+                        channels,
+                        flip_mode,
+                        flop_mode,
+                    );
+                    assert!(
+                        output == reference,
+                        "{width}x{height}, {channels} channels, {flip_mode:?}, {flop_mode:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Widths around every vector width used by row reversal kernels.
+pub const ROW_WIDTHS: [usize; 18] = [
+    1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 31, 32, 33, 63, 64, 65, 129,
+];
+
+pub type Mirror<V> = fn(&[V], usize, &mut [V], usize, usize, usize) -> Result<(), TransposeError>;
+
+/// Mirrors a packed `channels` image element by element.
+pub fn reference_mirror<V: Copy + Default>(
+    input: &[V],
+    input_stride: usize,
+    width: usize,
+    height: usize,
+    channels: usize,
+    reverse_x: bool,
+    reverse_y: bool,
+) -> Vec<V> {
+    let output_stride = width * channels;
+    let mut output = vec![V::default(); output_stride * height];
+    for y in 0..height {
+        let input_y = if reverse_y { height - 1 - y } else { y };
+        for x in 0..width {
+            let input_x = if reverse_x { width - 1 - x } else { x };
+            for c in 0..channels {
+                output[y * output_stride + x * channels + c] =
+                    input[input_y * input_stride + input_x * channels + c];
+            }
+        }
+    }
+    output
+}
+
+/// Compares `mirror` against [`reference_mirror`] over [`ROW_WIDTHS`] with packed and strided input.
+pub fn check_mirror<V: Copy + Default + PartialEq + Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    mirror: Mirror<V>,
+    reverse_x: bool,
+    reverse_y: bool,
+) {
+    for width in ROW_WIDTHS {
+        for height in [1, 2, 3, 8] {
+            for padding in [0, 1] {
+                let input_stride = (width + padding) * channels;
+                let seed = (width * 7 + height) as u32;
+                let input = random_samples(input_stride * height, seed, &sample);
+                let output_stride = width * channels;
+                let mut output = vec![V::default(); output_stride * height];
+                mirror(
+                    &input,
+                    input_stride,
+                    &mut output,
+                    output_stride,
+                    width,
+                    height,
+                )
+                .unwrap();
+                let reference = reference_mirror(
+                    &input,
+                    input_stride,
+                    width,
+                    height,
+                    channels,
+                    reverse_x,
+                    reverse_y,
+                );
+                assert!(
+                    output == reference,
+                    "{width}x{height}, padding {padding}, {channels} channels"
+                );
+            }
+        }
+    }
+}
+
+pub const ORIENTATIONS: [Orientation; 8] = [
+    Orientation::Identity,
+    Orientation::MirrorHorizontal,
+    Orientation::MirrorVertical,
+    Orientation::Rotate90,
+    Orientation::Rotate180,
+    Orientation::Rotate270,
+    Orientation::Transpose,
+    Orientation::Transverse,
+];
+
+/// Size of an image of `width` x `height` pixels after `orientation`.
+pub fn oriented_size(width: usize, height: usize, orientation: Orientation) -> (usize, usize) {
+    if orientation.is_transposed() {
+        (height, width)
+    } else {
+        (width, height)
+    }
+}
+
+/// Source pixel landing at `(x, y)` of the destination after `orientation`.
+pub fn source_position(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+) -> (usize, usize) {
+    let (reverse_x, reverse_y) = match orientation {
+        Orientation::Identity | Orientation::Transpose => (false, false),
+        Orientation::MirrorHorizontal | Orientation::Rotate270 => (true, false),
+        Orientation::MirrorVertical | Orientation::Rotate90 => (false, true),
+        Orientation::Rotate180 | Orientation::Transverse => (true, true),
+    };
+    let (x, y) = if orientation.is_transposed() {
+        (y, x)
+    } else {
+        (x, y)
+    };
+    (
+        if reverse_x { width - 1 - x } else { x },
+        if reverse_y { height - 1 - y } else { y },
+    )
+}
+
+/// Reorients a packed `channels` image pixel by pixel into a tightly packed one.
+pub fn reference_orient<V: Copy>(
+    input: &[V],
+    input_stride: usize,
+    width: usize,
+    height: usize,
+    channels: usize,
+    orientation: Orientation,
+) -> Vec<V> {
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let mut output = Vec::with_capacity(dst_width * dst_height * channels);
+    for y in 0..dst_height {
+        for x in 0..dst_width {
+            let (src_x, src_y) = source_position(x, y, width, height, orientation);
+            output.extend_from_slice(&input[src_y * input_stride + src_x * channels..][..channels]);
+        }
+    }
+    output
+}
+
+/// Copies `rows` of `row_length` samples out of a strided buffer.
+pub fn packed_rows<V: Copy>(input: &[V], stride: usize, row_length: usize, rows: usize) -> Vec<V> {
+    input
+        .chunks(stride)
+        .take(rows)
+        .flat_map(|row| row[..row_length].iter().copied())
+        .collect()
+}
+
+/// Compares `transposer` that converts samples against [`reference_transpose`] followed by
+/// `convert` applied to every sample, for every flip and flop mode with strided input.
+pub fn check_transpose_converted<I: Copy + Default, O: Copy + Default + PartialEq + Debug>(
+    widths: &[usize],
+    heights: &[usize],
+    channels: usize,
+    sample: impl Fn(u32) -> I,
+    convert: impl Fn(I) -> O,
+    transposer: impl Fn(
+        &[I],
+        usize,
+        &mut [O],
+        usize,
+        usize,
+        usize,
+        FlipMode,
+        FlopMode,
+    ) -> Result<(), TransposeError>,
+) {
+    for &width in widths {
+        for &height in heights {
+            let input_stride = (width + 1) * channels;
+            let input = random_samples(
+                input_stride * height,
+                (width * 131 + height) as u32,
+                &sample,
+            );
+            for flip_mode in FLIP_MODES {
+                for flop_mode in FLOP_MODES {
+                    let output_stride = height * channels;
+                    let mut output = vec![O::default(); output_stride * width];
+                    transposer(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        flip_mode,
+                        flop_mode,
+                    )
+                    .unwrap();
+                    let reference: Vec<O> = reference_transpose(
+                        &input,
+                        input_stride,
+                        width,
+                        height,
+                        channels,
+                        flip_mode,
+                        flop_mode,
+                    )
+                    .into_iter()
+                    .map(&convert)
+                    .collect();
+                    assert!(
+                        output == reference,
+                        "{width}x{height}, {channels} channels, {flip_mode:?}, {flop_mode:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Widths and heights with odd and even chroma subsampling edges around the block sizes.
+pub const YUV_SIZES: [usize; 11] = [1, 2, 3, 4, 5, 7, 8, 9, 16, 17, 33];
+
+pub fn average_u8(a: u8, b: u8) -> u8 {
+    ((a as u16 + b as u16 + 1) >> 1) as u8
+}
+
+pub fn average_u16(a: u16, b: u16) -> u16 {
+    ((a as u32 + b as u32 + 1) >> 1) as u16
+}
+
+/// Reorients a chroma plane of a `width` x `height` frame subsampled by `sub_x` and `sub_y`.
+///
+/// Transposed 4:2:2 chroma stays 4:2:2, so every destination sample averages the source
+/// chroma under the pair of destination luma columns it covers.
+pub fn reference_chroma<V: Copy>(
+    input: &[V],
+    input_stride: usize,
+    width: usize,
+    height: usize,
+    (sub_x, sub_y): (bool, bool),
+    orientation: Orientation,
+    average: impl Fn(V, V) -> V,
+) -> Vec<V> {
+    let chroma_width = if sub_x { width.div_ceil(2) } else { width };
+    let chroma_height = if sub_y { height.div_ceil(2) } else { height };
+    if !(sub_x && !sub_y && orientation.is_transposed()) {
+        return reference_orient(
+            input,
+            input_stride,
+            chroma_width,
+            chroma_height,
+            1,
+            orientation,
+        );
+    }
+    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+    let mut output = Vec::with_capacity(dst_width.div_ceil(2) * dst_height);
+    for y in 0..dst_height {
+        for x in (0..dst_width).step_by(2) {
+            let (x0, y0) = source_position(x, y, width, height, orientation);
+            let (x1, y1) =
+                source_position((x + 1).min(dst_width - 1), y, width, height, orientation);
+            output.push(average(
+                input[y0 * input_stride + x0 / 2],
+                input[y1 * input_stride + x1 / 2],
+            ));
+        }
+    }
+    output
+}
+
+pub const BIT_ORDERS: [BitOrder; 2] = [BitOrder::MsbFirst, BitOrder::LsbFirst];
+
+/// Sizes around 8x8 blocks, 64x64 words and 256 pixel tiles of packed pixel kernels.
+pub const PACKED_SIZES: [usize; 14] = [1, 2, 3, 5, 7, 8, 9, 15, 17, 63, 64, 65, 129, 257];
+
+/// Shift of the pixel at `index` of a byte holding pixels of `bits` bits.
+fn bit_shift(index: usize, bits: usize, bit_order: BitOrder) -> usize {
+    match bit_order {
+        BitOrder::MsbFirst => 8 - bits - index * bits,
+        BitOrder::LsbFirst => index * bits,
+    }
+}
+
+/// Expands rows of pixels of `bits` bits to a byte per pixel.
+pub fn unpack_bits(
+    input: &[u8],
+    input_stride: usize,
+    width: usize,
+    height: usize,
+    bits: usize,
+    bit_order: BitOrder,
+) -> Vec<u8> {
+    let pixels_per_byte = 8 / bits;
+    let mask = (1u8 << bits) - 1;
+    let mut output = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let byte = input[y * input_stride + x / pixels_per_byte];
+            output.push((byte >> bit_shift(x % pixels_per_byte, bits, bit_order)) & mask);
+        }
+    }
+    output
+}
+
+/// Packs a byte per pixel into tight rows of pixels of `bits` bits with zero padding.
+pub fn pack_bits(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    bits: usize,
+    bit_order: BitOrder,
+) -> Vec<u8> {
+    let pixels_per_byte = 8 / bits;
+    let row_bytes = width.div_ceil(pixels_per_byte);
+    let mut output = vec![0u8; row_bytes * height];
+    for y in 0..height {
+        for x in 0..width {
+            output[y * row_bytes + x / pixels_per_byte] |=
+                pixels[y * width + x] << bit_shift(x % pixels_per_byte, bits, bit_order);
+        }
+    }
+    output
+}
+
+pub type PackedTransposer = fn(
+    &[u8],
+    usize,
+    &mut [u8],
+    usize,
+    usize,
+    usize,
+    BitOrder,
+    FlipMode,
+    FlopMode,
+) -> Result<(), TransposeError>;
+
+pub type PackedMirror =
+    fn(&[u8], usize, &mut [u8], usize, usize, usize, BitOrder) -> Result<(), TransposeError>;
+
+/// Compares a transpose of pixels of `bits` bits against [`reference_transpose`] of the
+/// unpacked image, destination padding bits must be cleared.
+pub fn check_packed_transpose(bits: usize, transposer: PackedTransposer) {
+    let pixels_per_byte = 8 / bits;
+    for width in PACKED_SIZES {
+        for height in PACKED_SIZES {
+            // Garbage in the source padding bits and a spare byte per row
+            let input_stride = width.div_ceil(pixels_per_byte) + 1;
+            let input = random_samples(input_stride * height, (width * 131 + height) as u32, |v| {
+                v as u8
+            });
+            let output_bytes = height.div_ceil(pixels_per_byte);
+            let output_stride = output_bytes + 1;
+            for bit_order in BIT_ORDERS {
+                let pixels = unpack_bits(&input, input_stride, width, height, bits, bit_order);
+                for flip_mode in FLIP_MODES {
+                    for flop_mode in FLOP_MODES {
+                        let mut output = vec![0xffu8; output_stride * width];
+                        transposer(
+                            &input,
+                            input_stride,
+                            &mut output,
+                            output_stride,
+                            width,
+                            height,
+                            bit_order,
+                            flip_mode,
+                            flop_mode,
+                        )
+                        .unwrap();
+                        let reference = reference_transpose(
+                            &pixels, width, width, height, 1, flip_mode, flop_mode,
+                        );
+                        assert!(
+                            packed_rows(&output, output_stride, output_bytes, width)
+                                == pack_bits(&reference, height, width, bits, bit_order),
+                            "{width}x{height}, {bits} bits, {bit_order:?}, {flip_mode:?}, {flop_mode:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compares a mirror of pixels of `bits` bits against [`reference_mirror`] of the unpacked
+/// image, destination padding bits must be cleared.
+pub fn check_packed_mirror(bits: usize, mirror: PackedMirror, reverse_x: bool, reverse_y: bool) {
+    let pixels_per_byte = 8 / bits;
+    for width in PACKED_SIZES {
+        for height in [1, 2, 3, 8] {
+            let row_bytes = width.div_ceil(pixels_per_byte);
+            let stride = row_bytes + 1;
+            let input = random_samples(stride * height, (width * 7 + height) as u32, |v| v as u8);
+            for bit_order in BIT_ORDERS {
+                let pixels = unpack_bits(&input, stride, width, height, bits, bit_order);
+                let mut output = vec![0xffu8; stride * height];
+                mirror(
+                    &input,
+                    stride,
+                    &mut output,
+                    stride,
+                    width,
+                    height,
+                    bit_order,
+                )
+                .unwrap();
+                let reference =
+                    reference_mirror(&pixels, width, width, height, 1, reverse_x, reverse_y);
+                assert!(
+                    packed_rows(&output, stride, row_bytes, height)
+                        == pack_bits(&reference, width, height, bits, bit_order),
+                    "{width}x{height}, {bits} bits, {bit_order:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{check_transpose_converted, SIZES};
+use fast_transpose::*;
+
+// Crosses strips of blocks gathered before conversion in both directions.
+const WIDE: [usize; 2] = [300, 1027];
+const TALL: [usize; 2] = [517, 1100];
+
+macro_rules! check_channels {
+    ($widths:expr, $heights:expr, $sample:expr, $convert:expr, |$n:ident| $transposer:expr) => {{
+        {
+            const $n: usize = 1;
+            check_transpose_converted($widths, $heights, $n, $sample, $convert, $transposer);
+        }
+        {
+            const $n: usize = 2;
+            check_transpose_converted($widths, $heights, $n, $sample, $convert, $transposer);
+        }
+        {
+            const $n: usize = 3;
+            check_transpose_converted($widths, $heights, $n, $sample, $convert, $transposer);
+        }
+        {
+            const $n: usize = 4;
+            check_transpose_converted($widths, $heights, $n, $sample, $convert, $transposer);
+        }
+        {
+            const $n: usize = 5;
+            check_transpose_converted($widths, $heights, $n, $sample, $convert, $transposer);
+        }
+    }};
+}
+
+#[test]
+fn u8_to_f32_matches_reference() {
+    let scale = 1. / 255.;
+    for (widths, heights) in [(&SIZES[..], &SIZES[..]), (&WIDE[..1], &TALL[..])] {
+        check_channels!(
+            widths,
+            heights,
+            |v| v as u8,
+            |v: u8| v as f32 * scale,
+            |N| |i, is, o, os, w, h, fp, fl| transpose_u8_to_f32::<N>(
+                i, is, o, os, w, h, scale, fp, fl
+            )
+        );
+    }
+}
+
+#[test]
+fn u16_to_f32_matches_reference() {
+    let scale = 1. / 1023.;
+    for (widths, heights) in [(&SIZES[..], &SIZES[..]), (&TALL[..1], &WIDE[..])] {
+        check_channels!(
+            widths,
+            heights,
+            |v| (v & 0x3ff) as u16,
+            |v: u16| v as f32 * scale,
+            |N| |i, is, o, os, w, h, fp, fl| transpose_u16_to_f32::<N>(
+                i, is, o, os, w, h, scale, fp, fl
+            )
+        );
+    }
+}
+
+#[test]
+fn f32_to_u8_matches_reference() {
+    // Covers negative, above range values and NaN.
+    let sample = |v: u32| match v % 17 {
+        0 => f32::NAN,
+        _ => (v % 1000) as f32 / 700. - 0.2,
+    };
+    let convert = |v: f32| {
+        if v.is_nan() {
+            0
+        } else {
+            (v * 255.).clamp(0., 255.).round() as u8
+        }
+    };
+    for (widths, heights) in [(&SIZES[..], &SIZES[..]), (&WIDE[..1], &TALL[..1])] {
+        check_channels!(widths, heights, sample, convert, |N| {
+            |i, is, o, os, w, h, fp, fl| transpose_f32_to_u8::<N>(i, is, o, os, w, h, 255., fp, fl)
+        });
+    }
+}
+
+#[test]
+fn u16_to_u8_matches_reference() {
+    for bit_depth in [8, 10, 12, 16] {
+        let sample = |v: u32| (v & ((1 << bit_depth) - 1)) as u16;
+        let convert = |v: u16| (v >> (bit_depth - 8)) as u8;
+        check_channels!(&SIZES, &SIZES, sample, convert, |N| {
+            |i, is, o, os, w, h, fp, fl| {
+                transpose_u16_to_u8::<N>(i, is, o, os, w, h, bit_depth, fp, fl)
+            }
+        });
+    }
+    check_channels!(
+        &WIDE[..1],
+        &TALL,
+        |v| v as u16,
+        |v: u16| (v >> 8) as u8,
+        |N| |i, is, o, os, w, h, fp, fl| transpose_u16_to_u8::<N>(i, is, o, os, w, h, 16, fp, fl)
+    );
+}
+
+#[test]
+fn u8_to_u16_matches_reference() {
+    for bit_depth in [8, 10, 12, 16] {
+        // High bits of the sample repeated over 16 bits.
+        let convert = |v: u8| ((v as u32 * 257) >> (16 - bit_depth)) as u16;
+        check_channels!(&SIZES, &SIZES, |v| v as u8, convert, |N| {
+            |i, is, o, os, w, h, fp, fl| {
+                transpose_u8_to_u16::<N>(i, is, o, os, w, h, bit_depth, fp, fl)
+            }
+        });
+    }
+    check_channels!(
+        &TALL[..1],
+        &WIDE,
+        |v| v as u8,
+        |v: u8| u16::from_le_bytes([v, v]),
+        |N| |i, is, o, os, w, h, fp, fl| transpose_u8_to_u16::<N>(i, is, o, os, w, h, 16, fp, fl)
+    );
+}
+
+#[test]
+fn invalid_bit_depth_is_rejected() {
+    let input = [0u16; 4];
+    let mut output = [0u8; 4];
+    for bit_depth in [0, 7, 17] {
+        assert_eq!(
+            transpose_u16_to_u8::<1>(
+                &input,
+                2,
+                &mut output,
+                2,
+                2,
+                2,
+                bit_depth,
+                FlipMode::NoFlip,
+                FlopMode::NoFlop
+            ),
+            Err(TransposeError::InvalidBitDepth)
+        );
+    }
+}
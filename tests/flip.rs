@@ -0,0 +1,61 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::check_mirror;
+use fast_transpose::*;
+
+#[test]
+fn flip_matches_reference() {
+    check_mirror(1, |v| v as u8, flip_plane, true, false);
+    check_mirror(2, |v| v as u8, flip_plane_with_alpha, true, false);
+    check_mirror(3, |v| v as u8, flip_rgb, true, false);
+    check_mirror(4, |v| v as u8, flip_rgba, true, false);
+    check_mirror(1, |v| v as u16, flip_plane16, true, false);
+    check_mirror(2, |v| v as u16, flip_plane16_with_alpha, true, false);
+    check_mirror(3, |v| v as u16, flip_rgb16, true, false);
+    check_mirror(4, |v| v as u16, flip_rgba16, true, false);
+    check_mirror(1, |v| v as f32, flip_plane_f32, true, false);
+    check_mirror(2, |v| v as f32, flip_plane_f32_with_alpha, true, false);
+    check_mirror(3, |v| v as f32, flip_rgb_f32, true, false);
+    check_mirror(4, |v| v as f32, flip_rgba_f32, true, false);
+    check_mirror(1, |v| v as u8, flip_arbitrary, true, false);
+}
+
+#[test]
+fn flop_matches_reference() {
+    check_mirror(1, |v| v as u8, flop_plane, false, true);
+    check_mirror(2, |v| v as u8, flop_plane_with_alpha, false, true);
+    check_mirror(3, |v| v as u8, flop_rgb, false, true);
+    check_mirror(4, |v| v as u8, flop_rgba, false, true);
+    check_mirror(1, |v| v as u16, flop_plane16, false, true);
+    check_mirror(4, |v| v as u16, flop_rgba16, false, true);
+    check_mirror(1, |v| v as f32, flop_plane_f32, false, true);
+    check_mirror(4, |v| v as f32, flop_rgba_f32, false, true);
+}
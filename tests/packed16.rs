@@ -0,0 +1,147 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    check_mirror, check_transpose, oriented_size, packed_rows, random_samples, reference_orient,
+    ORIENTATIONS, SIZES,
+};
+use fast_transpose::*;
+
+type UnpackingRotate =
+    fn(&[u16], usize, &mut [u8], usize, usize, usize, Orientation) -> Result<(), TransposeError>;
+
+/// Widens a channel of `bits` bits at `shift` to 8 bits by bit replication.
+fn channel(pixel: u16, shift: u32, bits: u32) -> u8 {
+    let value = ((pixel >> shift) & ((1 << bits) - 1)) as u32;
+    ((value << (8 - bits)) | (value >> (2 * bits - 8))) as u8
+}
+
+fn rgb565(pixel: u16) -> [u8; 4] {
+    [
+        channel(pixel, 11, 5),
+        channel(pixel, 5, 6),
+        channel(pixel, 0, 5),
+        255,
+    ]
+}
+
+fn rgba4444(pixel: u16) -> [u8; 4] {
+    [
+        channel(pixel, 12, 4),
+        channel(pixel, 8, 4),
+        channel(pixel, 4, 4),
+        channel(pixel, 0, 4),
+    ]
+}
+
+fn rgba5551(pixel: u16) -> [u8; 4] {
+    [
+        channel(pixel, 11, 5),
+        channel(pixel, 6, 5),
+        channel(pixel, 1, 5),
+        if pixel & 1 != 0 { 255 } else { 0 },
+    ]
+}
+
+/// Compares an unpacking rotation for every orientation against [reference_orient] of the
+/// unpacked source.
+fn check_unpacking(unpack: fn(u16) -> [u8; 4], rotate: UnpackingRotate) {
+    for width in SIZES {
+        for height in SIZES {
+            let input_stride = width + 1;
+            let input = random_samples(input_stride * height, (width * 131 + height) as u32, |v| {
+                v as u16
+            });
+            let unpacked: Vec<u8> = input.iter().flat_map(|&pixel| unpack(pixel)).collect();
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let output_stride = dst_width * 4 + 4;
+                let mut output = vec![0u8; output_stride * dst_height];
+                rotate(
+                    &input,
+                    input_stride,
+                    &mut output,
+                    output_stride,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                assert!(
+                    packed_rows(&output, output_stride, dst_width * 4, dst_height)
+                        == reference_orient(
+                            &unpacked,
+                            input_stride * 4,
+                            width,
+                            height,
+                            4,
+                            orientation
+                        ),
+                    "{width}x{height} {orientation:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn transpose_packed16_matches_reference() {
+    check_transpose(&SIZES, &SIZES, 1, |v| v as u16, transpose_rgb565);
+    check_transpose(&SIZES, &SIZES, 1, |v| v as u16, transpose_rgba4444);
+    check_transpose(&SIZES, &SIZES, 1, |v| v as u16, transpose_rgba5551);
+}
+
+#[test]
+fn mirror_packed16_matches_reference() {
+    check_mirror(1, |v| v as u16, flip_rgb565, true, false);
+    check_mirror(1, |v| v as u16, flop_rgb565, false, true);
+    check_mirror(1, |v| v as u16, rotate180_rgb565, true, true);
+    check_mirror(1, |v| v as u16, flip_rgba4444, true, false);
+    check_mirror(1, |v| v as u16, flop_rgba4444, false, true);
+    check_mirror(1, |v| v as u16, rotate180_rgba4444, true, true);
+    check_mirror(1, |v| v as u16, flip_rgba5551, true, false);
+    check_mirror(1, |v| v as u16, flop_rgba5551, false, true);
+    check_mirror(1, |v| v as u16, rotate180_rgba5551, true, true);
+}
+
+#[test]
+fn rgb565_to_rgba8_matches_reference() {
+    check_unpacking(rgb565, rotate_rgb565_to_rgba8);
+}
+
+#[test]
+fn rgba4444_to_rgba8_matches_reference() {
+    check_unpacking(rgba4444, rotate_rgba4444_to_rgba8);
+}
+
+#[test]
+fn rgba5551_to_rgba8_matches_reference() {
+    check_unpacking(rgba5551, rotate_rgba5551_to_rgba8);
+}
@@ -0,0 +1,501 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    average_u16, average_u8, oriented_size, packed_rows, random_samples, reference_chroma,
+    reference_orient, ORIENTATIONS, YUV_SIZES,
+};
+use fast_transpose::*;
+
+type PackedRotate<'a> = &'a dyn Fn(
+    &[u8],
+    usize,
+    &mut [u8],
+    usize,
+    usize,
+    usize,
+    Orientation,
+) -> Result<(), TransposeError>;
+
+type UnpackedRotate<'a> = &'a dyn Fn(
+    &[u8],
+    usize,
+    &mut [u8],
+    usize,
+    &mut [u8],
+    usize,
+    &mut [u8],
+    usize,
+    usize,
+    usize,
+    Orientation,
+) -> Result<(), TransposeError>;
+
+/// Byte offsets of `Y0`, `U`, `Y1` and `V` in a macropixel.
+const YUY2: [usize; 4] = [0, 1, 2, 3];
+const UYVY: [usize; 4] = [1, 0, 3, 2];
+
+/// Splits a packed 4:2:2 frame into tightly packed luma and 4:2:2 chroma planes.
+fn unpack(
+    src: &[u8],
+    src_stride: usize,
+    width: usize,
+    height: usize,
+    layout: [usize; 4],
+) -> [Vec<u8>; 3] {
+    let chroma_width = width.div_ceil(2);
+    let mut planes = [
+        Vec::with_capacity(width * height),
+        Vec::with_capacity(chroma_width * height),
+        Vec::with_capacity(chroma_width * height),
+    ];
+    for row in src.chunks(src_stride).take(height) {
+        for (x, macropixel) in row[..chroma_width * 4].chunks_exact(4).enumerate() {
+            planes[0].push(macropixel[layout[0]]);
+            if x * 2 + 1 < width {
+                planes[0].push(macropixel[layout[2]]);
+            }
+            planes[1].push(macropixel[layout[1]]);
+            planes[2].push(macropixel[layout[3]]);
+        }
+    }
+    planes
+}
+
+/// Averages pairs of rows, or of columns when `horizontally`, with a lone last one kept.
+fn reference_halve(
+    src: &[u8],
+    width: usize,
+    height: usize,
+    horizontally: bool,
+) -> (Vec<u8>, usize, usize) {
+    let (dst_width, dst_height) = if horizontally {
+        (width.div_ceil(2), height)
+    } else {
+        (width, height.div_ceil(2))
+    };
+    let mut output = Vec::with_capacity(dst_width * dst_height);
+    for y in 0..dst_height {
+        for x in 0..dst_width {
+            let (x0, y0, x1, y1) = if horizontally {
+                (x * 2, y, (x * 2 + 1).min(width - 1), y)
+            } else {
+                (x, y * 2, x, (y * 2 + 1).min(height - 1))
+            };
+            output.push(average_u8(src[y0 * width + x0], src[y1 * width + x1]));
+        }
+    }
+    (output, dst_width, dst_height)
+}
+
+fn source_frame(width: usize, height: usize) -> (Vec<u8>, usize) {
+    let src_stride = width.div_ceil(2) * 4 + 4;
+    let src = random_samples(src_stride * height, (width * 131 + height) as u32, |v| {
+        v as u8
+    });
+    (src, src_stride)
+}
+
+/// Compares a packed to packed rotation for every orientation against the scalar reference.
+fn check_packed(layout: [usize; 4], rotate: PackedRotate) {
+    for width in YUV_SIZES {
+        for height in YUV_SIZES {
+            let (src, src_stride) = source_frame(width, height);
+            let [y, u, v] = unpack(&src, src_stride, width, height, layout);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let dst_stride = dst_width.div_ceil(2) * 4 + 8;
+                let mut dst = vec![0u8; dst_stride * dst_height];
+                rotate(
+                    &src,
+                    src_stride,
+                    &mut dst,
+                    dst_stride,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                let context = format!("{width}x{height} {orientation:?}");
+                let [dst_y, dst_u, dst_v] = unpack(&dst, dst_stride, dst_width, dst_height, layout);
+                assert_eq!(
+                    dst_y,
+                    reference_orient(&y, width, width, height, 1, orientation),
+                    "luma {context}"
+                );
+                if dst_width % 2 == 1 {
+                    for row in dst.chunks(dst_stride) {
+                        let macropixel = &row[(dst_width / 2) * 4..][..4];
+                        assert_eq!(
+                            macropixel[layout[0]], macropixel[layout[2]],
+                            "padding {context}"
+                        );
+                    }
+                }
+                let chroma_width = width.div_ceil(2);
+                for (plane, dst, src) in [("u", &dst_u, &u), ("v", &dst_v, &v)] {
+                    assert_eq!(
+                        dst,
+                        &reference_chroma(
+                            src,
+                            chroma_width,
+                            width,
+                            height,
+                            (true, false),
+                            orientation,
+                            average_u8
+                        ),
+                        "{plane} {context}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Compares a packed to planar rotation for every orientation against the scalar reference,
+/// with chroma kept at full resolution or halved to 4:2:0 when `to_i420`.
+fn check_unpacked(layout: [usize; 4], to_i420: bool, rotate: UnpackedRotate) {
+    for width in YUV_SIZES {
+        for height in YUV_SIZES {
+            let (src, src_stride) = source_frame(width, height);
+            let [y, u, v] = unpack(&src, src_stride, width, height, layout);
+            let chroma_width = width.div_ceil(2);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let (mut chroma_dst_width, mut chroma_dst_height) =
+                    oriented_size(chroma_width, height, orientation);
+                let reference = |src: &[u8]| {
+                    let oriented =
+                        reference_orient(src, chroma_width, chroma_width, height, 1, orientation);
+                    if to_i420 {
+                        reference_halve(
+                            &oriented,
+                            chroma_dst_width,
+                            chroma_dst_height,
+                            orientation.is_transposed(),
+                        )
+                        .0
+                    } else {
+                        oriented
+                    }
+                };
+                let (reference_u, reference_v) = (reference(&u), reference(&v));
+                if to_i420 {
+                    (chroma_dst_width, chroma_dst_height) =
+                        (dst_width.div_ceil(2), dst_height.div_ceil(2));
+                }
+                let dst_stride_y = dst_width + 2;
+                let dst_stride_c = chroma_dst_width + 1;
+                let mut dst_y = vec![0u8; dst_stride_y * dst_height];
+                let mut dst_u = vec![0u8; dst_stride_c * chroma_dst_height];
+                let mut dst_v = vec![0u8; dst_stride_c * chroma_dst_height];
+                rotate(
+                    &src,
+                    src_stride,
+                    &mut dst_y,
+                    dst_stride_y,
+                    &mut dst_u,
+                    dst_stride_c,
+                    &mut dst_v,
+                    dst_stride_c,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                let context = format!("{width}x{height} {orientation:?}");
+                assert_eq!(
+                    packed_rows(&dst_y, dst_stride_y, dst_width, dst_height),
+                    reference_orient(&y, width, width, height, 1, orientation),
+                    "luma {context}"
+                );
+                assert_eq!(
+                    packed_rows(&dst_u, dst_stride_c, chroma_dst_width, chroma_dst_height),
+                    reference_u,
+                    "u {context}"
+                );
+                assert_eq!(
+                    packed_rows(&dst_v, dst_stride_c, chroma_dst_width, chroma_dst_height),
+                    reference_v,
+                    "v {context}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn yuy2_matches_reference() {
+    check_packed(YUY2, &rotate_yuy2);
+}
+
+#[test]
+fn uyvy_matches_reference() {
+    check_packed(UYVY, &rotate_uyvy);
+}
+
+#[test]
+fn yuy2_to_planar_matches_reference() {
+    check_unpacked(YUY2, false, &rotate_yuy2_to_planar);
+}
+
+#[test]
+fn uyvy_to_planar_matches_reference() {
+    check_unpacked(UYVY, false, &rotate_uyvy_to_planar);
+}
+
+#[test]
+fn yuy2_to_i420_matches_reference() {
+    check_unpacked(YUY2, true, &rotate_yuy2_to_i420);
+}
+
+#[test]
+fn uyvy_to_i420_matches_reference() {
+    check_unpacked(UYVY, true, &rotate_uyvy_to_i420);
+}
+
+/// Plane and sample index of every 10-bit slot of a V210 group, three slots per word.
+const V210_SLOTS: [(usize, usize); 12] = [
+    (1, 0),
+    (0, 0),
+    (2, 0),
+    (0, 1),
+    (1, 1),
+    (0, 2),
+    (2, 1),
+    (0, 3),
+    (1, 2),
+    (0, 4),
+    (2, 2),
+    (0, 5),
+];
+
+/// Splits rows of V210 groups into luma and 4:2:2 chroma rows padded to whole groups.
+fn unpack_v210(src: &[u8], stride: usize, width: usize, height: usize) -> [Vec<u16>; 3] {
+    let groups = width.div_ceil(6);
+    let mut planes = [
+        vec![0u16; groups * 6 * height],
+        vec![0u16; groups * 3 * height],
+        vec![0u16; groups * 3 * height],
+    ];
+    for y in 0..height {
+        for group in 0..groups {
+            for (slot, &(plane, index)) in V210_SLOTS.iter().enumerate() {
+                let offset = y * stride + group * 16 + slot / 3 * 4;
+                let word = u32::from_le_bytes(src[offset..offset + 4].try_into().unwrap());
+                let row_length = if plane == 0 { groups * 6 } else { groups * 3 };
+                let per_group = row_length / groups;
+                planes[plane][y * row_length + group * per_group + index] =
+                    ((word >> (slot % 3 * 10)) & 0x3ff) as u16;
+            }
+        }
+    }
+    planes
+}
+
+#[test]
+fn v210_matches_reference() {
+    for width in YUV_SIZES.into_iter().chain([6, 12, 13]) {
+        for height in YUV_SIZES {
+            let src_stride = width.div_ceil(6) * 16 + 4;
+            let src = random_samples(src_stride * height, (width * 131 + height) as u32, |v| {
+                v as u8
+            });
+            let [y, u, v] = unpack_v210(&src, src_stride, width, height);
+            let (luma_stride, chroma_stride) = (width.div_ceil(6) * 6, width.div_ceil(6) * 3);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let dst_groups = dst_width.div_ceil(6);
+                let dst_stride = dst_groups * 16 + 16;
+                let mut dst = vec![0xffu8; dst_stride * dst_height];
+                rotate_v210(
+                    &src,
+                    src_stride,
+                    &mut dst,
+                    dst_stride,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                let context = format!("{width}x{height} {orientation:?}");
+                let [dst_y, dst_u, dst_v] = unpack_v210(&dst, dst_stride, dst_width, dst_height);
+                let dst_chroma_width = dst_width.div_ceil(2);
+                let padded = |plane: &[u16], row_length: usize, reference: Vec<u16>| {
+                    let per_row = plane.len() / dst_height;
+                    let mut expected = vec![0u16; plane.len()];
+                    for (dst, src) in expected
+                        .chunks_exact_mut(per_row)
+                        .zip(reference.chunks_exact(row_length))
+                    {
+                        dst[..row_length].copy_from_slice(src);
+                    }
+                    expected
+                };
+                assert_eq!(
+                    dst_y,
+                    padded(
+                        &dst_y,
+                        dst_width,
+                        reference_orient(&y, luma_stride, width, height, 1, orientation)
+                    ),
+                    "luma {context}"
+                );
+                for (plane, dst, src) in [("u", &dst_u, &u), ("v", &dst_v, &v)] {
+                    let reference = reference_chroma(
+                        src,
+                        chroma_stride,
+                        width,
+                        height,
+                        (true, false),
+                        orientation,
+                        average_u16,
+                    );
+                    assert_eq!(
+                        dst,
+                        &padded(dst, dst_chroma_width, reference),
+                        "{plane} {context}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_frames_are_accepted() {
+    for orientation in ORIENTATIONS {
+        for (width, height) in [(0, 0), (0, 3), (3, 0)] {
+            let (dst_width, dst_height) = oriented_size(width, height, orientation);
+            let src_stride = width.div_ceil(2) * 4;
+            let dst_stride = dst_width.div_ceil(2) * 4;
+            let src = vec![0u8; src_stride * height];
+            let mut dst = vec![0u8; dst_stride * dst_height];
+            let context = format!("{width}x{height} {orientation:?}");
+            assert_eq!(
+                rotate_yuy2(
+                    &src,
+                    src_stride,
+                    &mut dst,
+                    dst_stride,
+                    width,
+                    height,
+                    orientation
+                ),
+                Ok(()),
+                "{context}"
+            );
+            let v210_stride = dst_width.div_ceil(6) * 16;
+            let mut v210 = vec![0u8; v210_stride * dst_height];
+            assert_eq!(
+                rotate_v210(
+                    &vec![0u8; width.div_ceil(6) * 16 * height],
+                    width.div_ceil(6) * 16,
+                    &mut v210,
+                    v210_stride,
+                    width,
+                    height,
+                    orientation
+                ),
+                Ok(()),
+                "{context}"
+            );
+            assert_eq!(
+                rotate_uyvy_to_i420(
+                    &src,
+                    src_stride,
+                    &mut [],
+                    dst_width,
+                    &mut [],
+                    dst_width.div_ceil(2),
+                    &mut [],
+                    dst_width.div_ceil(2),
+                    width,
+                    height,
+                    orientation
+                ),
+                Ok(()),
+                "{context}"
+            );
+        }
+    }
+}
+
+#[test]
+fn mismatched_chroma_leaves_luma_untouched() {
+    let (width, height) = (8, 6);
+    let src = vec![7u8; width * 2 * height];
+    for orientation in ORIENTATIONS {
+        let (dst_width, dst_height) = oriented_size(width, height, orientation);
+        let mut dst_y = vec![0u8; dst_width * dst_height];
+        let mut dst_u = vec![0u8; dst_width * dst_height];
+        let mut dst_v = vec![0u8; 1];
+        let context = format!("{orientation:?}");
+        assert_eq!(
+            rotate_yuy2_to_planar(
+                &src,
+                width * 2,
+                &mut dst_y,
+                dst_width,
+                &mut dst_u,
+                dst_width.div_ceil(2),
+                &mut dst_v,
+                dst_width.div_ceil(2),
+                width,
+                height,
+                orientation
+            ),
+            Err(TransposeError::MismatchDimensions),
+            "{context}"
+        );
+        assert_eq!(
+            rotate_uyvy_to_i420(
+                &src,
+                width * 2,
+                &mut dst_y,
+                dst_width,
+                &mut dst_u,
+                dst_width.div_ceil(2),
+                &mut dst_v,
+                dst_width.div_ceil(2),
+                width,
+                height,
+                orientation
+            ),
+            Err(TransposeError::MismatchDimensions),
+            "{context}"
+        );
+        assert!(
+            dst_y.iter().chain(dst_u.iter()).all(|&v| v == 0),
+            "{context}"
+        );
+    }
+}
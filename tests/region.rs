@@ -0,0 +1,194 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    random_samples, reference_mirror, reference_transpose, Mirror, Transposer, FLIP_MODES,
+    FLOP_MODES,
+};
+use fast_transpose::*;
+
+const WIDTH: usize = 53;
+const HEIGHT: usize = 41;
+
+const REGIONS: [Rect; 6] = [
+    Rect::new(0, 0, WIDTH, HEIGHT),
+    Rect::new(0, 0, 17, 9),
+    Rect::new(3, 5, 33, 20),
+    Rect::new(WIDTH - 19, HEIGHT - 16, 19, 16),
+    Rect::new(7, 0, 1, HEIGHT),
+    Rect::new(0, 11, WIDTH, 1),
+];
+
+/// Copies region out of the image, so references run on a packed image.
+fn copy_region<V: Copy>(image: &[V], stride: usize, rect: Rect, channels: usize) -> Vec<V> {
+    image
+        .chunks(stride)
+        .skip(rect.y)
+        .take(rect.height)
+        .flat_map(|row| &row[rect.x * channels..(rect.x + rect.width) * channels])
+        .copied()
+        .collect()
+}
+
+/// Transposes every region of a padded image through `transposer` and compares it with
+/// [reference_transpose] applied to a packed copy of the region.
+fn check_transposed_regions<V: Copy + Default + PartialEq + std::fmt::Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    transposer: Transposer<V>,
+) {
+    let stride = (WIDTH + 1) * channels;
+    let image = random_samples(stride * HEIGHT, 5, sample);
+    for rect in REGIONS {
+        let region = copy_region(&image, stride, rect, channels);
+        let view = rect.crop(&image, stride, WIDTH, HEIGHT, channels).unwrap();
+        for flip_mode in FLIP_MODES {
+            for flop_mode in FLOP_MODES {
+                let mut output = vec![V::default(); rect.width * rect.height * channels];
+                transposer(
+                    view,
+                    stride,
+                    &mut output,
+                    rect.height * channels,
+                    rect.width,
+                    rect.height,
+                    flip_mode,
+                    flop_mode,
+                )
+                .unwrap();
+                let reference = reference_transpose(
+                    &region,
+                    rect.width * channels,
+                    rect.width,
+                    rect.height,
+                    channels,
+                    flip_mode,
+                    flop_mode,
+                );
+                assert!(
+                    output == reference,
+                    "{rect:?}, {channels} channels, {flip_mode:?}, {flop_mode:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn transposed_region_matches_reference() {
+    check_transposed_regions(4, |v| v as u8, transpose_rgba);
+    check_transposed_regions(1, |v| v as u8, transpose_plane);
+    check_transposed_regions(1, |v| v as u16, transpose_plane16);
+    check_transposed_regions(1, |v| v as f32, transpose_plane_f32);
+    check_transposed_regions(4, |v| v as f32, transpose_rgba_f32);
+}
+
+#[test]
+fn mirrored_region_matches_reference() {
+    let stride = WIDTH + 3;
+    let image = random_samples(stride * HEIGHT, 6, |v| v as u16);
+    for rect in REGIONS {
+        let region = copy_region(&image, stride, rect, 1);
+        let view = rect.crop(&image, stride, WIDTH, HEIGHT, 1).unwrap();
+        let mirrors: [(Mirror<u16>, bool, bool); 3] = [
+            (flip_plane16, true, false),
+            (flop_plane16, false, true),
+            (rotate180_plane16, true, true),
+        ];
+        for (mirror, reverse_x, reverse_y) in mirrors {
+            let mut output = vec![0u16; rect.width * rect.height];
+            mirror(
+                view,
+                stride,
+                &mut output,
+                rect.width,
+                rect.width,
+                rect.height,
+            )
+            .unwrap();
+            let reference = reference_mirror(
+                &region,
+                rect.width,
+                rect.width,
+                rect.height,
+                1,
+                reverse_x,
+                reverse_y,
+            );
+            assert!(output == reference, "{rect:?}, {reverse_x}, {reverse_y}");
+        }
+    }
+}
+
+#[test]
+fn region_outside_image_is_rejected() {
+    let image = vec![0u8; WIDTH * HEIGHT];
+    for rect in [
+        Rect::new(1, 0, WIDTH, 1),
+        Rect::new(0, HEIGHT, 1, 1),
+        Rect::new(WIDTH - 2, 3, 3, 3),
+    ] {
+        assert_eq!(
+            rect.crop(&image, WIDTH, WIDTH, HEIGHT, 1),
+            Err(TransposeError::MismatchDimensions)
+        );
+    }
+    assert_eq!(
+        Rect::new(0, 0, 1, 1).crop(&image[1..], WIDTH, WIDTH, HEIGHT, 1),
+        Err(TransposeError::MismatchDimensions)
+    );
+    // Offsets that overflow when added to the region size are rejected rather than wrapping.
+    for rect in [
+        Rect::new(usize::MAX, 0, 2, 1),
+        Rect::new(0, usize::MAX, 1, 2),
+    ] {
+        assert_eq!(
+            rect.crop(&image, WIDTH, WIDTH, HEIGHT, 1),
+            Err(TransposeError::MismatchDimensions)
+        );
+    }
+}
+
+#[test]
+fn crop_accepts_image_ending_after_last_row() {
+    // A view returned by crop is itself a valid image, so regions can be cropped again.
+    let stride = WIDTH + 5;
+    let image = random_samples(stride * HEIGHT, 7, |v| v as u8);
+    let outer = Rect::new(4, 3, 30, 20);
+    let view = outer.crop(&image, stride, WIDTH, HEIGHT, 1).unwrap();
+    let inner = Rect::new(2, 1, 28, 19);
+    let nested = inner
+        .crop(view, stride, outer.width, outer.height, 1)
+        .unwrap();
+    let direct = Rect::new(6, 4, 28, 19)
+        .crop(&image, stride, WIDTH, HEIGHT, 1)
+        .unwrap();
+    assert_eq!(nested, direct);
+}
@@ -0,0 +1,60 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::check_mirror;
+use fast_transpose::*;
+
+#[test]
+fn rotate180_matches_reference() {
+    check_mirror(1, |v| v as u8, rotate180_plane, true, true);
+    check_mirror(2, |v| v as u8, rotate180_plane_with_alpha, true, true);
+    check_mirror(3, |v| v as u8, rotate180_rgb, true, true);
+    check_mirror(4, |v| v as u8, rotate180_rgba, true, true);
+    check_mirror(1, |v| v as u16, rotate180_plane16, true, true);
+    check_mirror(2, |v| v as u16, rotate180_plane16_with_alpha, true, true);
+    check_mirror(3, |v| v as u16, rotate180_rgb16, true, true);
+    check_mirror(4, |v| v as u16, rotate180_rgba16, true, true);
+    check_mirror(1, |v| v as f32, rotate180_plane_f32, true, true);
+    check_mirror(2, |v| v as f32, rotate180_plane_f32_with_alpha, true, true);
+    check_mirror(3, |v| v as f32, rotate180_rgb_f32, true, true);
+    check_mirror(4, |v| v as f32, rotate180_rgba_f32, true, true);
+    check_mirror(1, |v| v as u8, rotate180_arbitrary, true, true);
+}
+
+#[test]
+fn rotate180_packed_image_spanning_many_cache_lines() {
+    // Packed images are reversed as one buffer in cache line sized chunks.
+    let (width, height) = (1021, 37);
+    let input = common::random_samples(width * height * 4, 3, |v| v as u8);
+    let mut output = vec![0u8; input.len()];
+    rotate180_rgba(&input, width * 4, &mut output, width * 4, width, height).unwrap();
+    let reference = common::reference_mirror(&input, width * 4, width, height, 4, true, true);
+    assert!(output == reference);
+}
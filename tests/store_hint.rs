@@ -0,0 +1,159 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{check_transpose, random_samples, reference_transpose, SIZES};
+use fast_transpose::*;
+
+/// Binds `store_hint` to a hinted transpose so it fits [common::Transposer].
+macro_rules! hinted {
+    ($transposer: ident, $store_hint: ident) => {
+        |input, input_stride, output, output_stride, width, height, flip_mode, flop_mode| {
+            $transposer(
+                input,
+                input_stride,
+                output,
+                output_stride,
+                width,
+                height,
+                flip_mode,
+                flop_mode,
+                StoreHint::$store_hint,
+            )
+        }
+    };
+}
+
+#[test]
+fn non_temporal_matches_reference() {
+    let heights = [1, 7, 16, 17, 64, 67, 130];
+    check_transpose(
+        &SIZES,
+        &heights,
+        1,
+        |v| v as u8,
+        hinted!(transpose_plane_hinted, NonTemporal),
+    );
+    check_transpose(
+        &SIZES,
+        &heights,
+        1,
+        |v| v as u16,
+        hinted!(transpose_plane16_hinted, NonTemporal),
+    );
+    check_transpose(
+        &SIZES,
+        &heights,
+        4,
+        |v| v as u8,
+        hinted!(transpose_rgba_hinted, NonTemporal),
+    );
+    check_transpose(
+        &SIZES,
+        &heights,
+        1,
+        |v| v as f32,
+        hinted!(transpose_plane_f32_hinted, NonTemporal),
+    );
+}
+
+#[test]
+fn non_temporal_spans_several_tiles() {
+    // Wide and tall enough for streamed bands to be grouped into more than one tile each way.
+    let (widths, heights) = ([517], [389]);
+    check_transpose(
+        &widths,
+        &heights,
+        1,
+        |v| v as u8,
+        hinted!(transpose_plane_hinted, NonTemporal),
+    );
+    check_transpose(
+        &widths,
+        &heights,
+        4,
+        |v| v as u8,
+        hinted!(transpose_rgba_hinted, NonTemporal),
+    );
+    check_transpose(
+        &widths,
+        &heights,
+        1,
+        |v| v as f32,
+        hinted!(transpose_plane_f32_hinted, NonTemporal),
+    );
+}
+
+#[test]
+fn auto_keeps_small_destinations_in_cache() {
+    check_transpose(
+        &SIZES,
+        &SIZES,
+        4,
+        |v| v as u8,
+        hinted!(transpose_rgba_hinted, Auto),
+    );
+}
+
+#[test]
+fn non_temporal_into_unaligned_destination() {
+    let (width, height) = (35, 131);
+    let input = random_samples(width * height * 4, 11, |v| v as u8);
+    let reference = reference_transpose(
+        &input,
+        width * 4,
+        width,
+        height,
+        4,
+        FlipMode::NoFlip,
+        FlopMode::Flop,
+    );
+    // Destination rows start one byte past alignment and have an odd stride.
+    let output_stride = height * 4 + 3;
+    let mut canvas = vec![0u8; output_stride * width + 1];
+    let output = &mut canvas[1..];
+    transpose_rgba_hinted(
+        &input,
+        width * 4,
+        output,
+        output_stride,
+        width,
+        height,
+        FlipMode::NoFlip,
+        FlopMode::Flop,
+        StoreHint::NonTemporal,
+    )
+    .unwrap();
+    for (row, expected) in output
+        .chunks(output_stride)
+        .zip(reference.chunks(height * 4))
+    {
+        assert_eq!(&row[..height * 4], expected);
+    }
+}
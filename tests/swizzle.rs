@@ -0,0 +1,236 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{random_samples, reference_mirror, reference_transpose, FLIP_MODES, FLOP_MODES};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+const ORDERS: [ChannelOrder; 6] = [
+    ChannelOrder::Rgba,
+    ChannelOrder::Bgra,
+    ChannelOrder::Argb,
+    ChannelOrder::Abgr,
+    ChannelOrder::Rgb,
+    ChannelOrder::Bgr,
+];
+
+// Around 4x4 blocks of the reordering kernels and strips of blocks.
+const SIZES: [usize; 9] = [1, 3, 4, 5, 8, 9, 17, 33, 70];
+
+/// Components stored in each channel, 0 is red, 1 green, 2 blue and 3 alpha.
+fn components(order: ChannelOrder) -> &'static [usize] {
+    match order {
+        ChannelOrder::Rgba => &[0, 1, 2, 3],
+        ChannelOrder::Bgra => &[2, 1, 0, 3],
+        ChannelOrder::Argb => &[3, 0, 1, 2],
+        ChannelOrder::Abgr => &[3, 2, 1, 0],
+        ChannelOrder::Rgb => &[0, 1, 2],
+        ChannelOrder::Bgr => &[2, 1, 0],
+    }
+}
+
+/// Reorders every pixel of packed `pixels` one component at a time.
+fn reorder<V: Copy>(
+    pixels: &[V],
+    src_order: ChannelOrder,
+    dst_order: ChannelOrder,
+    opaque: V,
+) -> Vec<V> {
+    let src = components(src_order);
+    let dst = components(dst_order);
+    pixels
+        .chunks_exact(src.len())
+        .flat_map(|px| {
+            dst.iter().map(move |component| {
+                src.iter()
+                    .position(|c| c == component)
+                    .map_or(opaque, |i| px[i])
+            })
+        })
+        .collect()
+}
+
+type SwizzledTransposer<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    ChannelOrder,
+    ChannelOrder,
+    FlipMode,
+    FlopMode,
+) -> Result<(), TransposeError>;
+
+type SwizzledMirror<V> = fn(
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    ChannelOrder,
+    ChannelOrder,
+) -> Result<(), TransposeError>;
+
+fn check_transpose_swizzled<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    opaque: V,
+    transposer: SwizzledTransposer<V>,
+) {
+    for src_order in ORDERS {
+        for dst_order in ORDERS {
+            let (src_channels, dst_channels) = (src_order.channels(), dst_order.channels());
+            for width in SIZES {
+                for height in SIZES {
+                    let input_stride = (width + 1) * src_channels;
+                    let seed = (width * 131 + height) as u32;
+                    let input = random_samples(input_stride * height, seed, &sample);
+                    for flip_mode in FLIP_MODES {
+                        for flop_mode in FLOP_MODES {
+                            let output_stride = height * dst_channels;
+                            let mut output = vec![V::default(); output_stride * width];
+                            transposer(
+                                &input,
+                                input_stride,
+                                &mut output,
+                                output_stride,
+                                width,
+                                height,
+                                src_order,
+                                dst_order,
+                                flip_mode,
+                                flop_mode,
+                            )
+                            .unwrap();
+                            let transposed = reference_transpose(
+                                &input,
+                                input_stride,
+                                width,
+                                height,
+                                src_channels,
+                                flip_mode,
+                                flop_mode,
+                            );
+                            let reference = reorder(&transposed, src_order, dst_order, opaque);
+                            assert!(
+                                output == reference,
+                                "{width}x{height}, {src_order:?} to {dst_order:?}, {flip_mode:?}, {flop_mode:?}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_mirror_swizzled<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    opaque: V,
+    mirror: SwizzledMirror<V>,
+    reverse_x: bool,
+    reverse_y: bool,
+) {
+    for src_order in ORDERS {
+        for dst_order in ORDERS {
+            let (src_channels, dst_channels) = (src_order.channels(), dst_order.channels());
+            for width in SIZES {
+                for height in [1, 2, 3, 8] {
+                    let input_stride = (width + 1) * src_channels;
+                    let seed = (width * 7 + height) as u32;
+                    let input = random_samples(input_stride * height, seed, &sample);
+                    let output_stride = width * dst_channels;
+                    let mut output = vec![V::default(); output_stride * height];
+                    mirror(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        src_order,
+                        dst_order,
+                    )
+                    .unwrap();
+                    let mirrored = reference_mirror(
+                        &input,
+                        input_stride,
+                        width,
+                        height,
+                        src_channels,
+                        reverse_x,
+                        reverse_y,
+                    );
+                    let reference = reorder(&mirrored, src_order, dst_order, opaque);
+                    assert!(
+                        output == reference,
+                        "{width}x{height}, {src_order:?} to {dst_order:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn transpose_swizzled_matches_reference() {
+    check_transpose_swizzled(|v| v as u8, u8::MAX, transpose_rgba_swizzled);
+    check_transpose_swizzled(|v| v as u16, u16::MAX, transpose_rgba16_swizzled);
+    check_transpose_swizzled(|v| v as f32, 1., transpose_rgba_f32_swizzled);
+}
+
+#[test]
+fn flip_swizzled_matches_reference() {
+    check_mirror_swizzled(|v| v as u8, u8::MAX, flip_rgba_swizzled, true, false);
+    check_mirror_swizzled(|v| v as u16, u16::MAX, flip_rgba16_swizzled, true, false);
+    check_mirror_swizzled(|v| v as f32, 1., flip_rgba_f32_swizzled, true, false);
+}
+
+#[test]
+fn flop_swizzled_matches_reference() {
+    check_mirror_swizzled(|v| v as u8, u8::MAX, flop_rgba_swizzled, false, true);
+    check_mirror_swizzled(|v| v as u16, u16::MAX, flop_rgba16_swizzled, false, true);
+    check_mirror_swizzled(|v| v as f32, 1., flop_rgba_f32_swizzled, false, true);
+}
+
+#[test]
+fn rotate180_swizzled_matches_reference() {
+    check_mirror_swizzled(|v| v as u8, u8::MAX, rotate180_rgba_swizzled, true, true);
+    check_mirror_swizzled(
+        |v| v as u16,
+        u16::MAX,
+        rotate180_rgba16_swizzled,
+        true,
+        true,
+    );
+    check_mirror_swizzled(|v| v as f32, 1., rotate180_rgba_f32_swizzled, true, true);
+}
@@ -0,0 +1,189 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{oriented_size, packed_rows, random_samples, reference_orient, ORIENTATIONS};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+type ToTiled<V> =
+    fn(&[V], usize, &mut [V], usize, usize, TileLayout, Orientation) -> Result<(), TransposeError>;
+
+type FromTiled<V> =
+    fn(&[V], &mut [V], usize, usize, usize, TileLayout, Orientation) -> Result<(), TransposeError>;
+
+/// Widths and heights around tile sizes and the 64 x 256 pixel blocks staged at once.
+const TILED_WIDTHS: [usize; 6] = [1, 3, 8, 17, 65, 259];
+const TILED_HEIGHTS: [usize; 5] = [1, 2, 9, 33, 70];
+
+const LAYOUTS: [TileLayout; 6] = [
+    TileLayout::Tiled { size: 4 },
+    TileLayout::Tiled { size: 5 },
+    TileLayout::Tiled { size: 64 },
+    TileLayout::Morton { size: 1 },
+    TileLayout::Morton { size: 8 },
+    TileLayout::Morton { size: 32 },
+];
+
+/// Offset of pixel `(x, y)` in a tiled buffer of an image `width` pixels wide.
+fn tiled_offset(x: usize, y: usize, width: usize, layout: TileLayout, channels: usize) -> usize {
+    let size = layout.size();
+    let tile = (y / size) * width.div_ceil(size) + x / size;
+    let (x, y) = (x % size, y % size);
+    let inner = match layout {
+        TileLayout::Tiled { .. } => y * size + x,
+        TileLayout::Morton { .. } => (0..usize::BITS as usize / 2).fold(0, |acc, bit| {
+            acc | (((x >> bit) & 1) << (bit * 2)) | (((y >> bit) & 1) << (bit * 2 + 1))
+        }),
+    };
+    (tile * size * size + inner) * channels
+}
+
+fn check_to_tiled<V: Copy + Default + PartialEq + Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    padding: V,
+    rotate: ToTiled<V>,
+) {
+    for width in TILED_WIDTHS {
+        for height in TILED_HEIGHTS {
+            let input_stride = (width + 1) * channels;
+            let input = random_samples(
+                input_stride * height,
+                (width * 131 + height) as u32,
+                &sample,
+            );
+            for layout in LAYOUTS {
+                for orientation in ORIENTATIONS {
+                    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                    let mut output =
+                        vec![padding; layout.buffer_len(dst_width, dst_height, channels)];
+                    rotate(
+                        &input,
+                        input_stride,
+                        &mut output,
+                        width,
+                        height,
+                        layout,
+                        orientation,
+                    )
+                    .unwrap();
+                    let oriented = reference_orient(
+                        &input,
+                        input_stride,
+                        width,
+                        height,
+                        channels,
+                        orientation,
+                    );
+                    let mut expected = vec![padding; output.len()];
+                    for (i, pixel) in oriented.chunks_exact(channels).enumerate() {
+                        let offset =
+                            tiled_offset(i % dst_width, i / dst_width, dst_width, layout, channels);
+                        expected[offset..offset + channels].copy_from_slice(pixel);
+                    }
+                    assert!(
+                        output == expected,
+                        "{width}x{height}, {channels} channels, {layout:?}, {orientation:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn check_from_tiled<V: Copy + Default + PartialEq + Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    rotate: FromTiled<V>,
+) {
+    for width in TILED_WIDTHS {
+        for height in TILED_HEIGHTS {
+            for layout in LAYOUTS {
+                let input = random_samples(
+                    layout.buffer_len(width, height, channels),
+                    (width * 131 + height) as u32,
+                    &sample,
+                );
+                let mut linear = Vec::with_capacity(width * height * channels);
+                for y in 0..height {
+                    for x in 0..width {
+                        let offset = tiled_offset(x, y, width, layout, channels);
+                        linear.extend_from_slice(&input[offset..offset + channels]);
+                    }
+                }
+                for orientation in ORIENTATIONS {
+                    let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                    let output_stride = (dst_width + 2) * channels;
+                    let mut output = vec![V::default(); output_stride * dst_height];
+                    rotate(
+                        &input,
+                        &mut output,
+                        output_stride,
+                        width,
+                        height,
+                        layout,
+                        orientation,
+                    )
+                    .unwrap();
+                    assert!(
+                        packed_rows(&output, output_stride, dst_width * channels, dst_height)
+                            == reference_orient(
+                                &linear,
+                                width * channels,
+                                width,
+                                height,
+                                channels,
+                                orientation
+                            ),
+                        "{width}x{height}, {channels} channels, {layout:?}, {orientation:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn to_tiled_matches_reference() {
+    check_to_tiled(1, |v| v as u8, 0xa5, rotate_to_tiled::<1>);
+    check_to_tiled(3, |v| v as u8, 0xa5, rotate_to_tiled::<3>);
+    check_to_tiled(4, |v| v as u8, 0xa5, rotate_to_tiled::<4>);
+    check_to_tiled(2, |v| v as u16, 0xa5a5, rotate_to_tiled16::<2>);
+    check_to_tiled(1, |v| v as f32, -1., rotate_to_tiled_f32::<1>);
+}
+
+#[test]
+fn from_tiled_matches_reference() {
+    check_from_tiled(1, |v| v as u8, rotate_from_tiled::<1>);
+    check_from_tiled(3, |v| v as u8, rotate_from_tiled::<3>);
+    check_from_tiled(4, |v| v as u8, rotate_from_tiled::<4>);
+    check_from_tiled(2, |v| v as u16, rotate_from_tiled16::<2>);
+    check_from_tiled(1, |v| v as f32, rotate_from_tiled_f32::<1>);
+}
@@ -0,0 +1,58 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::check_transpose;
+use fast_transpose::*;
+
+// Sizes cross several L2 tiles of blocks in both directions, stopping off tile edges.
+const WIDE: [usize; 3] = [40, 300, 1027];
+const TALL: [usize; 3] = [33, 517, 1100];
+
+#[test]
+fn tiled_traversal_matches_reference() {
+    check_transpose(&WIDE, &TALL, 1, |v| v as u8, transpose_plane);
+    check_transpose(&TALL, &WIDE, 4, |v| v as u8, transpose_rgba);
+    check_transpose(&WIDE, &TALL, 1, |v| v as u16, transpose_plane16);
+    check_transpose(&TALL, &WIDE, 1, |v| v as f32, transpose_plane_f32);
+}
+
+#[test]
+fn arbitrary_transpose_matches_reference() {
+    check_transpose(&WIDE, &TALL, 1, |v| v as u8, transpose_arbitrary);
+    check_transpose(
+        &[5, 300],
+        &[3, 517],
+        3,
+        |v| v as u16,
+        |i, is, o, os, w, h, fp, fl| {
+            transpose_arbitrary_grouped::<u16, 3>(i, is, o, os, w, h, fp, fl)
+        },
+    );
+}
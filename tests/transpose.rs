@@ -0,0 +1,154 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    check_transpose, random_samples, reference_transpose, Transposer, FLIP_MODES, FLOP_MODES, SIZES,
+};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+/// Runs `transposer` on sub-slices starting one sample into their buffers, so pixels of
+/// multi-byte layouts are not aligned to their size and rows are only sample aligned.
+fn check_misaligned<V: Copy + Default + PartialEq + Debug>(
+    channels: usize,
+    sample: impl Fn(u32) -> V,
+    transposer: Transposer<V>,
+) {
+    for (width, height) in [(1, 1), (7, 5), (33, 17), (70, 9)] {
+        let input_stride = width * channels + 1;
+        let output_stride = height * channels + 1;
+        let input = random_samples(input_stride * height + 1, width as u32, &sample);
+        for flip_mode in FLIP_MODES {
+            for flop_mode in FLOP_MODES {
+                let mut output = vec![V::default(); output_stride * width + 1];
+                transposer(
+                    &input[1..],
+                    input_stride,
+                    &mut output[1..],
+                    output_stride,
+                    width,
+                    height,
+                    flip_mode,
+                    flop_mode,
+                )
+                .unwrap();
+                let reference = reference_transpose(
+                    &input[1..],
+                    input_stride,
+                    width,
+                    height,
+                    channels,
+                    flip_mode,
+                    flop_mode,
+                );
+                for x in 0..width {
+                    let row = 1 + x * output_stride;
+                    assert!(
+                        output[row..row + height * channels]
+                            == reference[x * height * channels..(x + 1) * height * channels],
+                        "{width}x{height}, {channels} channels, {flip_mode:?}, {flop_mode:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn remainder_columns_without_flop() {
+    // Widths that leave a partial column of blocks for every block size in use.
+    let widths = [17, 18, 21, 25, 31, 33, 37, 47];
+    check_transpose(&widths, &[16, 32, 64], 4, |v| v as u8, transpose_rgba);
+    check_transpose(&widths, &[16, 32, 64], 1, |v| v as u8, transpose_plane);
+    check_transpose(&widths, &[16, 32, 64], 4, |v| v as u16, transpose_rgba16);
+    check_transpose(&widths, &[16, 32, 64], 1, |v| v as f32, transpose_plane_f32);
+}
+
+#[test]
+fn u8_matches_reference() {
+    check_transpose(&SIZES, &SIZES, 1, |v| v as u8, transpose_plane);
+    check_transpose(&SIZES, &SIZES, 2, |v| v as u8, transpose_plane_with_alpha);
+    check_transpose(&SIZES, &SIZES, 3, |v| v as u8, transpose_rgb);
+    check_transpose(&SIZES, &SIZES, 4, |v| v as u8, transpose_rgba);
+}
+
+#[test]
+fn u16_matches_reference() {
+    check_transpose(&SIZES, &SIZES, 1, |v| v as u16, transpose_plane16);
+    check_transpose(
+        &SIZES,
+        &SIZES,
+        2,
+        |v| v as u16,
+        transpose_plane16_with_alpha,
+    );
+    check_transpose(&SIZES, &SIZES, 3, |v| v as u16, transpose_rgb16);
+    check_transpose(&SIZES, &SIZES, 4, |v| v as u16, transpose_rgba16);
+}
+
+#[test]
+fn f32_matches_reference() {
+    check_transpose(&SIZES, &SIZES, 1, |v| v as f32, transpose_plane_f32);
+    check_transpose(
+        &SIZES,
+        &SIZES,
+        2,
+        |v| v as f32,
+        transpose_plane_f32_with_alpha,
+    );
+    check_transpose(&SIZES, &SIZES, 3, |v| v as f32, transpose_rgb_f32);
+    check_transpose(&SIZES, &SIZES, 4, |v| v as f32, transpose_rgba_f32);
+}
+
+#[test]
+fn block_size_cascade() {
+    // Heights left over by the widest kernels are finished by narrower ones and then
+    // by the scalar section, so every kernel of a tier meets its neighbours here.
+    let sizes = [24, 27, 44, 59, 61];
+    check_transpose(&sizes, &sizes, 1, |v| v as u8, transpose_plane);
+    check_transpose(&sizes, &sizes, 2, |v| v as u8, transpose_plane_with_alpha);
+    check_transpose(&sizes, &sizes, 1, |v| v as u16, transpose_plane16);
+    check_transpose(&sizes, &sizes, 4, |v| v as u8, transpose_rgba);
+    check_transpose(&sizes, &sizes, 4, |v| v as u16, transpose_rgba16);
+    check_transpose(&sizes, &sizes, 1, |v| v as f32, transpose_plane_f32);
+}
+
+#[test]
+fn misaligned_sub_slices() {
+    check_misaligned(2, |v| v as u8, transpose_plane_with_alpha);
+    check_misaligned(4, |v| v as u8, transpose_rgba);
+    check_misaligned(1, |v| v as u16, transpose_plane16);
+    check_misaligned(2, |v| v as u16, transpose_plane16_with_alpha);
+    check_misaligned(3, |v| v as u16, transpose_rgb16);
+    check_misaligned(4, |v| v as u16, transpose_rgba16);
+    check_misaligned(2, |v| v as f32, transpose_plane_f32_with_alpha);
+    check_misaligned(3, |v| v as f32, transpose_rgb_f32);
+    check_misaligned(4, |v| v as f32, transpose_rgba_f32);
+}
@@ -0,0 +1,314 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+mod common;
+
+use common::{
+    average_u16, average_u8, oriented_size, packed_rows, random_samples, reference_chroma,
+    reference_orient, ORIENTATIONS, YUV_SIZES,
+};
+use fast_transpose::*;
+use std::fmt::Debug;
+
+type PlanarRotate<'a, V> = &'a dyn Fn(
+    &[V],
+    usize,
+    &[V],
+    usize,
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    &mut [V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    Orientation,
+) -> Result<(), TransposeError>;
+
+type BiplanarRotate<'a, V> = &'a dyn Fn(
+    &[V],
+    usize,
+    &[V],
+    usize,
+    &mut [V],
+    usize,
+    &mut [V],
+    usize,
+    usize,
+    usize,
+    Orientation,
+) -> Result<(), TransposeError>;
+
+/// Compares a three plane rotation for every orientation against the scalar reference.
+fn check_planar<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    subsampling: (bool, bool),
+    average: impl Fn(V, V) -> V + Copy,
+    rotate: PlanarRotate<V>,
+) {
+    let (sub_x, sub_y) = subsampling;
+    for width in YUV_SIZES {
+        for height in YUV_SIZES {
+            let chroma_width = if sub_x { width.div_ceil(2) } else { width };
+            let chroma_height = if sub_y { height.div_ceil(2) } else { height };
+            let seed = (width * 131 + height) as u32;
+            let src_stride_y = width + 3;
+            let src_stride_c = chroma_width + 1;
+            let src_y = random_samples(src_stride_y * height, seed, &sample);
+            let src_u = random_samples(src_stride_c * chroma_height, seed + 1, &sample);
+            let src_v = random_samples(src_stride_c * chroma_height, seed + 2, &sample);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let (dst_chroma_width, dst_chroma_height) =
+                    if sub_x && !sub_y && orientation.is_transposed() {
+                        (dst_width.div_ceil(2), dst_height)
+                    } else {
+                        oriented_size(chroma_width, chroma_height, orientation)
+                    };
+                let dst_stride_y = dst_width + 2;
+                let dst_stride_c = dst_chroma_width + 1;
+                let mut dst_y = vec![V::default(); dst_stride_y * dst_height];
+                let mut dst_u = vec![V::default(); dst_stride_c * dst_chroma_height];
+                let mut dst_v = vec![V::default(); dst_stride_c * dst_chroma_height];
+                rotate(
+                    &src_y,
+                    src_stride_y,
+                    &src_u,
+                    src_stride_c,
+                    &src_v,
+                    src_stride_c,
+                    &mut dst_y,
+                    dst_stride_y,
+                    &mut dst_u,
+                    dst_stride_c,
+                    &mut dst_v,
+                    dst_stride_c,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                let context = format!("{width}x{height} {orientation:?}");
+                assert_eq!(
+                    packed_rows(&dst_y, dst_stride_y, dst_width, dst_height),
+                    reference_orient(&src_y, src_stride_y, width, height, 1, orientation),
+                    "luma {context}"
+                );
+                for (plane, dst, src) in [("u", &dst_u, &src_u), ("v", &dst_v, &src_v)] {
+                    assert_eq!(
+                        packed_rows(dst, dst_stride_c, dst_chroma_width, dst_chroma_height),
+                        reference_chroma(
+                            src,
+                            src_stride_c,
+                            width,
+                            height,
+                            subsampling,
+                            orientation,
+                            average
+                        ),
+                        "{plane} {context}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Compares a luma and interleaved 4:2:0 chroma rotation for every orientation against the
+/// scalar reference.
+fn check_biplanar<V: Copy + Default + PartialEq + Debug>(
+    sample: impl Fn(u32) -> V,
+    rotate: BiplanarRotate<V>,
+) {
+    for width in YUV_SIZES {
+        for height in YUV_SIZES {
+            let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+            let seed = (width * 131 + height) as u32;
+            let src_stride_y = width + 3;
+            let src_stride_uv = (chroma_width + 1) * 2;
+            let src_y = random_samples(src_stride_y * height, seed, &sample);
+            let src_uv = random_samples(src_stride_uv * chroma_height, seed + 1, &sample);
+            for orientation in ORIENTATIONS {
+                let (dst_width, dst_height) = oriented_size(width, height, orientation);
+                let (dst_chroma_width, dst_chroma_height) =
+                    oriented_size(chroma_width, chroma_height, orientation);
+                let dst_stride_y = dst_width + 2;
+                let dst_stride_uv = (dst_chroma_width + 1) * 2;
+                let mut dst_y = vec![V::default(); dst_stride_y * dst_height];
+                let mut dst_uv = vec![V::default(); dst_stride_uv * dst_chroma_height];
+                rotate(
+                    &src_y,
+                    src_stride_y,
+                    &src_uv,
+                    src_stride_uv,
+                    &mut dst_y,
+                    dst_stride_y,
+                    &mut dst_uv,
+                    dst_stride_uv,
+                    width,
+                    height,
+                    orientation,
+                )
+                .unwrap();
+                let context = format!("{width}x{height} {orientation:?}");
+                assert_eq!(
+                    packed_rows(&dst_y, dst_stride_y, dst_width, dst_height),
+                    reference_orient(&src_y, src_stride_y, width, height, 1, orientation),
+                    "luma {context}"
+                );
+                assert_eq!(
+                    packed_rows(
+                        &dst_uv,
+                        dst_stride_uv,
+                        dst_chroma_width * 2,
+                        dst_chroma_height
+                    ),
+                    reference_orient(
+                        &src_uv,
+                        src_stride_uv,
+                        chroma_width,
+                        chroma_height,
+                        2,
+                        orientation
+                    ),
+                    "uv {context}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn i420_matches_reference() {
+    check_planar(|v| v as u8, (true, true), average_u8, &rotate_i420);
+}
+
+#[test]
+fn i422_matches_reference() {
+    check_planar(|v| v as u8, (true, false), average_u8, &rotate_i422);
+}
+
+#[test]
+fn i444_matches_reference() {
+    check_planar(|v| v as u8, (false, false), average_u8, &rotate_i444);
+}
+
+#[test]
+fn nv12_matches_reference() {
+    check_biplanar(|v| v as u8, &rotate_nv12);
+}
+
+#[test]
+fn p010_matches_reference() {
+    check_biplanar(|v| (v as u16) & 0xffc0, &rotate_p010);
+}
+
+#[test]
+fn p016_matches_reference() {
+    check_biplanar(|v| v as u16, &rotate_p016);
+}
+
+#[test]
+fn i010_matches_reference() {
+    check_planar(
+        |v| (v as u16) & 0x3ff,
+        (true, true),
+        average_u16,
+        &rotate_i010,
+    );
+}
+
+#[test]
+fn i012_matches_reference() {
+    check_planar(
+        |v| (v as u16) & 0xfff,
+        (true, true),
+        average_u16,
+        &rotate_i012,
+    );
+}
+
+#[test]
+fn mismatched_chroma_leaves_every_plane_untouched() {
+    let (width, height) = (8, 6);
+    let src_y = vec![7u8; width * height];
+    let planar: [(PlanarRotate<u8>, usize, usize); 3] = [
+        (&rotate_i420, 4, 3),
+        (&rotate_i422, 4, 6),
+        (&rotate_i444, 8, 6),
+    ];
+    for (rotate, chroma_width, chroma_height) in planar {
+        let src_chroma = vec![7u8; chroma_width * chroma_height];
+        // Short destination V plane, then source U stride below its chroma width.
+        for (src_stride_u, dst_v_len) in [(chroma_width, 1), (chroma_width - 1, height * width)] {
+            let mut dst_y = vec![0u8; width * height];
+            let mut dst_u = vec![0u8; width * height];
+            let mut dst_v = vec![0u8; dst_v_len];
+            let result = rotate(
+                &src_y,
+                width,
+                &src_chroma,
+                src_stride_u,
+                &src_chroma,
+                chroma_width,
+                &mut dst_y,
+                height,
+                &mut dst_u,
+                height,
+                &mut dst_v,
+                height,
+                width,
+                height,
+                Orientation::Rotate90,
+            );
+            assert_eq!(result, Err(TransposeError::MismatchDimensions));
+            assert!(dst_y.iter().chain(dst_u.iter()).all(|&v| v == 0));
+        }
+    }
+    let src_uv = vec![7u8; width * height / 2];
+    let mut dst_y = vec![0u8; width * height];
+    let mut dst_uv = vec![0u8; 1];
+    let result = rotate_nv12(
+        &src_y,
+        width,
+        &src_uv,
+        width,
+        &mut dst_y,
+        height,
+        &mut dst_uv,
+        height,
+        width,
+        height,
+        Orientation::Rotate90,
+    );
+    assert_eq!(result, Err(TransposeError::MismatchDimensions));
+    assert!(dst_y.iter().all(|&v| v == 0));
+}